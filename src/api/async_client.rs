@@ -0,0 +1,95 @@
+use crate::api::clients::Place;
+use crate::api::request::{self, Endpoint, Session};
+use crate::api::stats::{ApiStats, ErrorKind};
+use crate::models::forecast::ForecastResponse;
+use crate::models::geocoding::GeoLocation;
+use crate::models::weather::WeatherResponse;
+use anyhow::Result;
+use reqwest::Client;
+use serde::de::DeserializeOwned;
+use std::sync::Arc;
+
+/// Async counterpart of [`WeatherApiClient`](crate::WeatherApiClient) for
+/// code already running on a tokio runtime, such as the web server. Requests,
+/// errors, stats and recorded fixtures are the same as the blocking client's.
+///
+/// ```no_run
+/// use weather_cl::api::async_client::AsyncWeatherApiClient;
+///
+/// # async fn example() -> anyhow::Result<()> {
+/// let client = AsyncWeatherApiClient::with_api_key("your-openweathermap-key")?;
+/// let (berlin, paris) = tokio::join!(client.get_weather("Berlin"), client.get_weather("Paris"));
+/// println!("{} / {}", berlin?.name, paris?.name);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct AsyncWeatherApiClient {
+    client: Client,
+    session: Session,
+}
+
+impl AsyncWeatherApiClient {
+    pub fn with_api_key(api_key: &str) -> Result<Self> {
+        Self::with_stats(api_key, Arc::new(ApiStats::default()))
+    }
+
+    /// A client counting into existing `stats`, e.g. a blocking client's.
+    pub fn with_stats(api_key: &str, stats: Arc<ApiStats>) -> Result<Self> {
+        let session = Session::new(api_key, stats)?;
+
+        let client = Client::builder()
+            .timeout(request::TIMEOUT)
+            .user_agent(request::USER_AGENT)
+            .build()?;
+
+        Ok(Self { client, session })
+    }
+
+    /// Name of the upstream weather provider, used to label exported data.
+    pub fn provider(&self) -> &'static str {
+        "openweathermap"
+    }
+
+    pub fn stats(&self) -> Arc<ApiStats> {
+        Arc::clone(&self.session.stats)
+    }
+
+    async fn fetch<T: DeserializeOwned>(&self, endpoint: &Endpoint<'_>) -> Result<T> {
+        self.session
+            .fetch_async(endpoint, |url| async move {
+                let response = self.client.get(url).send().await?;
+                Ok((response.status(), response.text().await?))
+            })
+            .await
+    }
+
+    pub async fn get_weather(&self, city: &str) -> Result<WeatherResponse> {
+        self.get_weather_at(&Place::Name(city.to_string())).await
+    }
+
+    pub async fn get_weather_at(&self, place: &Place) -> Result<WeatherResponse> {
+        let weather = self.fetch(&Endpoint::Weather(place)).await?;
+        let weather = request::check_weather(place, weather)
+            .inspect_err(|_| self.session.stats.record_error(ErrorKind::Decode))?;
+        self.session.stats.record_success();
+        Ok(weather)
+    }
+
+    pub async fn get_weather_by_coords(&self, lat: f64, lon: f64) -> Result<WeatherResponse> {
+        self.get_weather_at(&Place::Coords { lat, lon }).await
+    }
+
+    pub async fn get_forecast_at(&self, place: &Place) -> Result<ForecastResponse> {
+        let forecast = self.fetch(&Endpoint::Forecast(place)).await?;
+        self.session.stats.record_success();
+        Ok(forecast)
+    }
+
+    /// Best match for a place name from the geocoding API.
+    pub async fn geocode(&self, query: &str) -> Result<GeoLocation> {
+        let matches = self.fetch(&Endpoint::Geocode(query)).await?;
+        self.session.stats.record_success();
+        request::first_match(query, matches)
+    }
+}
@@ -0,0 +1,37 @@
+use crate::api::async_client::AsyncWeatherApiClient;
+use crate::api::clients::Place;
+use crate::models::weather::WeatherResponse;
+use anyhow::Result;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+/// Fetch current weather for every place with at most `jobs` requests in
+/// flight on the current runtime. Results come back in input order and one
+/// failure does not stop the others.
+pub async fn fetch_all_async(
+    client: &AsyncWeatherApiClient,
+    places: &[Place],
+    jobs: usize,
+) -> Vec<Result<WeatherResponse>> {
+    let permits = Arc::new(Semaphore::new(jobs.max(1)));
+    let mut tasks = JoinSet::new();
+    for (index, place) in places.iter().cloned().enumerate() {
+        let client = client.clone();
+        let permits = Arc::clone(&permits);
+        tasks.spawn(async move {
+            let _permit = permits.acquire_owned().await;
+            (index, client.get_weather_at(&place).await)
+        });
+    }
+
+    let mut results: Vec<Option<Result<WeatherResponse>>> = places.iter().map(|_| None).collect();
+    while let Some(joined) = tasks.join_next().await {
+        let (index, result) = joined.expect("fetch task panicked");
+        results[index] = Some(result);
+    }
+    results
+        .into_iter()
+        .map(|r| r.expect("every place is fetched"))
+        .collect()
+}
@@ -0,0 +1,144 @@
+use crate::api::async_client::AsyncWeatherApiClient;
+use crate::api::clients::WeatherApiClient;
+use crate::config::locations::Locations;
+use crate::history::History;
+use crate::models::weather::WeatherResponse;
+use anyhow::Result;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Most locations kept at once. Widgets take any `?city=`, so without a
+/// bound every distinct name asked for would stay in memory.
+const MAX_ENTRIES: usize = 256;
+
+/// Wraps a `WeatherApiClient` with an in-memory, per-location TTL cache so
+/// long-running modes can serve repeated lookups without hitting rate limits.
+/// Lookups go through the blocking client from plain threads and through
+/// its async twin from the web server.
+pub struct CachedWeatherClient {
+    client: WeatherApiClient,
+    async_client: AsyncWeatherApiClient,
+    ttl: Duration,
+    locations: Locations,
+    history: Option<Arc<History>>,
+    entries: Mutex<HashMap<String, (Instant, Arc<WeatherResponse>)>>,
+}
+
+impl CachedWeatherClient {
+    pub fn new(client: WeatherApiClient, ttl: Duration) -> Result<Self> {
+        Ok(Self {
+            async_client: client.to_async()?,
+            client,
+            ttl,
+            locations: Locations::default(),
+            history: None,
+            entries: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Resolve saved location aliases in lookups.
+    pub fn with_locations(mut self, locations: Locations) -> Self {
+        self.locations = locations;
+        self
+    }
+
+    /// Record every new observation in `history`.
+    pub fn with_history(mut self, history: Option<Arc<History>>) -> Self {
+        self.history = history;
+        self
+    }
+
+    pub fn client(&self) -> &WeatherApiClient {
+        &self.client
+    }
+
+    /// The async client, sharing the blocking one's key and stats.
+    pub fn async_client(&self) -> &AsyncWeatherApiClient {
+        &self.async_client
+    }
+
+    /// Store an observation that was fetched outside the cache.
+    pub fn insert(&self, location: &str, weather: WeatherResponse) -> Arc<WeatherResponse> {
+        if let Some(history) = &self.history {
+            // History is a side effect; a locked or full database must not
+            // break live lookups
+            if let Err(e) = history.record(location, self.client.provider(), &weather) {
+                eprintln!("Failed to record history for '{}': {}", location, e);
+            }
+        }
+        let weather = Arc::new(weather);
+        let key = cache_key(location);
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() >= MAX_ENTRIES && !entries.contains_key(&key) {
+            // Expired entries go first, then the one fetched longest ago
+            entries.retain(|_, (fetched, _)| fetched.elapsed() < self.ttl);
+            if entries.len() >= MAX_ENTRIES {
+                let oldest = entries.iter().min_by_key(|(_, (fetched, _))| *fetched).map(|(k, _)| k.clone());
+                if let Some(oldest) = oldest {
+                    entries.remove(&oldest);
+                }
+            }
+        }
+        entries.insert(key, (Instant::now(), Arc::clone(&weather)));
+        weather
+    }
+
+    /// Return a fresh cached observation or fetch a new one.
+    pub fn get_weather(&self, location: &str) -> Result<Arc<WeatherResponse>> {
+        if let Some(weather) = self.fresh(location) {
+            return Ok(weather);
+        }
+        let weather = self.client.get_weather_at(&self.locations.resolve(location))?;
+        Ok(self.insert(location, weather))
+    }
+
+    /// [`get_weather`](Self::get_weather) without blocking the runtime
+    /// while the provider answers.
+    pub async fn get_weather_async(&self, location: &str) -> Result<Arc<WeatherResponse>> {
+        if let Some(weather) = self.fresh(location) {
+            return Ok(weather);
+        }
+        let weather = self.async_client.get_weather_at(&self.locations.resolve(location)).await?;
+        Ok(self.insert(location, weather))
+    }
+
+    fn fresh(&self, location: &str) -> Option<Arc<WeatherResponse>> {
+        let entries = self.entries.lock().unwrap();
+        let (fetched, weather) = entries.get(&cache_key(location))?;
+        if fetched.elapsed() >= self.ttl {
+            return None;
+        }
+        self.client.stats().record_cache_hit();
+        Some(Arc::clone(weather))
+    }
+
+    /// Last observation for `location` regardless of age.
+    pub fn stale(&self, location: &str) -> Option<Arc<WeatherResponse>> {
+        let entries = self.entries.lock().unwrap();
+        entries.get(&cache_key(location)).map(|(_, w)| Arc::clone(w))
+    }
+}
+
+fn cache_key(location: &str) -> String {
+    location.trim().to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entries_are_bounded() {
+        let client = WeatherApiClient::with_api_key("test").unwrap();
+        let cache = CachedWeatherClient::new(client, Duration::from_secs(300)).unwrap();
+
+        for i in 0..MAX_ENTRIES + 10 {
+            let weather = serde_json::from_str(include_str!("../../tests/fixtures/berlin.json")).unwrap();
+            cache.insert(&format!("City {}", i), weather);
+        }
+        assert_eq!(cache.entries.lock().unwrap().len(), MAX_ENTRIES);
+        assert!(cache.stale("City 0").is_none());
+        assert!(cache.stale(&format!("City {}", MAX_ENTRIES + 9)).is_some());
+    }
+}
@@ -1,79 +1,102 @@
+use crate::api::async_client::AsyncWeatherApiClient;
+use crate::api::request::{self, Endpoint, Session};
+use crate::api::stats::{ApiStats, ErrorKind};
+use crate::models::forecast::ForecastResponse;
+use crate::models::geocoding::GeoLocation;
 use crate::models::weather::WeatherResponse;
-use anyhow::{anyhow, Result};
+use anyhow::Result;
 use reqwest::blocking::Client;
-use std::env;
-use std::time::Duration;
+use serde::de::DeserializeOwned;
+use std::fmt;
+use std::sync::Arc;
 
+/// What to look up: a free-form place name or a pair of coordinates
+/// (e.g. from a saved location alias).
+#[derive(Debug, Clone, PartialEq)]
+pub enum Place {
+    Name(String),
+    Coords { lat: f64, lon: f64 },
+}
+
+impl fmt::Display for Place {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Place::Name(name) => write!(f, "{}", name),
+            Place::Coords { lat, lon } => write!(f, "{:.4},{:.4}", lat, lon),
+        }
+    }
+}
+
+/// Blocking OpenWeatherMap client. See [`AsyncWeatherApiClient`] for use
+/// inside an async runtime.
+///
+/// Setting `WEATHER_RECORD_DIR` saves every response into that directory
+/// with the API key redacted; `WEATHER_REPLAY_DIR` answers requests from
+/// such recordings without touching the network.
 pub struct WeatherApiClient {
     client: Client,
-    api_key: String,
+    session: Session,
 }
 
 impl WeatherApiClient {
-    pub fn new() -> Result<Self> {
-        // Get API key from environment variable
-        let api_key = env::var("WEATHER_API_KEY")
-            .or_else(|_| env::var("OPENWEATHER_API_KEY"))
-            .map_err(|_| {
-                anyhow!("No API key found. Please set WEATHER_API_KEY or OPENWEATHER_API_KEY environment variable.\nGet a free API key at: https://openweathermap.org/api")
-            })?;
-        
-        if api_key.trim().is_empty() || api_key == "your_api_key_here" {
-            return Err(anyhow!("Invalid API key. Please set a valid OpenWeatherMap API key."));
-        }
+    pub fn with_api_key(api_key: &str) -> Result<Self> {
+        let session = Session::new(api_key, Arc::new(ApiStats::default()))?;
         
         let client = Client::builder()
-            .timeout(Duration::from_secs(10))
-            .user_agent("rust-weather-cli/1.0")
+            .timeout(request::TIMEOUT)
+            .user_agent(request::USER_AGENT)
             .build()?;
         
-        Ok(Self { client, api_key })
+        Ok(Self { client, session })
+    }
+
+    /// An async client with the same key, counting into the same stats.
+    pub fn to_async(&self) -> Result<AsyncWeatherApiClient> {
+        AsyncWeatherApiClient::with_stats(&self.session.api_key, self.stats())
+    }
+
+    /// Name of the upstream weather provider, used to label exported data.
+    pub fn provider(&self) -> &'static str {
+        "openweathermap"
+    }
+
+    pub fn stats(&self) -> Arc<ApiStats> {
+        Arc::clone(&self.session.stats)
+    }
+
+    fn fetch<T: DeserializeOwned>(&self, endpoint: &Endpoint) -> Result<T> {
+        self.session.fetch(endpoint, |url| {
+            let response = self.client.get(url).send()?;
+            Ok((response.status(), response.text()?))
+        })
     }
     
     pub fn get_weather(&self, city: &str) -> Result<WeatherResponse> {
-        // First, try direct city name
-        let url = format!(
-            "https://api.openweathermap.org/data/2.5/weather?q={}&appid={}",
-            city, self.api_key
-        );
-        
-        let response = self.client.get(&url).send()?;
-        
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response.text().unwrap_or_default();
-            
-            return match status.as_u16() {
-                401 => Err(anyhow!("Invalid API key. Please check your OpenWeatherMap API key.")),
-                404 => Err(anyhow!("City '{}' not found. Please check the spelling.", city)),
-                429 => Err(anyhow!("API rate limit exceeded. Please try again later.")),
-                _ => Err(anyhow!("API error ({}): {}", status, error_text)),
-            };
-        }
-        
-        let weather: WeatherResponse = response.json()?;
-        
-        // Validate response
-        if weather.name.is_empty() {
-            return Err(anyhow!("Invalid response from API"));
-        }
-        
-        Ok(weather)
+        self.get_weather_at(&Place::Name(city.to_string()))
     }
     
-    pub fn get_weather_by_coords(&self, lat: f64, lon: f64) -> Result<WeatherResponse> {
-        let url = format!(
-            "https://api.openweathermap.org/data/2.5/weather?lat={}&lon={}&appid={}",
-            lat, lon, self.api_key
-        );
-        
-        let response = self.client.get(&url).send()?;
-        
-        if !response.status().is_success() {
-            return Err(anyhow!("Failed to fetch weather data for coordinates"));
-        }
-        
-        let weather: WeatherResponse = response.json()?;
+    pub fn get_weather_at(&self, place: &Place) -> Result<WeatherResponse> {
+        let weather = self.fetch(&Endpoint::Weather(place))?;
+        let weather = request::check_weather(place, weather)
+            .inspect_err(|_| self.session.stats.record_error(ErrorKind::Decode))?;
+        self.session.stats.record_success();
         Ok(weather)
     }
-}
\ No newline at end of file
+
+    pub fn get_weather_by_coords(&self, lat: f64, lon: f64) -> Result<WeatherResponse> {
+        self.get_weather_at(&Place::Coords { lat, lon })
+    }
+
+    pub fn get_forecast_at(&self, place: &Place) -> Result<ForecastResponse> {
+        let forecast = self.fetch(&Endpoint::Forecast(place))?;
+        self.session.stats.record_success();
+        Ok(forecast)
+    }
+
+    /// Best match for a place name from the geocoding API.
+    pub fn geocode(&self, query: &str) -> Result<GeoLocation> {
+        let matches = self.fetch(&Endpoint::Geocode(query))?;
+        self.session.stats.record_success();
+        request::first_match(query, matches)
+    }
+}
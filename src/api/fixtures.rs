@@ -0,0 +1,129 @@
+//! Recorded provider responses, so the real binary can run offline.
+//!
+//! With `WEATHER_RECORD_DIR=<dir>` the clients save every response they get
+//! into `<dir>`, one JSON file per request with the API key redacted. With
+//! `WEATHER_REPLAY_DIR=<dir>` they answer from those files instead and never
+//! touch the network; a request nobody recorded fails.
+
+use crate::api::request::Endpoint;
+use anyhow::{anyhow, Context, Result};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub(crate) const RECORD_VAR: &str = "WEATHER_RECORD_DIR";
+pub(crate) const REPLAY_VAR: &str = "WEATHER_REPLAY_DIR";
+
+/// Stands in for the API key in recorded URLs and bodies.
+const REDACTED: &str = "REDACTED";
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Fixtures {
+    Record(PathBuf),
+    Replay(PathBuf),
+}
+
+/// One response as stored on disk. JSON bodies are kept as JSON so the
+/// files stay readable and easy to edit by hand.
+#[derive(Serialize, Deserialize)]
+struct Recording {
+    url: String,
+    status: u16,
+    body: Value,
+}
+
+impl Fixtures {
+    pub(crate) fn from_env() -> Result<Option<Self>> {
+        let dir = |name: &str| env::var_os(name).filter(|v| !v.is_empty()).map(PathBuf::from);
+        match (dir(RECORD_VAR), dir(REPLAY_VAR)) {
+            (Some(_), Some(_)) => Err(anyhow!("Set only one of {} and {}.", RECORD_VAR, REPLAY_VAR)),
+            (Some(dir), None) => Ok(Some(Fixtures::Record(dir))),
+            (None, Some(dir)) => Ok(Some(Fixtures::Replay(dir))),
+            (None, None) => Ok(None),
+        }
+    }
+
+    /// The recorded status and body for `endpoint`, when replaying.
+    pub(crate) fn replay(&self, endpoint: &Endpoint) -> Option<Result<(StatusCode, String)>> {
+        match self {
+            Fixtures::Replay(dir) => Some(load(dir, endpoint)),
+            Fixtures::Record(_) => None,
+        }
+    }
+
+    /// Save a response from the provider, when recording.
+    pub(crate) fn record(&self, endpoint: &Endpoint, api_key: &str, status: StatusCode, body: &str) -> Result<()> {
+        let Fixtures::Record(dir) = self else {
+            return Ok(());
+        };
+
+        let body = body.replace(api_key, REDACTED);
+        let recording = Recording {
+            url: endpoint.url(REDACTED),
+            status: status.as_u16(),
+            body: serde_json::from_str(&body).unwrap_or(Value::String(body)),
+        };
+
+        fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+        let path = dir.join(endpoint.fixture_name());
+        fs::write(&path, serde_json::to_string_pretty(&recording)? + "\n")
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+fn load(dir: &Path, endpoint: &Endpoint) -> Result<(StatusCode, String)> {
+    let path = dir.join(endpoint.fixture_name());
+    let content = fs::read_to_string(&path).map_err(|_| {
+        anyhow!(
+            "No recorded response for {} in {}. Record one with {}.",
+            endpoint.url(REDACTED),
+            dir.display(),
+            RECORD_VAR
+        )
+    })?;
+    let recording: Recording =
+        serde_json::from_str(&content).with_context(|| format!("Invalid recording {}", path.display()))?;
+
+    let status = StatusCode::from_u16(recording.status)
+        .with_context(|| format!("Invalid status in {}", path.display()))?;
+    let body = match recording.body {
+        Value::String(text) => text,
+        json => json.to_string(),
+    };
+    Ok((status, body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::clients::Place;
+
+    #[test]
+    fn test_record_then_replay() {
+        let dir = env::temp_dir().join(format!("weather-fixtures-{}", std::process::id()));
+        let place = Place::Name("New York".to_string());
+        let endpoint = Endpoint::Weather(&place);
+        assert_eq!(endpoint.fixture_name(), "weather-new_york.json");
+
+        let body = r#"{"cod":401,"message":"Invalid API key SECRET"}"#;
+        Fixtures::Record(dir.clone())
+            .record(&endpoint, "SECRET", StatusCode::UNAUTHORIZED, body)
+            .unwrap();
+        let saved = fs::read_to_string(dir.join("weather-new_york.json")).unwrap();
+        assert!(!saved.contains("SECRET"));
+        assert!(saved.contains("appid=REDACTED"));
+
+        let (status, replayed) = Fixtures::Replay(dir.clone()).replay(&endpoint).unwrap().unwrap();
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(replayed, r#"{"cod":401,"message":"Invalid API key REDACTED"}"#);
+
+        let paris = Place::Name("Paris".to_string());
+        let missing = Fixtures::Replay(dir.clone()).replay(&Endpoint::Weather(&paris)).unwrap();
+        assert!(missing.unwrap_err().to_string().starts_with("No recorded response"));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
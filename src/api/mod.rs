@@ -1 +1,7 @@
+pub mod async_client;
+pub mod batch;
+pub mod cache;
 pub mod clients;
+mod fixtures;
+mod request;
+pub mod stats;
@@ -0,0 +1,230 @@
+//! Request building, fixtures, stats and error mapping shared by the
+//! blocking and async clients, so both talk to the provider and fail in
+//! exactly the same way. Each client only supplies the HTTP call itself.
+
+use crate::api::clients::Place;
+use crate::api::fixtures::Fixtures;
+use crate::api::stats::{ApiStats, ErrorKind};
+use crate::models::geocoding::GeoLocation;
+use crate::models::weather::WeatherResponse;
+use anyhow::{anyhow, Error, Result};
+use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use url::form_urlencoded::Serializer;
+
+const BASE_URL: &str = "https://api.openweathermap.org";
+
+pub(crate) const TIMEOUT: Duration = Duration::from_secs(10);
+pub(crate) const USER_AGENT: &str = "rust-weather-cli/1.0";
+
+fn check_api_key(api_key: &str) -> Result<()> {
+    if api_key.trim().is_empty() || api_key == "your_api_key_here" {
+        return Err(anyhow!("Invalid API key. Please set a valid OpenWeatherMap API key."));
+    }
+    Ok(())
+}
+
+/// What a client needs besides its HTTP transport: the key, the counters
+/// and the fixtures directory, if any.
+#[derive(Clone)]
+pub(crate) struct Session {
+    pub(crate) api_key: String,
+    pub(crate) stats: Arc<ApiStats>,
+    fixtures: Option<Fixtures>,
+}
+
+impl Session {
+    pub(crate) fn new(api_key: &str, stats: Arc<ApiStats>) -> Result<Self> {
+        check_api_key(api_key)?;
+        Ok(Self { api_key: api_key.to_string(), stats, fixtures: Fixtures::from_env()? })
+    }
+
+    /// Call `endpoint` with `send`, which gets the URL and returns the
+    /// status and body.
+    pub(crate) fn fetch<T: DeserializeOwned>(
+        &self,
+        endpoint: &Endpoint,
+        send: impl FnOnce(String) -> reqwest::Result<(StatusCode, String)>,
+    ) -> Result<T> {
+        let response = match self.start(endpoint) {
+            Some(recorded) => recorded?,
+            None => self.received(endpoint, send(endpoint.url(&self.api_key)))?,
+        };
+        self.parse(endpoint, response)
+    }
+
+    /// [`fetch`](Self::fetch) for an async `send`.
+    pub(crate) async fn fetch_async<T: DeserializeOwned, F>(
+        &self,
+        endpoint: &Endpoint<'_>,
+        send: impl FnOnce(String) -> F,
+    ) -> Result<T>
+    where
+        F: Future<Output = reqwest::Result<(StatusCode, String)>>,
+    {
+        let response = match self.start(endpoint) {
+            Some(recorded) => recorded?,
+            None => self.received(endpoint, send(endpoint.url(&self.api_key)).await)?,
+        };
+        self.parse(endpoint, response)
+    }
+
+    /// Count the request and answer it from fixtures when replaying.
+    fn start(&self, endpoint: &Endpoint) -> Option<Result<(StatusCode, String)>> {
+        self.stats.record_request();
+        self.fixtures.as_ref().and_then(|f| f.replay(endpoint))
+    }
+
+    /// Count a transport failure or record a response when recording.
+    fn received(
+        &self,
+        endpoint: &Endpoint,
+        response: reqwest::Result<(StatusCode, String)>,
+    ) -> Result<(StatusCode, String)> {
+        // The URL carries the API key
+        let (status, body) = response
+            .map_err(|e| e.without_url())
+            .inspect_err(|_| self.stats.record_error(ErrorKind::Network))?;
+        if let Some(fixtures) = &self.fixtures {
+            fixtures.record(endpoint, &self.api_key, status, &body)?;
+        }
+        Ok((status, body))
+    }
+
+    /// Decode a provider response, counting the kind of failure if it is
+    /// not one.
+    fn parse<T: DeserializeOwned>(&self, endpoint: &Endpoint, (status, body): (StatusCode, String)) -> Result<T> {
+        if !status.is_success() {
+            self.stats.record_error(ErrorKind::from_status(status.as_u16()));
+            return Err(endpoint.error(status, &body));
+        }
+        serde_json::from_str(&body)
+            .inspect_err(|_| self.stats.record_error(ErrorKind::Decode))
+            .map_err(Error::from)
+    }
+}
+
+/// One call to the provider.
+pub(crate) enum Endpoint<'a> {
+    Weather(&'a Place),
+    Forecast(&'a Place),
+    Geocode(&'a str),
+}
+
+impl Endpoint<'_> {
+    pub(crate) fn url(&self, api_key: &str) -> String {
+        // Place names are user input, so encode them rather than let an
+        // `&` or `#` add parameters of its own
+        let mut query = Serializer::new(String::new());
+        let mut location = |place: &Place| match place {
+            Place::Name(city) => {
+                query.append_pair("q", city);
+            }
+            Place::Coords { lat, lon } => {
+                query.append_pair("lat", &lat.to_string()).append_pair("lon", &lon.to_string());
+            }
+        };
+        let path = match self {
+            Endpoint::Weather(place) => {
+                location(place);
+                "data/2.5/weather"
+            }
+            Endpoint::Forecast(place) => {
+                location(place);
+                "data/2.5/forecast"
+            }
+            Endpoint::Geocode(q) => {
+                query.append_pair("q", q).append_pair("limit", "1");
+                "geo/1.0/direct"
+            }
+        };
+        query.append_pair("appid", api_key);
+        format!("{}/{}?{}", BASE_URL, path, query.finish())
+    }
+
+    /// File holding this request's response in a fixtures directory, e.g.
+    /// `weather-new_york.json`.
+    pub(crate) fn fixture_name(&self) -> String {
+        let (kind, query) = match self {
+            Endpoint::Weather(place) => ("weather", place.to_string()),
+            Endpoint::Forecast(place) => ("forecast", place.to_string()),
+            Endpoint::Geocode(query) => ("geocode", query.to_string()),
+        };
+        let slug: String = query
+            .trim()
+            .to_lowercase()
+            .chars()
+            .map(|c| if c.is_alphanumeric() || c == '.' || c == '-' { c } else { '_' })
+            .collect();
+        format!("{}-{}.json", kind, slug)
+    }
+
+    /// The error to report for a non-success `status` with response `body`.
+    pub(crate) fn error(&self, status: StatusCode, body: &str) -> Error {
+        match (status.as_u16(), self) {
+            (401, _) => anyhow!("Invalid API key. Please check your OpenWeatherMap API key."),
+            (429, _) => anyhow!("API rate limit exceeded. Please try again later."),
+            (404, Endpoint::Weather(Place::Name(city))) => {
+                anyhow!("City '{}' not found. Please check the spelling.", city)
+            }
+            (_, Endpoint::Weather(Place::Name(_))) => anyhow!("API error ({}): {}", status, body),
+            (_, Endpoint::Weather(place)) => anyhow!("Failed to fetch weather data for {} ({})", place, status),
+            (_, Endpoint::Forecast(place)) => anyhow!("Failed to fetch forecast for '{}' ({})", place, status),
+            (_, Endpoint::Geocode(query)) => anyhow!("Failed to geocode '{}' ({})", query, status),
+        }
+    }
+}
+
+/// Reject responses the API returns for places it could not really resolve.
+pub(crate) fn check_weather(place: &Place, weather: WeatherResponse) -> Result<WeatherResponse> {
+    // Coordinates over open sea legitimately come back without a name
+    if matches!(place, Place::Name(_)) && weather.name.is_empty() {
+        return Err(anyhow!("Invalid response from API"));
+    }
+    Ok(weather)
+}
+
+pub(crate) fn first_match(query: &str, matches: Vec<GeoLocation>) -> Result<GeoLocation> {
+    matches
+        .into_iter()
+        .next()
+        .ok_or_else(|| anyhow!("No place found matching '{}'.", query))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_urls_and_errors() {
+        let berlin = Place::Name("Berlin".to_string());
+        let sea = Place::Coords { lat: 10.0, lon: -30.5 };
+        assert_eq!(
+            Endpoint::Weather(&berlin).url("KEY"),
+            "https://api.openweathermap.org/data/2.5/weather?q=Berlin&appid=KEY"
+        );
+        assert_eq!(
+            Endpoint::Forecast(&sea).url("KEY"),
+            "https://api.openweathermap.org/data/2.5/forecast?lat=10&lon=-30.5&appid=KEY"
+        );
+        let injected = Place::Name("Paris & Co&appid=OTHER".to_string());
+        assert_eq!(
+            Endpoint::Weather(&injected).url("KEY"),
+            "https://api.openweathermap.org/data/2.5/weather?q=Paris+%26+Co%26appid%3DOTHER&appid=KEY"
+        );
+        assert_eq!(
+            Endpoint::Geocode("St. John's").url("KEY"),
+            "https://api.openweathermap.org/geo/1.0/direct?q=St.+John%27s&limit=1&appid=KEY"
+        );
+
+        let not_found = Endpoint::Weather(&berlin).error(StatusCode::NOT_FOUND, "");
+        assert_eq!(not_found.to_string(), "City 'Berlin' not found. Please check the spelling.");
+        let limited = Endpoint::Geocode("Berlin").error(StatusCode::TOO_MANY_REQUESTS, "");
+        assert_eq!(limited.to_string(), "API rate limit exceeded. Please try again later.");
+        let failed = Endpoint::Forecast(&sea).error(StatusCode::BAD_GATEWAY, "");
+        assert_eq!(failed.to_string(), "Failed to fetch forecast for '10.0000,-30.5000' (502 Bad Gateway)");
+    }
+}
@@ -0,0 +1,93 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    Unauthorized,
+    NotFound,
+    RateLimited,
+    Http,
+    Network,
+    Decode,
+}
+
+impl ErrorKind {
+    pub const ALL: [ErrorKind; 6] = [
+        ErrorKind::Unauthorized,
+        ErrorKind::NotFound,
+        ErrorKind::RateLimited,
+        ErrorKind::Http,
+        ErrorKind::Network,
+        ErrorKind::Decode,
+    ];
+
+    pub fn from_status(status: u16) -> Self {
+        match status {
+            401 => ErrorKind::Unauthorized,
+            404 => ErrorKind::NotFound,
+            429 => ErrorKind::RateLimited,
+            _ => ErrorKind::Http,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorKind::Unauthorized => "unauthorized",
+            ErrorKind::NotFound => "not_found",
+            ErrorKind::RateLimited => "rate_limited",
+            ErrorKind::Http => "http",
+            ErrorKind::Network => "network",
+            ErrorKind::Decode => "decode",
+        }
+    }
+}
+
+/// Counters describing how the client has been talking to the provider.
+#[derive(Debug, Default)]
+pub struct ApiStats {
+    requests: AtomicU64,
+    errors: [AtomicU64; 6],
+    cache_hits: AtomicU64,
+    last_success: AtomicU64,
+}
+
+impl ApiStats {
+    pub fn record_request(&self) {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_error(&self, kind: ErrorKind) {
+        let index = ErrorKind::ALL.iter().position(|k| *k == kind).unwrap_or(0);
+        self.errors[index].fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_cache_hit(&self) {
+        self.cache_hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_success(&self) {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        self.last_success.store(now, Ordering::Relaxed);
+    }
+
+    pub fn requests(&self) -> u64 {
+        self.requests.load(Ordering::Relaxed)
+    }
+
+    pub fn errors(&self, kind: ErrorKind) -> u64 {
+        let index = ErrorKind::ALL.iter().position(|k| *k == kind).unwrap_or(0);
+        self.errors[index].load(Ordering::Relaxed)
+    }
+
+    pub fn cache_hits(&self) -> u64 {
+        self.cache_hits.load(Ordering::Relaxed)
+    }
+
+    /// Unix timestamp of the last successful API call, or 0 if none yet.
+    pub fn last_success(&self) -> u64 {
+        self.last_success.load(Ordering::Relaxed)
+    }
+}
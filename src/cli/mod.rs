@@ -0,0 +1,563 @@
+//! The `weather` command line: argument parsing, settings resolution and
+//! the modes that print weather. Internal to the binary and not covered by
+//! semver.
+
+mod options;
+#[cfg(feature = "web")]
+mod web;
+
+use crate::api::batch;
+use crate::commands;
+use crate::api::clients::WeatherApiClient;
+use crate::api::clients::Place;
+use crate::commands::check::{self, CheckArgs};
+use crate::commands::config::ConfigAction;
+#[cfg(feature = "history")]
+use crate::commands::history::HistoryArgs;
+use crate::commands::locations::LocationsAction;
+#[cfg(feature = "mqtt")]
+use crate::commands::mqtt::MqttArgs;
+use crate::commands::notify::NotifyAction;
+use crate::config::settings::Config;
+use crate::models::forecast::ForecastResponse;
+use crate::models::weather::{TemperatureUnit, WeatherResponse};
+use crate::utils::comparison;
+use crate::utils::display_templates::{WeatherDisplay, display_error, DisplayTemplate};
+use crate::utils::export;
+use crate::utils::glyphs::{self, icon, Charset};
+use crate::utils::term_charts::Trend;
+use crate::utils::themes::ThemeRegistry;
+
+use anyhow::{anyhow, Context, Result};
+use clap::{Parser, Subcommand, ValueEnum};
+use colored::Colorize;
+use std::env;
+use std::fs;
+use std::io;
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use std::process;
+#[cfg(feature = "tui")]
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use options::Options;
+#[cfg(feature = "web")]
+use web::run_web;
+
+/// How far back `--trend history` looks, matching the forecast horizon.
+const HISTORY_TREND_WINDOW: Duration = Duration::from_secs(5 * 24 * 60 * 60);
+
+#[derive(Parser, Debug)]
+#[command(
+    name = "weather",
+    about = "🌤️ A beautiful terminal weather application",
+    version,
+    author,
+    long_about = "Fetch current weather information for any city in the world.\n\nGet your free API key at: https://openweathermap.org/api",
+    args_conflicts_with_subcommands = true
+)]
+struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// City names or saved location aliases; several are compared side by side
+    /// (default: `location` from the config file). Put a city named like a
+    /// subcommand after `--`, as in `weather -- watch`
+    cities: Vec<String>,
+
+    /// Read more cities from a file, one per line ('-' for stdin)
+    #[arg(long, value_name = "FILE")]
+    from_file: Option<PathBuf>,
+
+    /// Maximum concurrent requests when fetching several cities
+    #[arg(short = 'j', long, default_value_t = 4, value_parser = clap::value_parser!(u16).range(1..=32))]
+    jobs: u16,
+    
+    /// Temperature unit (celsius, fahrenheit, kelvin)
+    #[arg(short, long, value_enum, global = true)]
+    unit: Option<Temperature>,
+
+    /// Display template (default, compact, detailed, minimal, art)
+    #[arg(short = 't', long, value_enum)]
+    template: Option<Template>,
+
+    /// Data for the compact sparklines and detailed charts; `off` also drops
+    /// the daily forecast under the art template
+    #[arg(long, value_enum, default_value_t = TrendSource::Forecast)]
+    trend: TrendSource,
+
+    /// Plain ASCII output: no emoji, box drawing or Unicode charts (implied by TERM=dumb)
+    #[arg(long, global = true)]
+    ascii: bool,
+
+    /// Turn colors off (implied by NO_COLOR, TERM=dumb and output that is not a terminal)
+    #[arg(long, global = true)]
+    no_color: bool,
+
+    /// Show weather in browser instead of terminal
+    #[arg(short = 'w', long, hide = !cfg!(feature = "web"))]
+    web: bool,
+
+    /// Write a standalone .html page or .svg card instead of displaying
+    #[arg(short = 'o', long, value_name = "FILE", conflicts_with = "web")]
+    output: Option<PathBuf>,
+
+    /// Web/HTML theme (auto, light, dark, high-contrast or a --theme-dir name)
+    #[arg(long)]
+    theme: Option<String>,
+
+    /// Directory of extra themes as CSS custom property files (NAME.css)
+    #[arg(long, value_name = "DIR")]
+    theme_dir: Option<PathBuf>,
+
+    /// Web server port (default: 8080)
+    #[arg(long, hide = !cfg!(feature = "web"))]
+    port: Option<u16>,
+
+    /// Address the web server listens on (default: 127.0.0.1)
+    #[arg(long, value_name = "ADDR", hide = !cfg!(feature = "web"))]
+    bind: Option<IpAddr>,
+
+    /// Extra city or alias to export on /metrics in web mode (repeatable)
+    #[arg(long = "metrics-city", value_name = "CITY", hide = !cfg!(feature = "web"))]
+    metrics_cities: Vec<String>,
+
+    /// Seconds to cache observations for /metrics and widgets (default: 300, minimum: 10)
+    #[arg(long, alias = "metrics-interval", value_name = "SECONDS")]
+    cache_ttl: Option<u64>,
+
+    /// Origin allowed to embed the widget and call the web API (repeatable)
+    #[arg(long = "allow-origin", value_name = "ORIGIN", hide = !cfg!(feature = "web"))]
+    allow_origins: Vec<String>,
+
+    /// Let `/widget?city=` show any place, not just the served, metrics and saved ones
+    #[arg(long, hide = !cfg!(feature = "web"))]
+    widget_any_city: bool,
+
+    /// Config profile to apply on top of the file defaults [env: WEATHER_PROFILE]
+    #[arg(long, global = true)]
+    profile: Option<String>,
+
+    /// Config file to use instead of the default location [env: WEATHER_CONFIG]
+    #[arg(long, global = true, value_name = "FILE")]
+    config: Option<PathBuf>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Show or change settings in the config file
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+    /// Manage saved location aliases (home, office, ...)
+    Locations {
+        #[command(subcommand)]
+        action: LocationsAction,
+    },
+    /// Keep refreshing the weather for one place in the terminal
+    Watch {
+        /// City name or saved location alias (default: `location` from the config file)
+        place: Option<String>,
+        /// Time between refreshes, e.g. 30s, 10m, 1h
+        #[arg(short, long, default_value = "10m", value_parser = humantime::parse_duration)]
+        interval: Duration,
+    },
+    /// Test current (and forecast) conditions against thresholds for scripts
+    #[command(after_help = "Exit status: 0 if no rule matched, 1 if at least one rule matched, 2 on errors.")]
+    Check(CheckArgs),
+    /// Full-screen dashboard for several locations
+    #[cfg(feature = "tui")]
+    Tui {
+        /// Cities or aliases to list (default: `location` plus all saved locations)
+        places: Vec<String>,
+        /// Time between automatic refreshes, e.g. 5m, 1h
+        #[arg(short, long, default_value = "10m", value_parser = humantime::parse_duration)]
+        interval: Duration,
+    },
+    /// Summarize or export recorded observations
+    #[cfg(feature = "history")]
+    History(HistoryArgs),
+    /// Publish sensors to an MQTT broker with Home Assistant discovery
+    #[cfg(feature = "mqtt")]
+    Mqtt(MqttArgs),
+    /// Test notification channels and list alert rules from `[notify]`
+    Notify {
+        #[command(subcommand)]
+        action: NotifyAction,
+    },
+    #[cfg(not(feature = "tui"))]
+    #[command(hide = true)]
+    Tui(Unavailable),
+    #[cfg(not(feature = "history"))]
+    #[command(hide = true)]
+    History(Unavailable),
+    #[cfg(not(feature = "mqtt"))]
+    #[command(hide = true)]
+    Mqtt(Unavailable),
+}
+
+/// Arguments of a subcommand left out of this build, accepted only to
+/// explain which feature it needs.
+#[cfg(not(all(feature = "tui", feature = "history", feature = "mqtt")))]
+#[derive(clap::Args, Debug)]
+struct Unavailable {
+    #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+    args: Vec<String>,
+}
+
+
+#[derive(ValueEnum, Clone, Debug)]
+enum Temperature {
+    Celsius,
+    Fahrenheit,
+    Kelvin,
+}
+
+#[derive(ValueEnum, Clone, Debug)]
+enum Template {
+    Default,
+    Compact,
+    Detailed,
+    Minimal,
+    Art,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum TrendSource {
+    /// The 5 day / 3 hour forecast
+    Forecast,
+    /// Observations recorded in the history database over the last 5 days
+    #[cfg_attr(not(feature = "history"), value(hide = true))]
+    History,
+    /// No charts
+    Off,
+}
+
+impl From<Temperature> for TemperatureUnit {
+    fn from(t: Temperature) -> Self {
+        match t {
+            Temperature::Celsius => TemperatureUnit::Celsius,
+            Temperature::Fahrenheit => TemperatureUnit::Fahrenheit,
+            Temperature::Kelvin => TemperatureUnit::Kelvin,
+        }
+    }
+}
+
+impl From<Template> for DisplayTemplate {
+    fn from(t: Template) -> Self {
+        match t {
+            Template::Default => DisplayTemplate::Default,
+            Template::Compact => DisplayTemplate::Compact,
+            Template::Detailed => DisplayTemplate::Detailed,
+            Template::Minimal => DisplayTemplate::Minimal,
+            Template::Art => DisplayTemplate::Art,
+        }
+    }
+}
+
+/// Parse the command line and run it, exiting with the status to report.
+pub fn main() {
+    let args = Args::parse();
+    let config_path = args.config.clone();
+    if !glyphs::color_enabled(args.no_color) {
+        colored::control::set_override(false);
+    }
+    glyphs::set_charset(Charset::detect(args.ascii));
+
+    if let Some(Command::Check(check_args)) = &args.command {
+        let code = match run_check(&args, check_args) {
+            Ok(true) => check::EXIT_MATCHED,
+            Ok(false) => check::EXIT_CLEAR,
+            Err(e) => {
+                display_error(&e.to_string());
+                check::EXIT_ERROR
+            }
+        };
+        process::exit(code);
+    }
+
+    if let Some(command) = &args.command {
+        let result = match command {
+            Command::Config { action } => commands::config::run(action.clone(), config_path.as_deref()),
+            Command::Locations { action } => {
+                commands::locations::run(action.clone(), config_path.as_deref(), profile(&args.profile).as_deref())
+            }
+            Command::Watch { place, interval } => run_watch(&args, place, *interval),
+            #[cfg(feature = "tui")]
+            Command::Tui { places, interval } => run_tui(&args, places, *interval),
+            #[cfg(feature = "history")]
+            Command::History(history_args) => run_history(&args, history_args),
+            #[cfg(feature = "mqtt")]
+            Command::Mqtt(mqtt_args) => run_mqtt(&args, mqtt_args),
+            Command::Notify { action } => commands::notify::run(
+                action.clone(),
+                config_path.as_deref(),
+            ),
+            Command::Check(_) => unreachable!("handled above"),
+            #[cfg(not(feature = "tui"))]
+            Command::Tui(_) => Err(missing_feature("tui", "the dashboard")),
+            #[cfg(not(feature = "history"))]
+            Command::History(_) => Err(missing_feature("history", "observation history")),
+            #[cfg(not(feature = "mqtt"))]
+            Command::Mqtt(_) => Err(missing_feature("mqtt", "MQTT publishing")),
+        };
+        if let Err(e) = result {
+            display_error(&e.to_string());
+            process::exit(1);
+        }
+        return;
+    }
+
+    let options = match load_options(&args, &args.cities) {
+        Ok(o) => o,
+        Err(e) => {
+            display_error(&e.to_string());
+            process::exit(1);
+        }
+    };
+    
+    let themes = match load_themes(&options.theme, args.theme_dir.as_deref()) {
+        Ok(t) => t,
+        Err(e) => {
+            display_error(&e.to_string());
+            process::exit(1);
+        }
+    };
+    
+    if args.web {
+        if let Err(e) = run_web(&options, &args, themes) {
+            display_error(&e.to_string());
+            process::exit(1);
+        }
+    } else if let Some(path) = args.output {
+        if let Err(e) = run_export(&options, &path, &themes) {
+            display_error(&e.to_string());
+            process::exit(1);
+        }
+    } else if options.cities.len() > 1 {
+        if let Err(e) = run_compare(&options, args.jobs as usize) {
+            display_error(&e.to_string());
+            process::exit(1);
+        }
+    } else if let Err(e) = run(&options) {
+        display_error(&e.to_string());
+        process::exit(1);
+    }
+}
+
+fn load_options(args: &Args, cities: &[String]) -> Result<Options> {
+    let config = Config::load(args.config.as_deref())?;
+    let settings = config.settings(profile(&args.profile).as_deref())?;
+    Options::resolve(args, cities, settings, config.locations()?, config.notify()?)
+}
+
+fn profile(flag: &Option<String>) -> Option<String> {
+    flag.clone().or_else(|| env::var("WEATHER_PROFILE").ok())
+}
+
+/// Cities listed in a file, one per line; blank lines and `#` comments are skipped.
+pub(crate) fn read_city_file(path: &Path) -> Result<Vec<String>> {
+    let content = if path == Path::new("-") {
+        io::read_to_string(io::stdin())?
+    } else {
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?
+    };
+
+    Ok(content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect())
+}
+
+fn run(options: &Options) -> Result<()> {
+    println!("{} Fetching weather data for '{}'...", icon("⏳", "*").yellow(), options.city());
+    
+    let api_client = options.api_client()?;
+    let place = options.place();
+    let weather = api_client.get_weather_at(&place)?;
+    options.record(options.city(), &weather);
+
+    let trend = match options.template {
+        DisplayTemplate::Compact | DisplayTemplate::Detailed => fetch_trend(options, &api_client, &place, &weather),
+        _ => None,
+    };
+    let forecast = match (options.template, options.trend) {
+        (DisplayTemplate::Art, TrendSource::Forecast | TrendSource::History) => fetch_forecast(&api_client, &place),
+        _ => None,
+    };
+    
+    let display = WeatherDisplay::new(&weather, options.unit)
+        .with_template(options.template)
+        .with_trend(trend.as_ref())
+        .with_forecast(forecast.as_ref());
+    display.display()?;
+    
+    Ok(())
+}
+
+/// Like forecasts, trends are extras: missing data only warns.
+fn fetch_trend(options: &Options, api_client: &WeatherApiClient, place: &Place, weather: &WeatherResponse) -> Option<Trend> {
+    match options.trend {
+        TrendSource::Off => None,
+        TrendSource::Forecast => fetch_forecast(api_client, place).map(|f| Trend::from_forecast(&f)),
+        TrendSource::History => {
+            let now = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+            let since = now.saturating_sub(HISTORY_TREND_WINDOW.as_secs());
+            let records = options
+                .settings
+                .open_history()
+                .and_then(|history| history.query(options.city(), since));
+            match records {
+                Ok(records) if records.len() >= 2 => {
+                    Some(Trend::from_history(&records, "last 5 days", weather.timezone))
+                }
+                Ok(_) => {
+                    println!(
+                        "{} Not enough history for '{}' to chart yet. Enable recording with `weather config set record_history true`.",
+                        icon("⚠️", "!").yellow(),
+                        options.city()
+                    );
+                    None
+                }
+                Err(e) => {
+                    println!("{} History unavailable: {:#}", icon("⚠️", "!").yellow(), e);
+                    None
+                }
+            }
+        }
+    }
+}
+
+/// Forecast charts are optional extras, so a failed fetch only warns.
+fn fetch_forecast(api_client: &WeatherApiClient, place: &Place) -> Option<ForecastResponse> {
+    match api_client.get_forecast_at(place) {
+        Ok(forecast) => Some(forecast),
+        Err(e) => {
+            println!("{} Forecast unavailable: {}", icon("⚠️", "!").yellow(), e);
+            None
+        }
+    }
+}
+
+fn load_themes(default: &str, dir: Option<&Path>) -> Result<ThemeRegistry> {
+    let mut themes = ThemeRegistry::default();
+    if let Some(dir) = dir {
+        themes.load_dir(dir)?;
+    }
+    themes.set_default(default)?;
+    Ok(themes)
+}
+
+fn run_watch(args: &Args, place: &Option<String>, interval: Duration) -> Result<()> {
+    let options = load_options(args, place.as_slice())?;
+    let cache = options.cache()?;
+    commands::watch::run(&cache, options.city(), options.unit, interval, options.notifier()?)
+}
+
+fn run_check(args: &Args, check_args: &CheckArgs) -> Result<bool> {
+    let options = load_options(args, check_args.place.as_slice())?;
+    let outcome = check::run(check_args, &options.api_client()?, &options.place(), &options.unit)?;
+    options.record(options.city(), &outcome.weather);
+    Ok(outcome.matched)
+}
+
+#[cfg(feature = "tui")]
+fn run_tui(args: &Args, places: &[String], interval: Duration) -> Result<()> {
+    if interval < commands::watch::MIN_INTERVAL {
+        return Err(anyhow!(
+            "Refresh interval must be at least {}.",
+            humantime::format_duration(commands::watch::MIN_INTERVAL)
+        ));
+    }
+
+    let mut cities = places.to_vec();
+    if cities.is_empty() {
+        let config = Config::load(args.config.as_deref())?;
+        cities.extend(config.settings(profile(&args.profile).as_deref())?.location);
+        cities.extend(config.locations()?.iter().map(|(alias, _)| alias.clone()));
+    }
+    let mut seen = std::collections::HashSet::new();
+    cities.retain(|c| seen.insert(c.trim().to_lowercase()));
+
+    let options = load_options(args, &cities)?;
+    let cache = options.cache()?;
+    crate::tui::run(Arc::new(cache), options.locations.clone(), options.cities.clone(), options.unit, interval)
+}
+
+#[cfg(feature = "history")]
+fn run_history(args: &Args, history_args: &HistoryArgs) -> Result<()> {
+    let config = Config::load(args.config.as_deref())?;
+    let settings = config.settings(profile(&args.profile).as_deref())?;
+    let unit = match (&args.unit, &settings.units) {
+        (Some(u), _) => u.clone().into(),
+        (None, Some(u)) => u.parse().map_err(|e: String| anyhow!(e))?,
+        (None, None) => TemperatureUnit::Celsius,
+    };
+    commands::history::run(history_args, &settings.open_history()?, &unit)
+}
+
+#[cfg(feature = "mqtt")]
+fn run_mqtt(args: &Args, mqtt_args: &MqttArgs) -> Result<()> {
+    let options = load_options(args, &mqtt_args.places)?;
+    let mqtt_config = Config::load(args.config.as_deref())?.mqtt()?;
+    let cache = options.cache()?;
+    commands::mqtt::run(mqtt_args, mqtt_config, &cache, &options.cities, options.unit)
+}
+
+fn run_compare(options: &Options, jobs: usize) -> Result<()> {
+    println!("{} Fetching weather data for {} locations...", icon("⏳", "*").yellow(), options.cities.len());
+
+    let api_client = options.api_client()?.to_async()?;
+    let places: Vec<Place> = options.cities.iter().map(|c| options.locations.resolve(c)).collect();
+    let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build()?;
+    let fetched = runtime.block_on(batch::fetch_all_async(&api_client, &places, jobs));
+    let results: Vec<_> = options.cities.iter().cloned().zip(fetched).collect();
+
+    for (city, weather) in &results {
+        if let Ok(weather) = weather {
+            options.record(city, weather);
+        }
+    }
+
+    println!();
+    print!("{}", comparison::comparison_table(&results, &options.unit));
+
+    if results.iter().all(|(_, r)| r.is_err()) {
+        return Err(anyhow!("Could not fetch weather for any of the requested locations."));
+    }
+    Ok(())
+}
+
+fn run_export(options: &Options, path: &Path, themes: &ThemeRegistry) -> Result<()> {
+    let city = options.city();
+    println!("{} Fetching weather data for '{}'...", icon("⏳", "*").yellow(), city);
+
+    let api_client = options.api_client()?;
+    let place = options.place();
+    let weather = api_client.get_weather_at(&place)?;
+    options.record(city, &weather);
+    let forecast = fetch_forecast(&api_client, &place);
+    export::write_output(path, &weather, forecast.as_ref(), &options.unit, themes.get(None))?;
+
+    println!("{} Saved weather for '{}' to {}", icon("✅", "*").green(), weather.name, path.display());
+    Ok(())
+}
+
+
+#[cfg(not(feature = "web"))]
+fn run_web(_options: &Options, _args: &Args, _themes: ThemeRegistry) -> Result<()> {
+    Err(missing_feature("web", "the web server"))
+}
+
+/// Error for using something this build was compiled without.
+#[cfg(not(all(feature = "web", feature = "tui", feature = "history", feature = "mqtt")))]
+fn missing_feature(feature: &str, what: &str) -> anyhow::Error {
+    anyhow!(
+        "This build of weather does not include {}. Rebuild with `cargo install --path . --features {}`.",
+        what,
+        feature
+    )
+}
@@ -0,0 +1,134 @@
+use super::{read_city_file, Args, TrendSource};
+use crate::api::cache::CachedWeatherClient;
+use crate::api::clients::{Place, WeatherApiClient};
+use crate::commands::watch::MIN_INTERVAL;
+use crate::config::locations::Locations;
+use crate::config::notify::NotifyConfig;
+use crate::config::settings::Settings;
+use crate::history::History;
+use crate::models::weather::{TemperatureUnit, WeatherResponse};
+use crate::notify::Notifier;
+use crate::utils::display_templates::DisplayTemplate;
+use crate::utils::glyphs::icon;
+use anyhow::{anyhow, Result};
+use colored::Colorize;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Settings after applying flags > env > profile > file > built-in defaults.
+pub(crate) struct Options {
+    pub(crate) cities: Vec<String>,
+    pub(crate) unit: TemperatureUnit,
+    pub(crate) template: DisplayTemplate,
+    pub(crate) trend: TrendSource,
+    pub(crate) theme: String,
+    #[cfg_attr(not(feature = "web"), allow(dead_code))]
+    pub(crate) port: u16,
+    pub(crate) cache_ttl: Duration,
+    pub(crate) locations: Locations,
+    pub(crate) notify: NotifyConfig,
+    pub(crate) history: Option<Arc<History>>,
+    pub(crate) settings: Settings,
+}
+
+impl Options {
+    pub(crate) fn resolve(
+        args: &Args,
+        cities: &[String],
+        settings: Settings,
+        locations: Locations,
+        notify: NotifyConfig,
+    ) -> Result<Self> {
+        if settings.provider() != "openweathermap" {
+            return Err(anyhow!(
+                "Unsupported provider '{}'. Only 'openweathermap' is available.",
+                settings.provider()
+            ));
+        }
+
+        let mut cities = cities.to_vec();
+        if let Some(path) = &args.from_file {
+            cities.extend(read_city_file(path)?);
+        }
+        if cities.is_empty() {
+            cities.extend(settings.location.clone());
+        }
+        if cities.is_empty() {
+            return Err(anyhow!("No city specified. Pass one or set a default with `weather config set location <CITY>`."));
+        }
+        if cities.len() > 1 && (args.web || args.output.is_some()) {
+            return Err(anyhow!("--web and --output take a single city; use --metrics-city to add more."));
+        }
+
+        let unit = match (&args.unit, &settings.units) {
+            (Some(u), _) => u.clone().into(),
+            (None, Some(u)) => u.parse().map_err(|e: String| anyhow!(e))?,
+            (None, None) => TemperatureUnit::Celsius,
+        };
+
+        let template = match (&args.template, &settings.template) {
+            (Some(t), _) => t.clone().into(),
+            (None, Some(t)) => t.parse().map_err(|e: String| anyhow!(e))?,
+            (None, None) => DisplayTemplate::Default,
+        };
+
+        // The web server refreshes metrics and notify locations once per TTL
+        let cache_ttl = Duration::from_secs(args.cache_ttl.or(settings.cache_ttl).unwrap_or(300));
+        if cache_ttl < MIN_INTERVAL {
+            return Err(anyhow!(
+                "Cache TTL must be at least {}.",
+                humantime::format_duration(MIN_INTERVAL)
+            ));
+        }
+
+        Ok(Self {
+            cities,
+            unit,
+            template,
+            trend: args.trend,
+            theme: args.theme.clone().or(settings.theme.clone()).unwrap_or_else(|| "auto".to_string()),
+            port: args.port.or(settings.port).unwrap_or(8080),
+            cache_ttl,
+            locations,
+            notify,
+            history: settings.history()?.map(Arc::new),
+            settings,
+        })
+    }
+
+    pub(crate) fn api_client(&self) -> Result<WeatherApiClient> {
+        self.settings.api_client()
+    }
+
+    /// A cache over the API client that resolves aliases and records history.
+    pub(crate) fn cache(&self) -> Result<CachedWeatherClient> {
+        Ok(CachedWeatherClient::new(self.api_client()?, self.cache_ttl)?
+            .with_locations(self.locations.clone())
+            .with_history(self.history.clone()))
+    }
+
+    /// Store a one-off observation when history recording is on.
+    pub(crate) fn record(&self, location: &str, weather: &WeatherResponse) {
+        if let Some(history) = &self.history {
+            if let Err(e) = history.record(location, self.settings.provider(), weather) {
+                println!("{} Failed to record history: {}", icon("⚠️", "!").yellow(), e);
+            }
+        }
+    }
+
+    /// The notifier for `[notify]`, or `None` when no rule could ever fire.
+    pub(crate) fn notifier(&self) -> Result<Option<Notifier>> {
+        let notifier = Notifier::new(self.notify.clone())?;
+        Ok((!notifier.is_empty()).then_some(notifier))
+    }
+
+    /// The first requested city; the only one outside comparison mode.
+    pub(crate) fn city(&self) -> &str {
+        &self.cities[0]
+    }
+
+    /// The requested city, with saved aliases replaced by their coordinates.
+    pub(crate) fn place(&self) -> Place {
+        self.locations.resolve(self.city())
+    }
+}
@@ -0,0 +1,107 @@
+//! `--web`: serve the page, widgets and `/metrics` while observations are
+//! refreshed in the background.
+
+use super::{Args, Options};
+use crate::api::cache::CachedWeatherClient;
+use crate::notify::Notifier;
+use crate::utils::glyphs::icon;
+use crate::utils::metrics::MetricsCollector;
+use crate::utils::server;
+use crate::utils::themes::ThemeRegistry;
+use anyhow::Result;
+use colored::Colorize;
+use std::net::{IpAddr, Ipv4Addr};
+use std::sync::Arc;
+use std::time::Duration;
+
+pub(super) fn run_web(options: &Options, args: &Args, themes: ThemeRegistry) -> Result<()> {
+    let metrics_cities = &args.metrics_cities;
+    let city = options.city();
+    let unit = options.unit;
+    let bind = args.bind.unwrap_or(IpAddr::V4(Ipv4Addr::LOCALHOST));
+    println!("{} Fetching weather data for '{}'...", icon("⏳", "*").yellow(), city);
+    
+    let api_client = options.api_client()?;
+    let place = options.place();
+    let mut locations = vec![city.to_string()];
+    locations.extend(metrics_cities.iter().filter(|c| c.as_str() != city).cloned());
+    let widget_cities = (!args.widget_any_city).then(|| {
+        let mut cities = locations.clone();
+        cities.extend(options.locations.iter().map(|(alias, _)| alias.clone()));
+        cities
+    });
+    let embed = server::EmbedPolicy { allowed_origins: args.allow_origins.clone(), widget_cities };
+    let notifier = options.notifier()?;
+    let cache = Arc::new(
+        CachedWeatherClient::new(api_client, options.cache_ttl)?
+            .with_locations(options.locations.clone())
+            .with_history(options.history.clone()),
+    );
+    let async_client = cache.async_client().clone();
+
+    let rt = actix_web::rt::System::new();
+    rt.block_on(async {
+        // Current weather and forecast in parallel on the server's runtime
+        let (weather, forecast) = tokio::join!(
+            async_client.get_weather_at(&place),
+            async_client.get_forecast_at(&place)
+        );
+        let weather = cache.insert(city, weather?);
+        let forecast = forecast
+            .inspect_err(|e| println!("{} Forecast unavailable: {}", icon("⚠️", "!").yellow(), e))
+            .ok();
+
+        let state = server::WeatherState { city: city.to_string(), weather, forecast, unit, themes };
+        if notifier.is_some() || options.history.is_some() {
+            // Without a recorder the cache only refreshes when someone visits
+            let recorded = if options.history.is_some() { locations.clone() } else { Vec::new() };
+            spawn_monitor(notifier, Arc::clone(&cache), city.to_string(), recorded, options.cache_ttl);
+        }
+        let collector = Arc::new(MetricsCollector::new(Arc::clone(&cache), locations));
+        collector.spawn_refresh(options.cache_ttl);
+        server::start_server(state, bind, options.port, cache, collector, embed).await
+    })?;
+    
+    Ok(())
+}
+
+/// Refresh observations in the background while the web server runs, once
+/// per cache period: `recorded` locations keep the history filling up and
+/// notification rules see every new observation.
+fn spawn_monitor(
+    mut notifier: Option<Notifier>,
+    cache: Arc<CachedWeatherClient>,
+    city: String,
+    recorded: Vec<String>,
+    period: Duration,
+) {
+    if let Some(notifier) = &notifier {
+        println!("{} Notification rules active for {}", icon("🔔", "*").cyan(), notifier.locations(&city).join(", "));
+    }
+    if !recorded.is_empty() {
+        println!("{} Recording history for {}", icon("🗄️", "*").cyan(), recorded.join(", "));
+    }
+
+    std::thread::spawn(move || loop {
+        let mut locations = recorded.clone();
+        for location in notifier.as_ref().map(|n| n.locations(&city)).unwrap_or_default() {
+            if !locations.iter().any(|l| l.eq_ignore_ascii_case(&location)) {
+                locations.push(location);
+            }
+        }
+
+        for location in &locations {
+            match cache.get_weather(location) {
+                Ok(weather) => {
+                    if let Some(notifier) = notifier.as_mut() {
+                        for line in notifier.evaluate(location, &city, &weather) {
+                            println!("{}", line);
+                        }
+                    }
+                }
+                Err(e) => println!("{} Background refresh for '{}' failed: {}", icon("⚠️", "!").yellow(), location, e),
+            }
+        }
+        std::thread::sleep(period);
+    });
+}
@@ -0,0 +1,179 @@
+use crate::api::clients::{Place, WeatherApiClient};
+use crate::models::weather::{TemperatureUnit, WeatherResponse};
+use crate::utils::condition::ConditionGroup;
+use crate::utils::formatters::format_day_time;
+use crate::utils::glyphs::{icon, Charset};
+use crate::utils::rules::{first_match, Comparator, Metric, Observation, Rule};
+use anyhow::{anyhow, Result};
+use clap::Args;
+use colored::Colorize;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Exit status when no rule matched.
+pub const EXIT_CLEAR: i32 = 0;
+/// Exit status when at least one rule matched.
+pub const EXIT_MATCHED: i32 = 1;
+/// Exit status when the check could not run (bad arguments, API failure).
+pub const EXIT_ERROR: i32 = 2;
+
+#[derive(Args, Debug, Clone)]
+pub struct CheckArgs {
+    /// City name or saved location alias (default: `location` from the config file)
+    pub place: Option<String>,
+
+    /// Match when the temperature is above this value (in --unit)
+    #[arg(long, value_name = "DEG", allow_negative_numbers = true)]
+    pub temp_above: Option<f64>,
+
+    /// Match when the temperature is below this value (in --unit)
+    #[arg(long, value_name = "DEG", allow_negative_numbers = true)]
+    pub temp_below: Option<f64>,
+
+    /// Match when the wind speed is above this value (m/s)
+    #[arg(long, value_name = "M/S")]
+    pub wind_above: Option<f64>,
+
+    /// Match when wind gusts are above this value (m/s)
+    #[arg(long, value_name = "M/S")]
+    pub gust_above: Option<f64>,
+
+    /// Match when the humidity is above this value (%)
+    #[arg(long, value_name = "PERCENT")]
+    pub humidity_above: Option<f64>,
+
+    /// Match rain or drizzle, or any rain volume in the forecast window
+    #[arg(long)]
+    pub rain: bool,
+
+    /// Match snow
+    #[arg(long)]
+    pub snow: bool,
+
+    /// Match a condition group: thunderstorm, drizzle, rain, snow, mist, clear, clouds (repeatable)
+    #[arg(long, value_name = "NAME")]
+    pub condition: Vec<ConditionGroup>,
+
+    /// Any other rule, e.g. "pressure<1000" or "pop>60" (repeatable)
+    #[arg(long = "rule", value_name = "RULE")]
+    pub rules: Vec<Rule>,
+
+    /// Also check the forecast for the next N hours (3 hour steps)
+    #[arg(long, value_name = "N", default_value_t = 0)]
+    pub hours: u32,
+
+    /// Print nothing but errors; only set the exit status
+    #[arg(short, long)]
+    pub quiet: bool,
+}
+
+impl CheckArgs {
+    /// All rules requested on the command line, in a stable order.
+    pub fn rules(&self) -> Vec<Rule> {
+        let threshold = |metric, comparator, value: Option<f64>| {
+            value.map(|value| Rule::Threshold { metric, comparator, value })
+        };
+
+        let mut rules: Vec<Rule> = [
+            threshold(Metric::Temp, Comparator::Above, self.temp_above),
+            threshold(Metric::Temp, Comparator::Below, self.temp_below),
+            threshold(Metric::Wind, Comparator::Above, self.wind_above),
+            threshold(Metric::Gust, Comparator::Above, self.gust_above),
+            threshold(Metric::Humidity, Comparator::Above, self.humidity_above),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        if self.rain {
+            rules.push(Rule::Condition(ConditionGroup::Rain));
+        }
+        if self.snow {
+            rules.push(Rule::Condition(ConditionGroup::Snow));
+        }
+        rules.extend(self.condition.iter().map(|c| Rule::Condition(*c)));
+        rules.extend(self.rules.iter().cloned());
+        rules
+    }
+}
+
+/// Result of a check, with the observation it was based on.
+pub struct Outcome {
+    pub matched: bool,
+    pub weather: WeatherResponse,
+}
+
+/// Evaluate the rules for `place` and report whether any of them matched.
+/// Rules are OR-ed: a single match is enough.
+pub fn run(
+    args: &CheckArgs,
+    client: &WeatherApiClient,
+    place: &Place,
+    unit: &TemperatureUnit,
+) -> Result<Outcome> {
+    let rules = args.rules();
+    if rules.is_empty() {
+        return Err(anyhow!(
+            "No rules given. Use --temp-above, --wind-above, --rain, --condition, --rule, ..."
+        ));
+    }
+
+    let weather = client.get_weather_at(place)?;
+    let mut observations = vec![Observation::from(&weather)];
+    let mut timezone = weather.timezone;
+
+    if args.hours > 0 {
+        let forecast = client.get_forecast_at(place)?;
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        let until = now + args.hours as u64 * 3600;
+        timezone = forecast.city.timezone;
+        observations.extend(
+            forecast
+                .list
+                .iter()
+                .filter(|item| item.dt <= until)
+                .map(Observation::from),
+        );
+    }
+
+    if !args.quiet {
+        let window = match args.hours {
+            0 => "now".to_string(),
+            h => format!("now and the next {}h", h),
+        };
+        println!("{} Checking {} ({})", icon("🔎", "*").cyan(), weather.name.bold(), window);
+    }
+
+    let g = Charset::current();
+    let mut matched = 0;
+    for rule in &rules {
+        let result = first_match(rule, &observations, unit);
+        if result.is_some() {
+            matched += 1;
+        }
+        if args.quiet {
+            continue;
+        }
+
+        match result {
+            Some((observation, observed)) => {
+                let when = match observation.time {
+                    None => "now".to_string(),
+                    Some(time) => format!("at {}", format_day_time(time, timezone)),
+                };
+                println!(
+                    "  {} {}  {} {}",
+                    "MATCH".red().bold(),
+                    g.text(&rule.describe(unit)),
+                    g.text(&observed).bold(),
+                    when.dimmed()
+                );
+            }
+            None => println!("  {} {}", "clear".green(), g.text(&rule.describe(unit))),
+        }
+    }
+
+    if !args.quiet {
+        println!("{} of {} rules matched", matched, rules.len());
+    }
+    Ok(Outcome { matched: matched > 0, weather })
+}
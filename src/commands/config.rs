@@ -0,0 +1,119 @@
+use crate::config::settings::Config;
+use crate::utils::glyphs::icon;
+use anyhow::{anyhow, Result};
+use clap::Subcommand;
+use colored::Colorize;
+use std::env;
+use std::path::Path;
+use std::process::Command;
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum ConfigAction {
+    /// Print the value of a key (e.g. units, profiles.work.location)
+    Get { key: String },
+    /// Set a key in the config file
+    Set { key: String, value: String },
+    /// Remove a key from the config file
+    Unset { key: String },
+    /// List every key in the config file
+    List,
+    /// Print the config file location
+    Path,
+    /// Open the config file in $VISUAL / $EDITOR
+    Edit,
+}
+
+pub fn run(action: ConfigAction, path: Option<&Path>) -> Result<()> {
+    let mut config = Config::load(path)?;
+
+    match action {
+        ConfigAction::Get { key } => {
+            let value = config
+                .get(&key)
+                .ok_or_else(|| anyhow!("'{}' is not set in {}", key, config.path().display()))?;
+            println!("{}", display_value(&key, value));
+        }
+        ConfigAction::Set { key, value } => {
+            config.set(&key, &value)?;
+            config.save()?;
+            println!("{} {} updated in {}", icon("✅", "*").green(), key.bold(), config.path().display());
+        }
+        ConfigAction::Unset { key } => {
+            if !config.unset(&key) {
+                return Err(anyhow!("'{}' is not set in {}", key, config.path().display()));
+            }
+            config.save()?;
+            println!("{} {} removed from {}", icon("✅", "*").green(), key.bold(), config.path().display());
+        }
+        ConfigAction::List => {
+            let entries = config.entries();
+            if entries.is_empty() {
+                println!("{} No settings in {}", icon("ℹ️", "*").cyan(), config.path().display());
+            }
+            for (key, value) in entries {
+                println!("{} = {}", key.bold(), display_value(&key, &value));
+            }
+        }
+        ConfigAction::Path => {
+            println!("{}", config.path().display());
+        }
+        ConfigAction::Edit => {
+            if !config.path().exists() {
+                config.save()?;
+            }
+            let editor = env::var("VISUAL")
+                .or_else(|_| env::var("EDITOR"))
+                .unwrap_or_else(|_| if cfg!(windows) { "notepad".into() } else { "vi".into() });
+            let status = Command::new(&editor).arg(config.path()).status()
+                .map_err(|e| anyhow!("Failed to start editor '{}': {}", editor, e))?;
+            if !status.success() {
+                return Err(anyhow!("Editor '{}' exited with {}", editor, status));
+            }
+            // Catch mistakes right away rather than on the next run
+            Config::load(Some(config.path()))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Render a value for the terminal, masking API keys, passwords and the
+/// webhook URLs of notification channels.
+fn display_value(key: &str, value: &toml::Value) -> String {
+    if key.split('.').any(|p| p == "api_keys") || key == "mqtt.password" {
+        if let toml::Value::String(s) = value {
+            let visible: String = s.chars().take(4).collect();
+            return format!("{}{}", visible, icon("…", "..."));
+        }
+    }
+
+    let value = if key == "notify" || key.starts_with("notify.channels") {
+        mask_urls(key.rsplit('.').next().unwrap_or(key), value)
+    } else {
+        value.clone()
+    };
+
+    match value {
+        toml::Value::String(s) => s,
+        other => other.to_string(),
+    }
+}
+
+/// Replace every `url` under `value` with its scheme and host; the rest of a
+/// webhook URL is the credential.
+fn mask_urls(key: &str, value: &toml::Value) -> toml::Value {
+    match value {
+        toml::Value::String(url) if key == "url" => {
+            let shown = reqwest::Url::parse(url)
+                .ok()
+                .and_then(|u| u.host_str().map(|host| format!("{}://{}/{}", u.scheme(), host, icon("…", "..."))))
+                .unwrap_or_else(|| icon("…", "...").to_string());
+            toml::Value::String(shown)
+        }
+        toml::Value::Array(items) => toml::Value::Array(items.iter().map(|v| mask_urls(key, v)).collect()),
+        toml::Value::Table(table) => {
+            toml::Value::Table(table.iter().map(|(k, v)| (k.clone(), mask_urls(k, v))).collect())
+        }
+        other => other.clone(),
+    }
+}
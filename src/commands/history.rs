@@ -0,0 +1,268 @@
+use crate::history::{History, Record, Stats};
+use crate::models::weather::TemperatureUnit;
+use crate::utils::glyphs::icon;
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Local, TimeZone, Utc};
+use clap::{Args, ValueEnum};
+use colored::Colorize;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[derive(Args, Debug, Clone)]
+pub struct HistoryArgs {
+    /// City name or saved location alias; lists recorded locations when omitted
+    pub place: Option<String>,
+
+    /// How far back to look, e.g. 24h, 7d, 4w
+    #[arg(long, default_value = "7d", value_parser = humantime::parse_duration)]
+    pub since: Duration,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = HistoryFormat::Summary)]
+    pub format: HistoryFormat,
+
+    /// Write to a file instead of stdout
+    #[arg(short, long, value_name = "FILE")]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HistoryFormat {
+    /// Min/max/mean per metric
+    Summary,
+    /// Every observation as CSV
+    Csv,
+    /// Summary and every observation as JSON
+    Json,
+}
+
+/// Metrics summarized by `weather history`: label, JSON key, suffix and value.
+type MetricColumn = (&'static str, &'static str, &'static str, fn(&Record, &TemperatureUnit) -> Option<f64>);
+
+const METRICS: [MetricColumn; 7] = [
+    ("Temperature", "temp", "", |r, u| Some(u.convert(r.temp))),
+    ("Feels like", "feels_like", "", |r, u| Some(u.convert(r.feels_like))),
+    ("Humidity", "humidity", "%", |r, _| Some(r.humidity as f64)),
+    ("Pressure", "pressure", " hPa", |r, _| Some(r.pressure as f64)),
+    ("Wind", "wind_speed", " m/s", |r, _| Some(r.wind_speed)),
+    ("Gusts", "wind_gust", " m/s", |r, _| r.wind_gust),
+    ("Clouds", "clouds", "%", |r, _| Some(r.clouds as f64)),
+];
+
+/// One exported observation, with temperatures in the requested unit.
+#[derive(Serialize)]
+struct ExportRow<'a> {
+    observed_at: String,
+    location: &'a str,
+    name: &'a str,
+    country: &'a str,
+    lat: f64,
+    lon: f64,
+    provider: &'a str,
+    unit: String,
+    temp: f64,
+    feels_like: f64,
+    temp_min: f64,
+    temp_max: f64,
+    pressure: u32,
+    humidity: u32,
+    sea_level: Option<u32>,
+    grnd_level: Option<u32>,
+    wind_speed: f64,
+    wind_deg: u32,
+    wind_gust: Option<f64>,
+    clouds: u32,
+    condition_id: Option<u32>,
+    description: Option<&'a str>,
+    /// Space separated, so CSV keeps one column
+    condition_ids: String,
+}
+
+#[derive(Serialize)]
+struct JsonExport<'a> {
+    location: &'a str,
+    since: String,
+    unit: String,
+    count: usize,
+    summary: BTreeMap<&'static str, Stats>,
+    observations: Vec<ExportRow<'a>>,
+}
+
+impl<'a> ExportRow<'a> {
+    fn new(record: &'a Record, unit: &TemperatureUnit) -> Self {
+        let round = |kelvin: f64| (unit.convert(kelvin) * 100.0).round() / 100.0;
+        Self {
+            observed_at: utc(record.observed_at).to_rfc3339(),
+            location: &record.location,
+            name: &record.name,
+            country: &record.country,
+            lat: record.lat,
+            lon: record.lon,
+            provider: &record.provider,
+            unit: unit.to_string(),
+            temp: round(record.temp),
+            feels_like: round(record.feels_like),
+            temp_min: round(record.temp_min),
+            temp_max: round(record.temp_max),
+            pressure: record.pressure,
+            humidity: record.humidity,
+            sea_level: record.sea_level,
+            grnd_level: record.grnd_level,
+            wind_speed: record.wind_speed,
+            wind_deg: record.wind_deg,
+            wind_gust: record.wind_gust,
+            clouds: record.clouds,
+            condition_id: record.condition_id,
+            description: record.description.as_deref(),
+            condition_ids: record.condition_ids.iter().map(u32::to_string).collect::<Vec<_>>().join(" "),
+        }
+    }
+}
+
+pub fn run(args: &HistoryArgs, history: &History, unit: &TemperatureUnit) -> Result<()> {
+    let Some(place) = &args.place else {
+        return list_locations(history);
+    };
+
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    let since = now.saturating_sub(args.since.as_secs());
+    let records = history.query(place, since)?;
+    if records.is_empty() {
+        return Err(anyhow!(
+            "No observations of '{}' in the last {} in {}. Enable recording with `weather config set record_history true`.",
+            place,
+            humantime::format_duration(args.since),
+            history.path().display()
+        ));
+    }
+
+    let mut out: Box<dyn Write> = match &args.output {
+        Some(path) => {
+            // Files get plain text, not terminal escape codes
+            colored::control::set_override(false);
+            Box::new(File::create(path).with_context(|| format!("Failed to create {}", path.display()))?)
+        }
+        None => Box::new(io::stdout().lock()),
+    };
+
+    match args.format {
+        HistoryFormat::Summary => write_summary(&mut out, place, &records, args.since, unit)?,
+        HistoryFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(out);
+            for record in &records {
+                writer.serialize(ExportRow::new(record, unit))?;
+            }
+            writer.flush()?;
+        }
+        HistoryFormat::Json => {
+            let summary: BTreeMap<&str, Stats> = METRICS
+                .iter()
+                .filter_map(|(_, key, _, value)| Some((*key, Stats::of(records.iter().filter_map(|r| value(r, unit)))?.rounded())))
+                .collect();
+            let document = JsonExport {
+                location: place,
+                since: utc(since).to_rfc3339(),
+                unit: unit.to_string(),
+                count: records.len(),
+                summary,
+                observations: records.iter().map(|r| ExportRow::new(r, unit)).collect(),
+            };
+            serde_json::to_writer_pretty(&mut out, &document)?;
+            writeln!(out)?;
+        }
+    }
+
+    if let Some(path) = &args.output {
+        colored::control::unset_override();
+        eprintln!("{} Wrote {} observations to {}", icon("✅", "*").green(), records.len(), path.display());
+    }
+    Ok(())
+}
+
+fn write_summary(
+    out: &mut impl Write,
+    place: &str,
+    records: &[Record],
+    window: Duration,
+    unit: &TemperatureUnit,
+) -> Result<()> {
+    let last = &records[records.len() - 1];
+    let label = if last.country.is_empty() {
+        last.name.clone()
+    } else {
+        format!("{}, {}", last.name, last.country)
+    };
+
+    writeln!(
+        out,
+        "{} {} {}",
+        icon("🗄️ HISTORY", "HISTORY").bold().cyan(),
+        label.bold(),
+        format!("({}, last {})", place, humantime::format_duration(window)).dimmed()
+    )?;
+    writeln!(
+        out,
+        "{} observations from {} to {}",
+        records.len(),
+        local(records[0].observed_at).format("%a %d %b %H:%M"),
+        local(last.observed_at).format("%a %d %b %H:%M")
+    )?;
+    writeln!(out, "{}", icon("─", "-").repeat(52).dimmed())?;
+    let heading = |title: &str| format!("{:>12}", title).bold();
+    writeln!(out, "{:<14}{}{}{}", "", heading("min"), heading("max"), heading("mean"))?;
+
+    for (label, _, suffix, value) in &METRICS {
+        let Some(stats) = Stats::of(records.iter().filter_map(|r| value(r, unit))) else {
+            continue;
+        };
+        let suffix = if suffix.is_empty() { unit.to_string() } else { suffix.to_string() };
+        let cell = |v: f64| format!("{:>12}", format!("{:.1}{}", v, suffix));
+        writeln!(
+            out,
+            "{:<14}{}{}{}",
+            label,
+            cell(stats.min).blue(),
+            cell(stats.max).red(),
+            cell(stats.mean).bold()
+        )?;
+    }
+    Ok(())
+}
+
+fn list_locations(history: &History) -> Result<()> {
+    let locations = history.locations()?;
+    if locations.is_empty() {
+        println!(
+            "{} No history in {} yet. Enable recording with `weather config set record_history true`.",
+            icon("ℹ️", "*").cyan(),
+            history.path().display()
+        );
+        return Ok(());
+    }
+
+    println!("{} {}", icon("🗄️ Recorded locations in", "Recorded locations in").bold(), history.path().display());
+    for location in locations {
+        println!(
+            "  {} {:<20} {:>6} observations  {} {} {}",
+            format!("{:<16}", location.location).bold(),
+            location.name,
+            location.count,
+            local(location.first).format("%Y-%m-%d %H:%M"),
+            icon("→", "->"),
+            local(location.last).format("%Y-%m-%d %H:%M")
+        );
+    }
+    Ok(())
+}
+
+fn utc(timestamp: u64) -> DateTime<Utc> {
+    Utc.timestamp_opt(timestamp as i64, 0).single().unwrap_or_default()
+}
+
+fn local(timestamp: u64) -> DateTime<Local> {
+    utc(timestamp).with_timezone(&Local)
+}
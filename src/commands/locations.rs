@@ -0,0 +1,124 @@
+use crate::config::locations::{validate_alias, SavedLocation};
+use crate::config::settings::Config;
+use crate::utils::glyphs::icon;
+use anyhow::{anyhow, Result};
+use clap::Subcommand;
+use colored::Colorize;
+use std::path::Path;
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum LocationsAction {
+    /// Geocode a place and save it under an alias
+    Add {
+        /// Short name to type instead of the place, e.g. home, office, dc-fra
+        alias: String,
+        /// Place to geocode, e.g. "Frankfurt am Main, DE"
+        #[arg(required_unless_present = "lat")]
+        place: Option<String>,
+        /// Latitude, to save coordinates without geocoding
+        #[arg(long, requires = "lon", allow_negative_numbers = true)]
+        lat: Option<f64>,
+        /// Longitude, to save coordinates without geocoding
+        #[arg(long, requires = "lat", allow_negative_numbers = true)]
+        lon: Option<f64>,
+        /// Replace an existing alias
+        #[arg(short, long)]
+        force: bool,
+    },
+    /// Remove a saved alias
+    #[command(alias = "remove")]
+    Rm { alias: String },
+    /// List saved aliases
+    #[command(alias = "list")]
+    Ls,
+    /// Rename a saved alias
+    Rename { from: String, to: String },
+}
+
+pub fn run(action: LocationsAction, path: Option<&Path>, profile: Option<&str>) -> Result<()> {
+    let mut config = Config::load(path)?;
+    let locations = config.locations()?;
+
+    match action {
+        LocationsAction::Add { alias, place, lat, lon, force } => {
+            let alias = validate_alias(&alias)?;
+            if locations.get(&alias).is_some() && !force {
+                return Err(anyhow!("Alias '{}' already exists. Use --force to replace it.", alias));
+            }
+
+            let location = match (lat, lon) {
+                (Some(lat), Some(lon)) => {
+                    if !(-90.0..=90.0).contains(&lat) || !(-180.0..=180.0).contains(&lon) {
+                        return Err(anyhow!("Coordinates out of range: {}, {}", lat, lon));
+                    }
+                    SavedLocation {
+                        name: place.unwrap_or_else(|| alias.clone()),
+                        country: None,
+                        state: None,
+                        lat,
+                        lon,
+                    }
+                }
+                _ => {
+                    let place = place.unwrap_or_default();
+                    let client = config.settings(profile)?.api_client()?;
+                    SavedLocation::from(client.geocode(&place)?)
+                }
+            };
+
+            config.insert_location(&alias, &location)?;
+            config.save()?;
+            println!(
+                "{} {} {} {} ({:.4}, {:.4})",
+                icon("✅", "*").green(),
+                alias.bold(),
+                icon("→", "->"),
+                location.label(),
+                location.lat,
+                location.lon
+            );
+        }
+        LocationsAction::Rm { alias } => {
+            let alias = alias.trim().to_lowercase();
+            if !config.remove_location(&alias) {
+                return Err(anyhow!("No saved location named '{}'.", alias));
+            }
+            config.save()?;
+            println!("{} Removed {}", icon("✅", "*").green(), alias.bold());
+        }
+        LocationsAction::Ls => {
+            if locations.is_empty() {
+                println!(
+                    "{} No saved locations. Add one with `weather locations add <ALIAS> <PLACE>`.",
+                    icon("ℹ️", "*").cyan()
+                );
+            }
+            let width = locations.iter().map(|(alias, _)| alias.len()).max().unwrap_or(0);
+            for (alias, location) in locations.iter() {
+                println!(
+                    "{}  {}  {}",
+                    format!("{:<width$}", alias, width = width).bold(),
+                    location.label(),
+                    format!("({:.4}, {:.4})", location.lat, location.lon).dimmed()
+                );
+            }
+        }
+        LocationsAction::Rename { from, to } => {
+            let from = from.trim().to_lowercase();
+            let to = validate_alias(&to)?;
+            let location = locations
+                .get(&from)
+                .ok_or_else(|| anyhow!("No saved location named '{}'.", from))?;
+            if locations.get(&to).is_some() {
+                return Err(anyhow!("Alias '{}' already exists.", to));
+            }
+
+            config.remove_location(&from);
+            config.insert_location(&to, location)?;
+            config.save()?;
+            println!("{} Renamed {} to {}", icon("✅", "*").green(), from.bold(), to.bold());
+        }
+    }
+
+    Ok(())
+}
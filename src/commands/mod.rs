@@ -0,0 +1,9 @@
+pub mod check;
+pub mod config;
+#[cfg(feature = "history")]
+pub mod history;
+pub mod locations;
+#[cfg(feature = "mqtt")]
+pub mod mqtt;
+pub mod notify;
+pub mod watch;
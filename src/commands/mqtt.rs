@@ -0,0 +1,229 @@
+use crate::api::cache::CachedWeatherClient;
+use crate::config::mqtt::MqttConfig;
+use crate::models::weather::{TemperatureUnit, WeatherResponse};
+use crate::mqtt::{Broker, BrokerEvent, Publisher};
+use crate::utils::glyphs::icon;
+use anyhow::{anyhow, Result};
+use chrono::Local;
+use clap::Args;
+use colored::Colorize;
+use std::env;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// How often the loop wakes up to check for Ctrl-C and broker events.
+const TICK: Duration = Duration::from_millis(250);
+
+#[derive(Args, Debug, Clone)]
+pub struct MqttArgs {
+    /// Cities or aliases to publish (default: `location` from the config file)
+    pub places: Vec<String>,
+
+    /// Broker host (default: `mqtt.host` or localhost)
+    #[arg(long)]
+    pub host: Option<String>,
+
+    /// Broker port (default: `mqtt.port` or 1883)
+    #[arg(long)]
+    pub port: Option<u16>,
+
+    /// Broker username
+    #[arg(long)]
+    pub username: Option<String>,
+
+    /// Broker password [env: WEATHER_MQTT_PASSWORD]
+    #[arg(long)]
+    pub password: Option<String>,
+
+    /// MQTT client id (default: weather-cli-<hostname>)
+    #[arg(long)]
+    pub client_id: Option<String>,
+
+    /// Prefix for state and availability topics (default: weather)
+    #[arg(long)]
+    pub topic_prefix: Option<String>,
+
+    /// Home Assistant discovery prefix (default: homeassistant)
+    #[arg(long)]
+    pub discovery_prefix: Option<String>,
+
+    /// Time between publishes, e.g. 5m, 1h
+    #[arg(short, long, default_value = "10m", value_parser = humantime::parse_duration)]
+    pub interval: Duration,
+}
+
+/// A published location and the last observation for it, so discovery and
+/// state can be replayed when the broker or Home Assistant comes back.
+struct Location {
+    query: String,
+    weather: Option<Arc<WeatherResponse>>,
+}
+
+/// Publish sensors for `places` until Ctrl-C.
+pub fn run(
+    args: &MqttArgs,
+    config: MqttConfig,
+    cache: &CachedWeatherClient,
+    places: &[String],
+    unit: TemperatureUnit,
+) -> Result<()> {
+    if args.interval < super::watch::MIN_INTERVAL {
+        return Err(anyhow!(
+            "Publish interval must be at least {}.",
+            humantime::format_duration(super::watch::MIN_INTERVAL)
+        ));
+    }
+
+    let broker = Broker {
+        host: args.host.clone().or(config.host).unwrap_or_else(|| "localhost".to_string()),
+        port: args.port.or(config.port).unwrap_or(1883),
+        username: args.username.clone().or(config.username),
+        password: args
+            .password
+            .clone()
+            .or_else(|| env::var("WEATHER_MQTT_PASSWORD").ok())
+            .or(config.password),
+        client_id: args.client_id.clone().or(config.client_id).unwrap_or_else(default_client_id),
+    };
+    let prefix = args.topic_prefix.clone().or(config.topic_prefix).unwrap_or_else(|| "weather".to_string());
+    let discovery_prefix = args
+        .discovery_prefix
+        .clone()
+        .or(config.discovery_prefix)
+        .unwrap_or_else(|| "homeassistant".to_string());
+
+    let stop = Arc::new(AtomicBool::new(false));
+    {
+        let stop = Arc::clone(&stop);
+        ctrlc::set_handler(move || stop.store(true, Ordering::SeqCst))?;
+    }
+
+    println!(
+        "{} Publishing {} to {}:{} every {} (Ctrl-C to stop)",
+        icon("📡", "*").cyan(),
+        places.join(", ").bold(),
+        broker.host,
+        broker.port,
+        humantime::format_duration(args.interval)
+    );
+
+    let publisher = Publisher::connect(&broker, &prefix, &discovery_prefix, unit)?;
+    let mut locations: Vec<Location> = places
+        .iter()
+        .map(|query| Location { query: query.clone(), weather: None })
+        .collect();
+    let result = publish_loop(&publisher, cache, &mut locations, args.interval, &stop);
+
+    publisher.shutdown();
+    println!("{} Stopped publishing", icon("👋", "*").cyan());
+    result
+}
+
+fn publish_loop(
+    publisher: &Publisher,
+    cache: &CachedWeatherClient,
+    locations: &mut [Location],
+    interval: Duration,
+    stop: &AtomicBool,
+) -> Result<()> {
+    let mut connected = false;
+    let mut last_error: Option<String> = None;
+    let mut next_refresh = Instant::now();
+
+    while !stop.load(Ordering::SeqCst) {
+        match publisher.next_event(TICK) {
+            Some(BrokerEvent::Connected) => {
+                connected = true;
+                last_error = None;
+                log(&format!("{} Connected to broker", icon("✅", "*").green()));
+                publisher.online()?;
+                for location in locations.iter() {
+                    announce(publisher, location)?;
+                }
+            }
+            Some(BrokerEvent::HomeAssistantOnline) if connected => {
+                log(&format!("{} Home Assistant restarted, resending discovery", icon("🔁", "*").cyan()));
+                for location in locations.iter() {
+                    announce(publisher, location)?;
+                }
+            }
+            Some(BrokerEvent::Error(e)) => {
+                connected = false;
+                // Retries repeat the same error every few seconds; report it once
+                if last_error.as_ref() != Some(&e) {
+                    log(&format!("{} Broker unavailable: {} (retrying)", icon("⚠️", "!").yellow(), e));
+                    last_error = Some(e);
+                }
+            }
+            Some(BrokerEvent::Disconnected) => return Err(anyhow!("MQTT connection closed")),
+            _ => {}
+        }
+
+        if Instant::now() < next_refresh {
+            continue;
+        }
+        for location in locations.iter_mut() {
+            match cache.get_weather(&location.query) {
+                Ok(weather) => {
+                    let first = location.weather.is_none();
+                    location.weather = Some(weather);
+                    // Until connected the sample is only kept, then replayed on connect
+                    if !connected {
+                        continue;
+                    }
+                    if first {
+                        announce(publisher, location)?;
+                    } else {
+                        publish_state(publisher, location)?;
+                    }
+                }
+                Err(e) => log(&format!("{} {}: {}", icon("⚠️", "!").yellow(), location.query, e)),
+            }
+        }
+        next_refresh = Instant::now() + interval;
+    }
+
+    Ok(())
+}
+
+/// Discovery configs followed by the current state, once an observation exists.
+fn announce(publisher: &Publisher, location: &Location) -> Result<()> {
+    let Some(weather) = &location.weather else {
+        return Ok(());
+    };
+    let label = if weather.sys.country.is_empty() {
+        weather.name.clone()
+    } else {
+        format!("{}, {}", weather.name, weather.sys.country)
+    };
+    publisher.announce(&location.query, &label)?;
+    publish_state(publisher, location)
+}
+
+fn publish_state(publisher: &Publisher, location: &Location) -> Result<()> {
+    let Some(weather) = &location.weather else {
+        return Ok(());
+    };
+    publisher.publish_state(&location.query, weather)?;
+    log(&format!(
+        "{} {} {} {}",
+        icon("📤", "*").cyan(),
+        location.query.bold(),
+        weather.format_temperature(publisher.unit()).yellow(),
+        weather.description()
+    ));
+    Ok(())
+}
+
+fn log(message: &str) {
+    println!("{} {}", Local::now().format("%H:%M:%S").to_string().dimmed(), message);
+}
+
+fn default_client_id() -> String {
+    let host = env::var("HOSTNAME")
+        .ok()
+        .filter(|h| !h.is_empty())
+        .unwrap_or_else(|| std::process::id().to_string());
+    format!("weather-cli-{}", host)
+}
@@ -0,0 +1,70 @@
+use crate::config::settings::Config;
+use crate::models::weather::TemperatureUnit;
+use crate::notify::Notifier;
+use crate::utils::glyphs::{icon, Charset};
+use anyhow::Result;
+use clap::Subcommand;
+use colored::Colorize;
+use std::path::Path;
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum NotifyAction {
+    /// Send a test message to every channel, or just the named one
+    Test { channel: Option<String> },
+    /// List configured channels and rules
+    #[command(alias = "ls")]
+    Rules,
+}
+
+pub fn run(
+    action: NotifyAction,
+    path: Option<&Path>,
+) -> Result<()> {
+    let config = Config::load(path)?;
+    let notify = config.notify()?;
+
+    match action {
+        NotifyAction::Test { channel } => {
+            let notifier = Notifier::new(notify)?;
+            for line in notifier.send_test(channel.as_deref())? {
+                println!("{}", line);
+            }
+        }
+        NotifyAction::Rules => {
+            if notify.channels.is_empty() && notify.rules.is_empty() {
+                println!("{} No [notify] section in {}", icon("ℹ️", "*").cyan(), config.path().display());
+                return Ok(());
+            }
+
+            println!("{}", "Channels".bold());
+            for channel in &notify.channels {
+                // Webhook URLs are secrets, so only show where they point
+                let host = reqwest::Url::parse(&channel.url)
+                    .ok()
+                    .and_then(|url| url.host_str().map(str::to_string))
+                    .unwrap_or_else(|| "invalid URL".to_string());
+                let kind = format!("{:?}", channel.kind).to_lowercase();
+                println!("  {} {:<8} {}", format!("{:<12}", channel.name).bold(), kind, host.dimmed());
+            }
+
+            println!("{}", "Rules".bold());
+            for (i, rule) in notify.rules.iter().enumerate() {
+                let name = rule.name.clone().unwrap_or_else(|| format!("rule-{}", i + 1));
+                let channels = match rule.channels.is_empty() {
+                    true => "all channels".to_string(),
+                    false => rule.channels.join(", "),
+                };
+                println!(
+                    "  {} {}  {} {} {}",
+                    format!("{:<12}", name).bold(),
+                    Charset::current().text(&rule.rule()?.describe(&TemperatureUnit::Celsius)),
+                    rule.location.as_deref().unwrap_or("(watched place)").cyan(),
+                    icon("·", "-"),
+                    channels.dimmed()
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,274 @@
+use crate::api::cache::CachedWeatherClient;
+use crate::models::weather::{TemperatureUnit, WeatherResponse};
+use crate::notify::Notifier;
+use crate::utils::condition;
+use crate::utils::formatters::*;
+use crate::utils::glyphs::{icon, Charset};
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Local};
+use colored::*;
+use console::Term;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How often the loop wakes up to check for Ctrl-C, resizes and the countdown.
+const TICK: Duration = Duration::from_millis(250);
+
+pub const MIN_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Notification log lines kept under the frame.
+const NOTIFY_LINES: usize = 3;
+
+/// One observation plus when it was taken, so deltas only move when the
+/// cache hands back a new sample.
+struct Sample {
+    weather: Arc<WeatherResponse>,
+    fetched_at: DateTime<Local>,
+}
+
+/// Redraw `place` in place every `interval` until Ctrl-C. Lookups go through
+/// the cache, so an interval shorter than the cache TTL reuses observations
+/// instead of spending API calls. When a notifier is given, each new sample
+/// is checked against the `[notify]` rules, and the other places those rules
+/// name are polled on the same interval.
+pub fn run(
+    cache: &CachedWeatherClient,
+    place: &str,
+    unit: TemperatureUnit,
+    interval: Duration,
+    notifier: Option<Notifier>,
+) -> Result<()> {
+    if interval < MIN_INTERVAL {
+        return Err(anyhow!(
+            "Watch interval must be at least {}.",
+            humantime::format_duration(MIN_INTERVAL)
+        ));
+    }
+
+    let stop = Arc::new(AtomicBool::new(false));
+    {
+        let stop = Arc::clone(&stop);
+        ctrlc::set_handler(move || stop.store(true, Ordering::SeqCst))?;
+    }
+
+    let term = Term::stdout();
+    let _ = term.hide_cursor();
+    let _ = term.clear_screen();
+
+    let result = watch_loop(&term, cache, place, unit, interval, notifier, &stop);
+
+    let _ = term.show_cursor();
+    println!("\n{} Stopped watching {}", icon("👋", "*").cyan(), place.bold());
+    result
+}
+
+fn watch_loop(
+    term: &Term,
+    cache: &CachedWeatherClient,
+    place: &str,
+    unit: TemperatureUnit,
+    interval: Duration,
+    mut notifier: Option<Notifier>,
+    stop: &AtomicBool,
+) -> Result<()> {
+    let mut current: Option<Sample> = None;
+    // Last observation seen for each other rule location
+    let mut others: HashMap<String, Arc<WeatherResponse>> = HashMap::new();
+    let mut notifications: Vec<String> = Vec::new();
+    let mut previous: Option<Sample> = None;
+    let mut last_error: Option<String> = None;
+    let mut next_refresh = Instant::now();
+    let mut size = term.size();
+
+    while !stop.load(Ordering::SeqCst) {
+        if Instant::now() >= next_refresh {
+            match cache.get_weather(place) {
+                Ok(weather) => {
+                    let is_new = !current
+                        .as_ref()
+                        .is_some_and(|s| Arc::ptr_eq(&s.weather, &weather));
+                    if is_new {
+                        if let Some(notifier) = notifier.as_mut() {
+                            log_lines(&mut notifications, notifier.evaluate(place, place, &weather));
+                        }
+                        previous = current.take();
+                        current = Some(Sample { weather, fetched_at: Local::now() });
+                    }
+                    last_error = None;
+                }
+                Err(e) if current.is_none() => return Err(e),
+                Err(e) => last_error = Some(e.to_string()),
+            }
+            if let Some(notifier) = notifier.as_mut() {
+                for location in notifier.locations(place) {
+                    if location.eq_ignore_ascii_case(place) {
+                        continue;
+                    }
+                    match cache.get_weather(&location) {
+                        Ok(weather) => {
+                            if !others.get(&location).is_some_and(|seen| Arc::ptr_eq(seen, &weather)) {
+                                log_lines(&mut notifications, notifier.evaluate(&location, place, &weather));
+                                others.insert(location, weather);
+                            }
+                        }
+                        Err(e) => log_lines(
+                            &mut notifications,
+                            vec![format!("{} Refresh for '{}' failed: {}", icon("⚠️", "!").yellow(), location, e)],
+                        ),
+                    }
+                }
+            }
+            next_refresh = Instant::now() + interval;
+        }
+
+        // A resize can leave wrapped lines behind, so start from a blank screen
+        let new_size = term.size();
+        if new_size != size {
+            size = new_size;
+            let _ = term.clear_screen();
+        }
+
+        if let Some(sample) = &current {
+            let remaining = next_refresh.saturating_duration_since(Instant::now());
+            let mut lines = frame(sample, previous.as_ref(), &unit, remaining, last_error.as_deref());
+            // Keep the Ctrl-C hint last
+            let at = lines.len() - 1;
+            lines.splice(at..at, notifications.iter().cloned());
+            draw(term, &lines, size.1 as usize)?;
+        }
+
+        thread::sleep(TICK);
+    }
+
+    Ok(())
+}
+
+/// Append notifier output to the log under the frame, keeping the newest.
+fn log_lines(log: &mut Vec<String>, lines: Vec<String>) {
+    let stamp = Local::now().format("%H:%M");
+    log.extend(lines.into_iter().map(|line| format!("{} {}", stamp, line)));
+    let excess = log.len().saturating_sub(NOTIFY_LINES);
+    log.drain(..excess);
+}
+
+/// Overwrite the screen from the top without clearing it first, which
+/// avoids flicker on every tick.
+fn draw(term: &Term, lines: &[String], width: usize) -> Result<()> {
+    term.move_cursor_to(0, 0)?;
+    for line in lines {
+        term.clear_line()?;
+        term.write_line(&console::truncate_str(line, width, icon("…", "...")))?;
+    }
+    term.clear_to_end_of_screen()?;
+    Ok(())
+}
+
+fn frame(
+    sample: &Sample,
+    previous: Option<&Sample>,
+    unit: &TemperatureUnit,
+    remaining: Duration,
+    error: Option<&str>,
+) -> Vec<String> {
+    let g = Charset::current();
+    let weather = &sample.weather;
+    let prev = previous.map(|p| p.weather.as_ref());
+    let condition = weather.weather.first();
+    let location = if weather.sys.country.is_empty() {
+        weather.name.clone()
+    } else {
+        format!("{}, {}", weather.name, weather.sys.country)
+    };
+
+    let temp = |w: &WeatherResponse| unit.convert(w.main.temp);
+    let feels = |w: &WeatherResponse| unit.convert(w.main.feels_like);
+    let degree = match unit {
+        TemperatureUnit::Kelvin => "K",
+        _ => icon("°", ""),
+    };
+
+    let mut lines = vec![
+        format!("{} {}", icon("👀 WATCHING", "WATCHING").bold().cyan(), location.bold()),
+        icon("─", "-").repeat(48).dimmed().to_string(),
+        format!(
+            "{} {}",
+            match g {
+                Charset::Unicode => condition.map_or("🌈", |c| condition::emoji(c.id, &c.icon)),
+                Charset::Ascii => condition.map_or("[?]", |c| condition::ascii(c.id, &c.icon)),
+            },
+            weather.description()
+        ),
+        format!(
+            "{}Temperature  {}  {}",
+            icon("🌡️  ", ""),
+            format!("{:>10}", g.text(&weather.format_temperature(unit))).bold().yellow(),
+            delta(temp(weather), prev.map(temp), 1, degree)
+        ),
+        format!(
+            "{}Feels like   {:>10}  {}",
+            icon("🤔 ", ""),
+            g.text(&format!("{:.1}{}", feels(weather), unit)),
+            delta(feels(weather), prev.map(feels), 1, degree)
+        ),
+        format!(
+            "{}Humidity     {:>10}  {}",
+            icon("💧 ", ""),
+            format_humidity(weather.main.humidity),
+            delta(weather.main.humidity as f64, prev.map(|w| w.main.humidity as f64), 0, "%")
+        ),
+        format!(
+            "{}Pressure     {:>10}  {}",
+            icon("🎈 ", ""),
+            format_pressure(weather.main.pressure),
+            delta(weather.main.pressure as f64, prev.map(|w| w.main.pressure as f64), 0, " hPa")
+        ),
+        format!(
+            "{}Wind         {:>10}  {}",
+            icon("💨 ", ""),
+            format!("{:.1} m/s {}", weather.wind.speed, format_wind_direction(weather.wind.deg)),
+            delta(weather.wind.speed, prev.map(|w| w.wind.speed), 1, " m/s")
+        ),
+        icon("─", "-").repeat(48).dimmed().to_string(),
+    ];
+
+    let since = previous.map_or(String::new(), |p| {
+        format!(" {} deltas since {}", icon("·", "-"), p.fetched_at.format("%H:%M:%S"))
+    });
+    lines.push(
+        format!(
+            "Updated {}{} {} next refresh in {}",
+            sample.fetched_at.format("%H:%M:%S"),
+            since,
+            icon("·", "-"),
+            humantime::format_duration(Duration::from_secs(remaining.as_secs()))
+        )
+        .dimmed()
+        .to_string(),
+    );
+    if let Some(error) = error {
+        lines.push(format!("{} Refresh failed: {}", icon("⚠️", "!").yellow(), error));
+    }
+    lines.push("Press Ctrl-C to stop".dimmed().to_string());
+    lines
+}
+
+/// "↑0.4°" / "↓2 hPa" relative to the previous sample, blank for the first one.
+fn delta(current: f64, previous: Option<f64>, precision: usize, suffix: &str) -> String {
+    let Some(previous) = previous else {
+        return String::new();
+    };
+    let change = current - previous;
+    let threshold = 0.5 / 10f64.powi(precision as i32);
+
+    if change.abs() < threshold {
+        format!("{}", icon("→ no change", "= no change").dimmed())
+    } else if change > 0.0 {
+        format!("{}{:.*}{}", icon("↑", "+"), precision, change, suffix).red().to_string()
+    } else {
+        format!("{}{:.*}{}", icon("↓", "-"), precision, change.abs(), suffix).blue().to_string()
+    }
+}
+
@@ -0,0 +1,98 @@
+use crate::api::clients::Place;
+use crate::models::geocoding::GeoLocation;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A geocoded place saved under `[locations.ALIAS]` in the config file.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct SavedLocation {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    pub lat: f64,
+    pub lon: f64,
+}
+
+impl SavedLocation {
+    /// "Name, State, CC" with whichever parts are known.
+    pub fn label(&self) -> String {
+        [Some(&self.name), self.state.as_ref(), self.country.as_ref()]
+            .into_iter()
+            .flatten()
+            .filter(|part| !part.is_empty())
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    pub fn place(&self) -> Place {
+        Place::Coords { lat: self.lat, lon: self.lon }
+    }
+}
+
+impl From<GeoLocation> for SavedLocation {
+    fn from(geo: GeoLocation) -> Self {
+        Self {
+            name: geo.name,
+            country: Some(geo.country).filter(|c| !c.is_empty()),
+            state: geo.state,
+            lat: geo.lat,
+            lon: geo.lon,
+        }
+    }
+}
+
+/// Alias table used to turn whatever the user typed into a `Place`.
+#[derive(Debug, Clone, Default)]
+pub struct Locations {
+    aliases: BTreeMap<String, SavedLocation>,
+}
+
+impl Locations {
+    pub fn new(aliases: BTreeMap<String, SavedLocation>) -> Self {
+        Self {
+            aliases: aliases.into_iter().map(|(k, v)| (k.to_lowercase(), v)).collect(),
+        }
+    }
+
+    pub fn get(&self, alias: &str) -> Option<&SavedLocation> {
+        self.aliases.get(&alias.trim().to_lowercase())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &SavedLocation)> {
+        self.aliases.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.aliases.is_empty()
+    }
+
+    /// Saved coordinates for an alias, otherwise the text as a place name.
+    pub fn resolve(&self, query: &str) -> Place {
+        match self.get(query) {
+            Some(location) => location.place(),
+            None => Place::Name(query.trim().to_string()),
+        }
+    }
+}
+
+/// Aliases are used as TOML keys and typed on the command line, so keep them
+/// to lowercase letters, digits, `-` and `_`.
+pub fn validate_alias(alias: &str) -> Result<String> {
+    let alias = alias.trim().to_lowercase();
+    let valid = !alias.is_empty()
+        && alias
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(anyhow!(
+            "Invalid alias '{}'. Use letters, digits, '-' and '_' only.",
+            alias
+        ));
+    }
+    Ok(alias)
+}
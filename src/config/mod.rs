@@ -0,0 +1,4 @@
+pub mod locations;
+pub mod mqtt;
+pub mod notify;
+pub mod settings;
@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+/// The `[mqtt]` section: broker connection and topic layout for `weather mqtt`.
+/// Command line flags override these values.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct MqttConfig {
+    pub host: Option<String>,
+    pub port: Option<u16>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub client_id: Option<String>,
+    /// Prefix for state and availability topics (default: weather)
+    pub topic_prefix: Option<String>,
+    /// Home Assistant discovery prefix (default: homeassistant)
+    pub discovery_prefix: Option<String>,
+}
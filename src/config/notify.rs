@@ -0,0 +1,70 @@
+use crate::utils::condition::ConditionGroup;
+use crate::utils::rules::{Comparator, Metric, Rule};
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// The `[notify]` section: where to deliver alerts and which rules fire them.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct NotifyConfig {
+    /// Minimum time between two "triggered" messages for the same rule
+    #[serde(default, with = "humantime_serde", skip_serializing_if = "Option::is_none")]
+    pub cooldown: Option<Duration>,
+    #[serde(default)]
+    pub channels: Vec<ChannelConfig>,
+    #[serde(default)]
+    pub rules: Vec<RuleConfig>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChannelKind {
+    /// Plain JSON event posted to any URL
+    #[default]
+    Webhook,
+    Slack,
+    Discord,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ChannelConfig {
+    pub name: String,
+    #[serde(default)]
+    pub kind: ChannelKind,
+    pub url: String,
+}
+
+/// One `[[notify.rules]]` entry: either `metric`/`op`/`value` or `condition`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RuleConfig {
+    pub name: Option<String>,
+    /// City or alias; rules without one apply to the place being watched or served
+    pub location: Option<String>,
+    pub metric: Option<Metric>,
+    pub op: Option<Comparator>,
+    pub value: Option<f64>,
+    pub condition: Option<ConditionGroup>,
+    #[serde(default, with = "humantime_serde", skip_serializing_if = "Option::is_none")]
+    pub cooldown: Option<Duration>,
+    /// Channel names to deliver to; all channels when empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub channels: Vec<String>,
+}
+
+impl RuleConfig {
+    pub fn rule(&self) -> Result<Rule> {
+        match (self.metric, self.op, self.value, self.condition) {
+            (Some(metric), Some(comparator), Some(value), None) => {
+                Ok(Rule::Threshold { metric, comparator, value })
+            }
+            (None, None, None, Some(group)) => Ok(Rule::Condition(group)),
+            _ => Err(anyhow!(
+                "Notification rule{} needs either metric, op and value, or condition",
+                self.name.as_ref().map_or(String::new(), |n| format!(" '{}'", n))
+            )),
+        }
+    }
+}
@@ -0,0 +1,430 @@
+use crate::api::clients::WeatherApiClient;
+use crate::config::locations::{Locations, SavedLocation};
+use crate::config::mqtt::MqttConfig;
+use crate::config::notify::NotifyConfig;
+use crate::history::History;
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml_edit::{DocumentMut, Item, Table, TableLike, Value};
+
+/// Values that can come from the config file, a profile or the environment.
+/// Every field is optional so layers can be merged on top of each other.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Settings {
+    pub location: Option<String>,
+    pub units: Option<String>,
+    pub template: Option<String>,
+    pub provider: Option<String>,
+    pub theme: Option<String>,
+    pub port: Option<u16>,
+    /// Seconds to reuse a fetched observation in long-running modes
+    pub cache_ttl: Option<u64>,
+    /// Store every fetched observation in the history database
+    pub record_history: Option<bool>,
+    /// History database file (default: weather/history.db in the data dir)
+    pub history_db: Option<String>,
+    #[serde(default)]
+    pub api_keys: BTreeMap<String, String>,
+}
+
+impl Settings {
+    /// Overlay `other` on top of `self`; values set in `other` win.
+    pub fn merge(mut self, other: Settings) -> Settings {
+        self.location = other.location.or(self.location);
+        self.units = other.units.or(self.units);
+        self.template = other.template.or(self.template);
+        self.provider = other.provider.or(self.provider);
+        self.theme = other.theme.or(self.theme);
+        self.port = other.port.or(self.port);
+        self.cache_ttl = other.cache_ttl.or(self.cache_ttl);
+        self.record_history = other.record_history.or(self.record_history);
+        self.history_db = other.history_db.or(self.history_db);
+        self.api_keys.extend(other.api_keys);
+        self
+    }
+
+    /// Settings taken from `WEATHER_*` environment variables.
+    pub fn from_env() -> Result<Settings> {
+        let var = |name: &str| env::var(name).ok().filter(|v| !v.trim().is_empty());
+        let number = |name: &str| -> Result<Option<u64>> {
+            var(name)
+                .map(|v| v.parse().with_context(|| format!("{} must be a number, got '{}'", name, v)))
+                .transpose()
+        };
+
+        let mut api_keys = BTreeMap::new();
+        if let Some(key) = var("WEATHER_API_KEY").or_else(|| var("OPENWEATHER_API_KEY")) {
+            api_keys.insert("openweathermap".to_string(), key);
+        }
+
+        Ok(Settings {
+            location: var("WEATHER_LOCATION"),
+            units: var("WEATHER_UNITS"),
+            template: var("WEATHER_TEMPLATE"),
+            provider: var("WEATHER_PROVIDER"),
+            theme: var("WEATHER_THEME"),
+            port: var("WEATHER_PORT")
+                .map(|v| v.parse().with_context(|| format!("WEATHER_PORT must be a port from 0 to 65535, got '{}'", v)))
+                .transpose()?,
+            cache_ttl: number("WEATHER_CACHE_TTL")?,
+            record_history: var("WEATHER_RECORD_HISTORY")
+                .map(|v| match v.to_lowercase().as_str() {
+                    "1" | "true" | "yes" | "on" => Ok(true),
+                    "0" | "false" | "no" | "off" => Ok(false),
+                    _ => Err(anyhow!("WEATHER_RECORD_HISTORY must be true or false, got '{}'", v)),
+                })
+                .transpose()?,
+            history_db: var("WEATHER_HISTORY_DB"),
+            api_keys,
+        })
+    }
+
+    pub fn provider(&self) -> &str {
+        self.provider.as_deref().unwrap_or("openweathermap")
+    }
+
+    /// API key for the selected provider, if any layer provided one.
+    pub fn api_key(&self) -> Option<&str> {
+        self.api_keys.get(self.provider()).map(String::as_str)
+    }
+
+    /// The history database to record into, or `None` when recording is off.
+    pub fn history(&self) -> Result<Option<History>> {
+        if self.record_history != Some(true) {
+            return Ok(None);
+        }
+        self.open_history().map(Some)
+    }
+
+    /// Open the history database regardless of `record_history`.
+    pub fn open_history(&self) -> Result<History> {
+        let path = match &self.history_db {
+            Some(path) => PathBuf::from(path),
+            None => History::default_path()?,
+        };
+        History::open(&path)
+    }
+
+    /// Client for the selected provider using the configured API key.
+    pub fn api_client(&self) -> Result<WeatherApiClient> {
+        match self.api_key() {
+            Some(key) => WeatherApiClient::with_api_key(key),
+            None => Err(anyhow!(
+                "No API key found. Please set WEATHER_API_KEY or OPENWEATHER_API_KEY environment variable,\nor run `weather config set api_keys.openweathermap <KEY>`.\nGet a free API key at: https://openweathermap.org/api"
+            )),
+        }
+    }
+}
+
+/// Typed view of the config file: top-level settings plus `[profiles.NAME]`,
+/// saved `[locations.ALIAS]` and the `[notify]` and `[mqtt]` sections.
+#[derive(Debug, Default)]
+pub struct ConfigFile {
+    pub settings: Settings,
+    pub profiles: BTreeMap<String, Settings>,
+    pub locations: BTreeMap<String, SavedLocation>,
+    pub notify: NotifyConfig,
+    pub mqtt: MqttConfig,
+}
+
+impl ConfigFile {
+    fn from_table(mut table: toml::Table) -> Result<Self> {
+        let profiles = match table.remove("profiles") {
+            Some(value) => value.try_into()?,
+            None => BTreeMap::new(),
+        };
+        let locations = match table.remove("locations") {
+            Some(value) => value.try_into()?,
+            None => BTreeMap::new(),
+        };
+        let notify: NotifyConfig = match table.remove("notify") {
+            Some(value) => value.try_into()?,
+            None => NotifyConfig::default(),
+        };
+        for rule in &notify.rules {
+            rule.rule()?;
+        }
+        let mqtt = match table.remove("mqtt") {
+            Some(value) => value.try_into()?,
+            None => MqttConfig::default(),
+        };
+        let settings = toml::Value::Table(table).try_into()?;
+        Ok(Self { settings, profiles, locations, notify, mqtt })
+    }
+}
+
+/// The TOML config file. Edits go through a `toml_edit` document so `config
+/// set` changes a single key and keeps the user's comments, ordering and
+/// formatting; `table` is the parsed view used for reading.
+pub struct Config {
+    path: PathBuf,
+    doc: DocumentMut,
+    table: toml::Table,
+}
+
+impl Config {
+    /// `$WEATHER_CONFIG`, or `config.toml` under the XDG config dir.
+    pub fn default_path() -> Result<PathBuf> {
+        if let Ok(path) = env::var("WEATHER_CONFIG") {
+            return Ok(PathBuf::from(path));
+        }
+        dirs::config_dir()
+            .map(|dir| dir.join("weather").join("config.toml"))
+            .ok_or_else(|| anyhow!("Could not determine the config directory. Set WEATHER_CONFIG."))
+    }
+
+    /// Load the config file; a missing file is treated as empty.
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        let path = match path {
+            Some(p) => p.to_path_buf(),
+            None => Self::default_path()?,
+        };
+
+        let doc = if path.exists() {
+            let content = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read config file {}", path.display()))?;
+            content
+                .parse::<DocumentMut>()
+                .with_context(|| format!("Invalid config file {}", path.display()))?
+        } else {
+            DocumentMut::new()
+        };
+
+        let table = parse_table(&doc).with_context(|| format!("Invalid config file {}", path.display()))?;
+        let config = Self { path, doc, table };
+        config.file()?;
+        Ok(config)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn file(&self) -> Result<ConfigFile> {
+        ConfigFile::from_table(self.table.clone())
+            .map_err(|e| anyhow!("Invalid config file {}: {:#}", self.path.display(), e))
+    }
+
+    /// Effective settings with precedence env > profile > file. Command line
+    /// flags are applied on top by the caller.
+    pub fn settings(&self, profile: Option<&str>) -> Result<Settings> {
+        let mut file = self.file()?;
+        let mut settings = file.settings;
+
+        if let Some(name) = profile {
+            let profile = file.profiles.remove(name).ok_or_else(|| {
+                let defined: Vec<_> = file.profiles.keys().cloned().collect();
+                anyhow!(
+                    "Unknown profile '{}'. Defined profiles: {}",
+                    name,
+                    if defined.is_empty() { "none".to_string() } else { defined.join(", ") }
+                )
+            })?;
+            settings = settings.merge(profile);
+        }
+
+        Ok(settings.merge(Settings::from_env()?))
+    }
+
+    pub fn locations(&self) -> Result<Locations> {
+        Ok(Locations::new(self.file()?.locations))
+    }
+
+    pub fn notify(&self) -> Result<NotifyConfig> {
+        Ok(self.file()?.notify)
+    }
+
+    pub fn mqtt(&self) -> Result<MqttConfig> {
+        Ok(self.file()?.mqtt)
+    }
+
+    /// Save `location` under `alias`, replacing any existing entry.
+    pub fn insert_location(&mut self, alias: &str, location: &SavedLocation) -> Result<()> {
+        let entry = toml::to_string(location)?.parse::<DocumentMut>()?.as_table().clone();
+        let locations = self.doc.entry("locations").or_insert_with(implicit_table);
+        let locations = locations
+            .as_table_like_mut()
+            .ok_or_else(|| anyhow!("'locations' is not a table in {}", self.path.display()))?;
+        locations.insert(alias, Item::Table(entry));
+        self.sync()
+    }
+
+    /// Remove the saved location `alias` however its key is cased in the
+    /// file, returning whether there was one.
+    pub fn remove_location(&mut self, alias: &str) -> bool {
+        let alias = alias.trim().to_lowercase();
+        let Some(locations) = self.doc.get_mut("locations").and_then(Item::as_table_like_mut) else {
+            return false;
+        };
+        let keys: Vec<String> = locations
+            .iter()
+            .map(|(key, _)| key.to_string())
+            .filter(|key| key.to_lowercase() == alias)
+            .collect();
+        for key in &keys {
+            locations.remove(key);
+        }
+        // Removing keys keeps the parsed view valid
+        let _ = self.sync();
+        !keys.is_empty()
+    }
+
+    /// Value at a dotted key such as `units` or `profiles.work.location`.
+    pub fn get(&self, key: &str) -> Option<&toml::Value> {
+        let mut parts = key.split('.');
+        let mut value = self.table.get(parts.next()?)?;
+        for part in parts {
+            value = value.as_table()?.get(part)?;
+        }
+        Some(value)
+    }
+
+    /// Set a dotted key. The value is parsed as TOML when possible (numbers,
+    /// booleans) and stored as a string otherwise.
+    pub fn set(&mut self, key: &str, raw: &str) -> Result<()> {
+        let mut value = raw.parse::<Value>().unwrap_or_else(|_| Value::from(raw));
+        value.decor_mut().clear();
+
+        let mut updated = self.doc.clone();
+        let parts: Vec<&str> = key.split('.').collect();
+        let (last, parents) = parts.split_last().ok_or_else(|| anyhow!("Empty config key"))?;
+
+        let mut table: &mut dyn TableLike = updated.as_table_mut();
+        for part in parents {
+            table = table
+                .entry(part)
+                .or_insert_with(implicit_table)
+                .as_table_like_mut()
+                .ok_or_else(|| anyhow!("'{}' is not a table in {}", part, key))?;
+        }
+        table.insert(last, Item::Value(value));
+
+        // Reject keys and types the settings do not understand
+        let parsed = parse_table(&updated)?;
+        ConfigFile::from_table(parsed.clone()).map_err(|e| anyhow!("Cannot set '{}': {}", key, e))?;
+
+        self.doc = updated;
+        self.table = parsed;
+        Ok(())
+    }
+
+    /// Remove a dotted key, returning whether it existed.
+    pub fn unset(&mut self, key: &str) -> bool {
+        let parts: Vec<&str> = key.split('.').collect();
+        let Some((last, parents)) = parts.split_last() else {
+            return false;
+        };
+
+        let mut table: &mut dyn TableLike = self.doc.as_table_mut();
+        for part in parents {
+            match table.get_mut(part).and_then(Item::as_table_like_mut) {
+                Some(t) => table = t,
+                None => return false,
+            }
+        }
+        let removed = table.remove(last).is_some();
+        let _ = self.sync();
+        removed
+    }
+
+    /// Every leaf value as `(dotted.key, value)`, sorted by key.
+    pub fn entries(&self) -> Vec<(String, toml::Value)> {
+        fn walk(prefix: &str, table: &toml::Table, out: &mut Vec<(String, toml::Value)>) {
+            for (key, value) in table {
+                let full = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+                match value {
+                    toml::Value::Table(t) => walk(&full, t, out),
+                    v => out.push((full, v.clone())),
+                }
+            }
+        }
+
+        let mut out = Vec::new();
+        walk("", &self.table, &mut out);
+        out
+    }
+
+    /// Write the file back, readable by the owner only since it holds API
+    /// keys and passwords.
+    pub fn save(&self) -> Result<()> {
+        if let Some(dir) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+        write_private(&self.path, &self.doc.to_string())
+            .with_context(|| format!("Failed to write config file {}", self.path.display()))
+    }
+
+    /// Refresh the parsed view after an edit.
+    fn sync(&mut self) -> Result<()> {
+        self.table = parse_table(&self.doc)?;
+        Ok(())
+    }
+}
+
+fn parse_table(doc: &DocumentMut) -> Result<toml::Table> {
+    Ok(doc.to_string().parse::<toml::Table>()?)
+}
+
+/// A table that only gets a `[header]` of its own once it holds values.
+fn implicit_table() -> Item {
+    let mut table = Table::new();
+    table.set_implicit(true);
+    Item::Table(table)
+}
+
+#[cfg(unix)]
+fn write_private(path: &Path, content: &str) -> std::io::Result<()> {
+    use std::io::Write;
+    use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+
+    let mut file = fs::OpenOptions::new().write(true).create(true).truncate(true).mode(0o600).open(path)?;
+    // The mode only applies to new files
+    file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    file.write_all(content.as_bytes())
+}
+
+#[cfg(not(unix))]
+fn write_private(path: &Path, content: &str) -> std::io::Result<()> {
+    fs::write(path, content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edits_keep_comments_and_formatting() {
+        let path = env::temp_dir().join(format!("weather-config-{}.toml", std::process::id()));
+        let original = "# My weather setup\nlocation = \"London\"   # home\nunits = \"celsius\"\n\n# Keys\n[api_keys]\nopenweathermap = \"secret\"\n";
+        fs::write(&path, original).unwrap();
+
+        let mut config = Config::load(Some(&path)).unwrap();
+        config.set("units", "fahrenheit").unwrap();
+        config.set("profiles.work.location", "Berlin").unwrap();
+        config.insert_location("Home", &SavedLocation { name: "Berlin".to_string(), country: None, state: None, lat: 52.52, lon: 13.405 }).unwrap();
+        assert!(config.remove_location("HOME"));
+        assert!(config.unset("profiles.work.location"));
+        config.set("cache_ttl", "600").unwrap();
+        assert!(config.set("cache_ttl", "\"soon\"").is_err());
+        config.save().unwrap();
+
+        let saved = fs::read_to_string(&path).unwrap();
+        assert!(saved.starts_with("# My weather setup\nlocation = \"London\"   # home\nunits = \"fahrenheit\"\n"), "{}", saved);
+        assert!(saved.contains("# Keys\n[api_keys]\nopenweathermap = \"secret\"\n"), "{}", saved);
+        assert!(saved.contains("cache_ttl = 600"), "{}", saved);
+        assert!(!saved.contains("Berlin"), "{}", saved);
+        assert_eq!(Config::load(Some(&path)).unwrap().get("cache_ttl"), Some(&toml::Value::Integer(600)));
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+        }
+        fs::remove_file(&path).unwrap();
+    }
+}
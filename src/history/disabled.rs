@@ -0,0 +1,42 @@
+//! Stand-in for the SQLite store in builds without the `history` feature.
+//! Opening it fails with a hint, so nothing else needs feature checks.
+
+use crate::history::{LocationSummary, Record};
+use crate::models::weather::WeatherResponse;
+use anyhow::{anyhow, Result};
+use std::convert::Infallible;
+use std::path::{Path, PathBuf};
+
+pub struct History {
+    never: Infallible,
+}
+
+impl History {
+    pub fn default_path() -> Result<PathBuf> {
+        Err(unavailable())
+    }
+
+    pub fn open(_path: &Path) -> Result<Self> {
+        Err(unavailable())
+    }
+
+    pub fn path(&self) -> &Path {
+        match self.never {}
+    }
+
+    pub fn record(&self, _location: &str, _provider: &str, _weather: &WeatherResponse) -> Result<bool> {
+        match self.never {}
+    }
+
+    pub fn query(&self, _location: &str, _since: u64) -> Result<Vec<Record>> {
+        match self.never {}
+    }
+
+    pub fn locations(&self) -> Result<Vec<LocationSummary>> {
+        match self.never {}
+    }
+}
+
+fn unavailable() -> anyhow::Error {
+    anyhow!("This build has no history support. Rebuild with `--features history` to record observations.")
+}
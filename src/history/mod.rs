@@ -0,0 +1,88 @@
+use serde::Serialize;
+
+#[cfg(feature = "history")]
+mod sqlite;
+#[cfg(feature = "history")]
+pub use sqlite::History;
+
+#[cfg(not(feature = "history"))]
+mod disabled;
+#[cfg(not(feature = "history"))]
+pub use disabled::History;
+
+/// One stored observation. Temperatures are in Kelvin like the API models.
+#[derive(Debug, Clone, Serialize)]
+pub struct Record {
+    /// The place as it was requested (city name or alias), lowercased
+    pub location: String,
+    pub name: String,
+    pub country: String,
+    pub lat: f64,
+    pub lon: f64,
+    /// Observation time from the API, UTC seconds
+    pub observed_at: u64,
+    pub fetched_at: u64,
+    pub provider: String,
+    pub temp: f64,
+    pub feels_like: f64,
+    pub temp_min: f64,
+    pub temp_max: f64,
+    pub pressure: u32,
+    pub humidity: u32,
+    pub sea_level: Option<u32>,
+    pub grnd_level: Option<u32>,
+    pub wind_speed: f64,
+    pub wind_deg: u32,
+    pub wind_gust: Option<f64>,
+    pub clouds: u32,
+    /// Main condition; every reported one is in `condition_ids`
+    pub condition_id: Option<u32>,
+    /// All reported conditions, e.g. "moderate rain and mist"
+    pub description: Option<String>,
+    pub condition_ids: Vec<u32>,
+}
+
+/// A recorded location with how much history exists for it.
+#[derive(Debug, Clone)]
+pub struct LocationSummary {
+    pub location: String,
+    pub name: String,
+    pub count: u64,
+    pub first: u64,
+    pub last: u64,
+}
+
+/// Minimum, maximum and mean of one metric over a set of records.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Stats {
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    pub count: usize,
+}
+
+impl Stats {
+    pub fn of(values: impl IntoIterator<Item = f64>) -> Option<Self> {
+        let mut stats: Option<Stats> = None;
+        let mut sum = 0.0;
+        for value in values {
+            sum += value;
+            stats = Some(match stats {
+                None => Stats { min: value, max: value, mean: 0.0, count: 1 },
+                Some(s) => Stats {
+                    min: s.min.min(value),
+                    max: s.max.max(value),
+                    mean: 0.0,
+                    count: s.count + 1,
+                },
+            });
+        }
+        stats.map(|s| Stats { mean: sum / s.count as f64, ..s })
+    }
+
+    /// The same figures rounded to two decimals for export.
+    pub fn rounded(self) -> Self {
+        let round = |v: f64| (v * 100.0).round() / 100.0;
+        Stats { min: round(self.min), max: round(self.max), mean: round(self.mean), count: self.count }
+    }
+}
@@ -0,0 +1,274 @@
+use crate::history::{LocationSummary, Record};
+use crate::models::weather::WeatherResponse;
+use anyhow::{anyhow, Context, Result};
+use rusqlite::{params, Connection, Row};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS observations (
+    id           INTEGER PRIMARY KEY,
+    location     TEXT    NOT NULL,
+    name         TEXT    NOT NULL,
+    country      TEXT    NOT NULL,
+    lat          REAL    NOT NULL,
+    lon          REAL    NOT NULL,
+    observed_at  INTEGER NOT NULL,
+    fetched_at   INTEGER NOT NULL,
+    provider     TEXT    NOT NULL,
+    temp         REAL    NOT NULL,
+    feels_like   REAL    NOT NULL,
+    temp_min     REAL    NOT NULL,
+    temp_max     REAL    NOT NULL,
+    pressure     INTEGER NOT NULL,
+    humidity     INTEGER NOT NULL,
+    sea_level    INTEGER,
+    grnd_level   INTEGER,
+    wind_speed   REAL    NOT NULL,
+    wind_deg     INTEGER NOT NULL,
+    wind_gust    REAL,
+    clouds       INTEGER NOT NULL,
+    condition_id INTEGER,
+    description  TEXT,
+    condition_ids TEXT,
+    UNIQUE (location, provider, observed_at)
+);
+CREATE INDEX IF NOT EXISTS observations_location_time ON observations (location, observed_at);
+";
+
+const COLUMNS: &str = "location, name, country, lat, lon, observed_at, fetched_at, provider, \
+    temp, feels_like, temp_min, temp_max, pressure, humidity, sea_level, grnd_level, \
+    wind_speed, wind_deg, wind_gust, clouds, condition_id, description, condition_ids";
+
+impl Record {
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(Self {
+            location: row.get(0)?,
+            name: row.get(1)?,
+            country: row.get(2)?,
+            lat: row.get(3)?,
+            lon: row.get(4)?,
+            observed_at: row.get(5)?,
+            fetched_at: row.get(6)?,
+            provider: row.get(7)?,
+            temp: row.get(8)?,
+            feels_like: row.get(9)?,
+            temp_min: row.get(10)?,
+            temp_max: row.get(11)?,
+            pressure: row.get(12)?,
+            humidity: row.get(13)?,
+            sea_level: row.get(14)?,
+            grnd_level: row.get(15)?,
+            wind_speed: row.get(16)?,
+            wind_deg: row.get(17)?,
+            wind_gust: row.get(18)?,
+            clouds: row.get(19)?,
+            condition_id: row.get(20)?,
+            description: row.get(21)?,
+            // Rows written before every id was kept only have the main one
+            condition_ids: match row.get::<_, Option<String>>(22)? {
+                Some(ids) => ids.split(',').filter_map(|id| id.parse().ok()).collect(),
+                None => row.get::<_, Option<u32>>(20)?.into_iter().collect(),
+            },
+        })
+    }
+}
+
+/// SQLite store of fetched observations. The connection sits behind a mutex
+/// so the store can be shared with the cache and background threads.
+pub struct History {
+    conn: Mutex<Connection>,
+    path: PathBuf,
+}
+
+impl History {
+    /// `weather/history.db` under the platform data directory.
+    pub fn default_path() -> Result<PathBuf> {
+        dirs::data_dir()
+            .map(|dir| dir.join("weather").join("history.db"))
+            .ok_or_else(|| anyhow!("Could not determine the data directory. Set history_db in the config file."))
+    }
+
+    pub fn open(path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent).with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let conn = Connection::open(path)
+            .with_context(|| format!("Failed to open history database {}", path.display()))?;
+        // Watch, serve and one-off runs may write at the same time
+        conn.busy_timeout(std::time::Duration::from_secs(5))?;
+        conn.execute_batch(SCHEMA)?;
+        if conn.prepare("SELECT condition_ids FROM observations LIMIT 0").is_err() {
+            conn.execute_batch("ALTER TABLE observations ADD COLUMN condition_ids TEXT")?;
+        }
+        Ok(Self { conn: Mutex::new(conn), path: path.to_path_buf() })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Store an observation of `location`. The same API observation fetched
+    /// twice is only kept once. Returns whether a new row was written.
+    pub fn record(&self, location: &str, provider: &str, weather: &WeatherResponse) -> Result<bool> {
+        let fetched_at = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        let observed_at = if weather.dt > 0 { weather.dt } else { fetched_at };
+        let condition = weather.weather.first();
+        let condition_ids: Vec<String> = weather.weather.iter().map(|c| c.id.to_string()).collect();
+
+        let conn = self.conn.lock().unwrap();
+        let inserted = conn.execute(
+            &format!(
+                "INSERT OR IGNORE INTO observations ({}) VALUES \
+                 (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23)",
+                COLUMNS
+            ),
+            params![
+                location_key(location),
+                weather.name,
+                weather.sys.country,
+                weather.coord.lat,
+                weather.coord.lon,
+                observed_at,
+                fetched_at,
+                provider,
+                weather.main.temp,
+                weather.main.feels_like,
+                weather.main.temp_min,
+                weather.main.temp_max,
+                weather.main.pressure,
+                weather.main.humidity,
+                weather.main.sea_level,
+                weather.main.grnd_level,
+                weather.wind.speed,
+                weather.wind.deg,
+                weather.wind.gust,
+                weather.clouds.all,
+                condition.map(|c| c.id),
+                Some(weather.description()).filter(|d| !d.is_empty()),
+                condition_ids.join(","),
+            ],
+        )?;
+        Ok(inserted > 0)
+    }
+
+    /// Observations of `location` since `since` (UTC seconds), oldest first.
+    /// Looks up the name or alias as it was requested, then falls back to
+    /// the city name the API returned.
+    pub fn query(&self, location: &str, since: u64) -> Result<Vec<Record>> {
+        let conn = self.conn.lock().unwrap();
+        let select = |column: &str| -> Result<Vec<Record>> {
+            let mut statement = conn.prepare(&format!(
+                "SELECT {} FROM observations WHERE {} = ?1 AND observed_at >= ?2 ORDER BY observed_at",
+                COLUMNS, column
+            ))?;
+            let records = statement
+                .query_map(params![location_key(location), since], Record::from_row)?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            Ok(records)
+        };
+
+        let records = select("location")?;
+        if records.is_empty() {
+            return select("lower(name)");
+        }
+        Ok(records)
+    }
+
+    pub fn locations(&self) -> Result<Vec<LocationSummary>> {
+        let conn = self.conn.lock().unwrap();
+        let mut statement = conn.prepare(
+            "SELECT location, max(name), count(*), min(observed_at), max(observed_at) \
+             FROM observations GROUP BY location ORDER BY location",
+        )?;
+        let locations = statement
+            .query_map([], |row| {
+                Ok(LocationSummary {
+                    location: row.get(0)?,
+                    name: row.get(1)?,
+                    count: row.get(2)?,
+                    first: row.get(3)?,
+                    last: row.get(4)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(locations)
+    }
+}
+
+fn location_key(location: &str) -> String {
+    location.trim().to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::Stats;
+    use crate::models::weather::*;
+
+    fn weather(dt: u64, temp: f64) -> WeatherResponse {
+        WeatherResponse {
+            coord: Coordinates { lon: 13.4, lat: 52.5 },
+            weather: vec![WeatherCondition {
+                id: 500,
+                main: "Rain".to_string(),
+                description: "light rain".to_string(),
+                icon: "10d".to_string(),
+            }],
+            main: MainData {
+                temp,
+                feels_like: temp,
+                temp_min: temp,
+                temp_max: temp,
+                pressure: 1010,
+                humidity: 80,
+                sea_level: Some(1010),
+                grnd_level: None,
+            },
+            wind: WindData { speed: 4.0, deg: 270, gust: Some(9.0) },
+            clouds: CloudData { all: 90 },
+            sys: SystemData { country: "DE".to_string(), sunrise: 0, sunset: 0 },
+            name: "Berlin".to_string(),
+            visibility: Some(8000),
+            timezone: 3600,
+            dt,
+        }
+    }
+
+    #[test]
+    fn test_record_dedup_and_query() {
+        let history = History::open(Path::new(":memory:")).unwrap();
+        assert!(history.record("Home", "openweathermap", &weather(1000, 280.0)).unwrap());
+        assert!(!history.record("home", "openweathermap", &weather(1000, 280.0)).unwrap());
+        assert!(history.record("home", "openweathermap", &weather(2000, 290.0)).unwrap());
+
+        let records = history.query("berlin", 1500).unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].wind_gust, Some(9.0));
+        assert_eq!(records[0].condition_ids, vec![500]);
+        assert_eq!(history.query("HOME", 0).unwrap().len(), 2);
+
+        let stats = Stats::of(history.query("home", 0).unwrap().iter().map(|r| r.temp)).unwrap();
+        assert_eq!(stats, Stats { min: 280.0, max: 290.0, mean: 285.0, count: 2 });
+    }
+
+    #[test]
+    fn test_every_condition_is_stored() {
+        let history = History::open(Path::new(":memory:")).unwrap();
+        let mut observation = weather(1000, 280.0);
+        observation.weather.push(WeatherCondition {
+            id: 701,
+            main: "Mist".to_string(),
+            description: "mist".to_string(),
+            icon: "50d".to_string(),
+        });
+        history.record("home", "openweathermap", &observation).unwrap();
+
+        let record = &history.query("home", 0).unwrap()[0];
+        assert_eq!(record.condition_id, Some(500));
+        assert_eq!(record.condition_ids, vec![500, 701]);
+        assert_eq!(record.description.as_deref(), Some("light rain and mist"));
+    }
+}
@@ -0,0 +1,81 @@
+//! Weather data from OpenWeatherMap, plus the renderers behind the `weather`
+//! command line tool.
+//!
+//! The most used types are re-exported at the crate root:
+//!
+//! - [`WeatherApiClient`] fetches current weather, forecasts and geocoding
+//!   results. It is blocking and takes the API key in
+//!   [`WeatherApiClient::with_api_key`]; it never reads the environment.
+//!   [`config::settings::Config`] resolves the key from `WEATHER_API_KEY`,
+//!   `OPENWEATHER_API_KEY` or the config file, and
+//!   [`Settings::api_client`](config::settings::Settings::api_client)
+//!   builds a client from it.
+//! - [`WeatherResponse`] and [`ForecastResponse`] are the API models.
+//!   Temperatures stay in Kelvin; [`TemperatureUnit`] converts them.
+//! - [`utils::export`] renders HTML pages and SVG cards into any
+//!   [`std::io::Write`], [`utils::formatters`] has the text helpers the
+//!   terminal templates use.
+//! - [`utils::display_templates`] draws the terminal templates into any
+//!   writer, with or without color.
+//!
+//! ```no_run
+//! use weather_cl::{TemperatureUnit, WeatherApiClient};
+//!
+//! let client = WeatherApiClient::with_api_key("your-openweathermap-key")?;
+//! let weather = client.get_weather("Berlin")?;
+//! println!("{} {}", weather.name, weather.format_temperature(&TemperatureUnit::Celsius));
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+//!
+//! The models deserialize straight from the provider's JSON, so stored or
+//! recorded responses can be rendered without a client:
+//!
+//! ```
+//! use weather_cl::{TemperatureUnit, WeatherResponse};
+//!
+//! let weather: WeatherResponse = serde_json::from_str(r#"{
+//!     "coord": {"lon": -0.13, "lat": 51.51},
+//!     "weather": [{"id": 803, "main": "Clouds", "description": "broken clouds", "icon": "04d"}],
+//!     "main": {"temp": 283.65, "feels_like": 283.05, "temp_min": 283.15, "temp_max": 284.05,
+//!              "pressure": 1014, "humidity": 88},
+//!     "wind": {"speed": 3.6, "deg": 180},
+//!     "clouds": {"all": 75},
+//!     "sys": {"country": "GB", "sunrise": 1700000000, "sunset": 1700030000},
+//!     "name": "London",
+//!     "visibility": 10000,
+//!     "timezone": 0
+//! }"#)?;
+//!
+//! assert_eq!(weather.format_temperature(&TemperatureUnit::Celsius), "10.5°C");
+//! assert_eq!(weather.format_temperature(&TemperatureUnit::Fahrenheit), "50.9°F");
+//! # Ok::<(), serde_json::Error>(())
+//! ```
+//!
+//! The web server, TUI, SQLite history and MQTT publisher sit behind the
+//! `web`, `tui`, `history` and `mqtt` cargo features (`full` enables all of
+//! them). Without `history`, [`history::History::open`] returns an error.
+//!
+//! Everything reachable from the documented modules follows semver. The
+//! hidden `cli`, `commands` and `tui` modules are internals of the binary and
+//! may change in any release.
+
+pub mod api;
+pub mod config;
+pub mod history;
+pub mod models;
+#[cfg(feature = "mqtt")]
+pub mod mqtt;
+pub mod notify;
+pub mod utils;
+
+#[doc(hidden)]
+pub mod cli;
+#[doc(hidden)]
+pub mod commands;
+#[cfg(feature = "tui")]
+#[doc(hidden)]
+pub mod tui;
+
+pub use api::clients::{Place, WeatherApiClient};
+pub use models::forecast::ForecastResponse;
+pub use models::weather::{TemperatureUnit, WeatherResponse};
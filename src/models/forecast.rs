@@ -0,0 +1,125 @@
+use crate::models::weather::{CloudData, Coordinates, MainData, WeatherCondition, WindData};
+use chrono::{DateTime, NaiveDate, Timelike};
+use serde::{Deserialize, Serialize};
+
+/// 5 day / 3 hour forecast as returned by the `/data/2.5/forecast` endpoint.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ForecastResponse {
+    pub list: Vec<ForecastItem>,
+    pub city: ForecastCity,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ForecastItem {
+    pub dt: u64,
+    pub main: MainData,
+    pub weather: Vec<WeatherCondition>,
+    pub wind: WindData,
+    pub clouds: CloudData,
+    pub visibility: Option<u32>,
+    /// Probability of precipitation, 0.0 - 1.0
+    #[serde(default)]
+    pub pop: f64,
+    pub rain: Option<Precipitation>,
+    pub snow: Option<Precipitation>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Precipitation {
+    /// Volume for the last 3 hours, in mm
+    #[serde(rename = "3h", default)]
+    pub three_hours: f64,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ForecastCity {
+    pub name: String,
+    pub coord: Coordinates,
+    pub country: String,
+    pub timezone: i32,
+    pub sunrise: u64,
+    pub sunset: u64,
+}
+
+/// Per-day summary of the 3 hour forecast slots.
+#[derive(Debug, Clone)]
+pub struct DailySummary {
+    /// Date at the forecast location
+    pub date: NaiveDate,
+    /// Condition code, icon and description of the slot closest to midday
+    pub condition_id: u32,
+    pub icon: String,
+    pub description: String,
+    /// Kelvin
+    pub min: f64,
+    pub max: f64,
+    /// Total rain and snow, in mm
+    pub precipitation: f64,
+    /// Highest probability of precipitation, 0.0 - 1.0
+    pub pop: f64,
+}
+
+impl ForecastResponse {
+    /// The forecast slots grouped by local date, in order.
+    pub fn daily(&self) -> Vec<DailySummary> {
+        let mut days: Vec<(DailySummary, u32)> = Vec::new();
+
+        for item in &self.list {
+            let Some(local) = DateTime::from_timestamp(item.dt as i64 + self.city.timezone as i64, 0) else {
+                continue;
+            };
+            let condition = item.weather.first();
+            // Prefer the condition of the slot closest to midday
+            let distance = (local.hour() as i32 - 12).unsigned_abs();
+
+            match days.last_mut() {
+                Some((day, best)) if day.date == local.date_naive() => {
+                    day.min = day.min.min(item.main.temp_min);
+                    day.max = day.max.max(item.main.temp_max);
+                    day.precipitation += item.precipitation();
+                    day.pop = day.pop.max(item.pop);
+                    if distance < *best {
+                        if let Some(condition) = condition {
+                            day.set_condition(condition);
+                        }
+                        *best = distance;
+                    }
+                }
+                _ => {
+                    let mut day = DailySummary {
+                        date: local.date_naive(),
+                        condition_id: 0,
+                        icon: String::new(),
+                        description: String::new(),
+                        min: item.main.temp_min,
+                        max: item.main.temp_max,
+                        precipitation: item.precipitation(),
+                        pop: item.pop,
+                    };
+                    if let Some(condition) = condition {
+                        day.set_condition(condition);
+                    }
+                    days.push((day, distance));
+                }
+            }
+        }
+
+        days.into_iter().map(|(day, _)| day).collect()
+    }
+}
+
+impl DailySummary {
+    fn set_condition(&mut self, condition: &WeatherCondition) {
+        self.condition_id = condition.id;
+        self.icon = condition.icon.clone();
+        self.description = condition.description.clone();
+    }
+}
+
+impl ForecastItem {
+    /// Combined rain and snow volume for the 3 hour slot, in mm.
+    pub fn precipitation(&self) -> f64 {
+        self.rain.as_ref().map_or(0.0, |r| r.three_hours)
+            + self.snow.as_ref().map_or(0.0, |s| s.three_hours)
+    }
+}
@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+/// One match from the OpenWeatherMap direct geocoding API.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct GeoLocation {
+    pub name: String,
+    pub lat: f64,
+    pub lon: f64,
+    #[serde(default)]
+    pub country: String,
+    pub state: Option<String>,
+}
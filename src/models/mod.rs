@@ -1,2 +1,4 @@
+pub mod forecast;
+pub mod geocoding;
 pub mod weather_new;
 pub use weather_new as weather;
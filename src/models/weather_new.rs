@@ -1,3 +1,4 @@
+use crate::utils::condition;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -11,6 +12,9 @@ pub struct WeatherResponse {
     pub name: String,
     pub visibility: Option<u32>,
     pub timezone: i32,
+    /// Time of the observation, UTC seconds
+    #[serde(default)]
+    pub dt: u64,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -59,23 +63,38 @@ pub struct SystemData {
 }
 
 impl WeatherResponse {
-    pub fn get_weather_symbol(&self) -> &'static str {
-        let icon = self.weather.first().map_or("01d", |w| w.icon.as_str());
-
-        match icon {
-            "01d" => "☀️",
-            "01n" => "🌙",
-            "02d" | "02n" => "⛅",
-            "03d" | "03n" | "04d" | "04n" => "☁️",
-            "09d" | "09n" => "🌧️",
-            "10d" | "10n" => "🌦️",
-            "11d" | "11n" => "⛈️",
-            "13d" | "13n" => "❄️",
-            "50d" | "50n" => "🌫️",
-            _ => "🌈",
+    /// Descriptions of every reported condition, the main one first.
+    ///
+    /// ```
+    /// # use weather_cl::WeatherResponse;
+    /// # let weather: WeatherResponse = serde_json::from_str(r#"{"coord": {"lon": 0.0, "lat": 51.5},
+    /// #     "weather": [{"id": 501, "main": "Rain", "description": "moderate rain", "icon": "10n"},
+    /// #                 {"id": 701, "main": "Mist", "description": "mist", "icon": "50n"},
+    /// #                 {"id": 211, "main": "Thunderstorm", "description": "thunderstorm", "icon": "11n"}],
+    /// #     "main": {"temp": 285.0, "feels_like": 284.0, "temp_min": 284.0, "temp_max": 286.0,
+    /// #              "pressure": 998, "humidity": 93},
+    /// #     "wind": {"speed": 8.8, "deg": 220}, "clouds": {"all": 100},
+    /// #     "sys": {"country": "GB", "sunrise": 0, "sunset": 0},
+    /// #     "name": "London", "visibility": 3500, "timezone": 0}"#)?;
+    /// assert_eq!(weather.description(), "moderate rain, mist and thunderstorm");
+    /// # Ok::<(), serde_json::Error>(())
+    /// ```
+    pub fn description(&self) -> String {
+        match self.weather.as_slice() {
+            [] => String::new(),
+            [only] => only.description.clone(),
+            [rest @ .., last] => {
+                let rest: Vec<&str> = rest.iter().map(|w| w.description.as_str()).collect();
+                format!("{} and {}", rest.join(", "), last.description)
+            }
         }
     }
 
+    /// Emoji for the main condition.
+    pub fn get_weather_symbol(&self) -> &'static str {
+        self.weather.first().map_or("🌈", |c| condition::emoji(c.id, &c.icon))
+    }
+
     pub fn get_temperature_color(&self) -> colored::Color {
         let temp_c = self.main.temp - 273.15;
 
@@ -119,6 +138,25 @@ pub enum TemperatureUnit {
     Kelvin,
 }
 
+impl TemperatureUnit {
+    /// Convert a temperature in Kelvin, as returned by the API, into this unit
+    ///
+    /// ```
+    /// use weather_cl::TemperatureUnit;
+    ///
+    /// assert_eq!(TemperatureUnit::Kelvin.convert(300.0), 300.0);
+    /// assert!((TemperatureUnit::Celsius.convert(300.0) - 26.85).abs() < 1e-9);
+    /// assert!((TemperatureUnit::Fahrenheit.convert(273.15) - 32.0).abs() < 1e-9);
+    /// ```
+    pub fn convert(&self, kelvin: f64) -> f64 {
+        match self {
+            TemperatureUnit::Celsius => kelvin - 273.15,
+            TemperatureUnit::Fahrenheit => (kelvin - 273.15) * 9.0 / 5.0 + 32.0,
+            TemperatureUnit::Kelvin => kelvin,
+        }
+    }
+}
+
 impl std::str::FromStr for TemperatureUnit {
     type Err = String;
 
@@ -141,3 +179,52 @@ impl std::fmt::Display for TemperatureUnit {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_temperature_conversion() {
+        let mock_weather = WeatherResponse {
+            coord: Coordinates { lon: 0.0, lat: 0.0 },
+            weather: vec![WeatherCondition {
+                id: 800,
+                main: "Clear".to_string(),
+                description: "clear sky".to_string(),
+                icon: "01d".to_string(),
+            }],
+            main: MainData {
+                temp: 293.15,
+                feels_like: 295.15,
+                temp_min: 288.15,
+                temp_max: 298.15,
+                pressure: 1013,
+                humidity: 65,
+                sea_level: None,
+                grnd_level: None,
+            },
+            wind: WindData {
+                speed: 5.0,
+                deg: 180,
+                gust: None,
+            },
+            clouds: CloudData { all: 0 },
+            sys: SystemData {
+                country: "US".to_string(),
+                sunrise: 1678867200,
+                sunset: 1678910400,
+            },
+            name: "Test City".to_string(),
+            visibility: Some(10000),
+            timezone: 0,
+            dt: 0,
+        };
+        
+        let celsius = mock_weather.format_temperature(&TemperatureUnit::Celsius);
+        assert!(celsius.contains("20.0°C"));
+        
+        let fahrenheit = mock_weather.format_temperature(&TemperatureUnit::Fahrenheit);
+        assert!(fahrenheit.contains("68.0°F"));
+    }
+}
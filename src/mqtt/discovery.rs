@@ -0,0 +1,253 @@
+use crate::models::weather::{TemperatureUnit, WeatherResponse};
+use serde_json::{json, Map, Value};
+
+/// One Home Assistant sensor derived from a `WeatherResponse`.
+pub struct Sensor {
+    /// Key in the state JSON and suffix of the unique id
+    pub key: &'static str,
+    pub name: &'static str,
+    pub device_class: Option<&'static str>,
+    /// Unit of measurement; `None` for temperatures (taken from the unit flag)
+    /// and text sensors
+    pub unit: Option<&'static str>,
+    pub icon: Option<&'static str>,
+    /// Numeric sensors get `state_class: measurement` for long-term statistics
+    pub numeric: bool,
+    pub value: fn(&WeatherResponse, &TemperatureUnit) -> Value,
+    /// Extra Home Assistant attributes, published as `<key>_attributes` in
+    /// the state JSON
+    pub attributes: Option<fn(&WeatherResponse) -> Value>,
+}
+
+fn round(value: f64) -> Value {
+    json!((value * 10.0).round() / 10.0)
+}
+
+pub const SENSORS: &[Sensor] = &[
+    Sensor {
+        key: "temperature",
+        name: "Temperature",
+        device_class: Some("temperature"),
+        unit: None,
+        icon: None,
+        numeric: true,
+        value: |w, unit| round(unit.convert(w.main.temp)),
+        attributes: None,
+    },
+    Sensor {
+        key: "apparent_temperature",
+        name: "Feels like",
+        device_class: Some("temperature"),
+        unit: None,
+        icon: None,
+        numeric: true,
+        value: |w, unit| round(unit.convert(w.main.feels_like)),
+        attributes: None,
+    },
+    Sensor {
+        key: "humidity",
+        name: "Humidity",
+        device_class: Some("humidity"),
+        unit: Some("%"),
+        icon: None,
+        numeric: true,
+        value: |w, _| json!(w.main.humidity),
+        attributes: None,
+    },
+    Sensor {
+        key: "pressure",
+        name: "Pressure",
+        device_class: Some("atmospheric_pressure"),
+        unit: Some("hPa"),
+        icon: None,
+        numeric: true,
+        value: |w, _| json!(w.main.pressure),
+        attributes: None,
+    },
+    Sensor {
+        key: "wind_speed",
+        name: "Wind speed",
+        device_class: Some("wind_speed"),
+        unit: Some("m/s"),
+        icon: None,
+        numeric: true,
+        value: |w, _| round(w.wind.speed),
+        attributes: None,
+    },
+    Sensor {
+        key: "wind_gust",
+        name: "Wind gust",
+        device_class: Some("wind_speed"),
+        unit: Some("m/s"),
+        icon: None,
+        numeric: true,
+        // Only reported in windy conditions; null shows as unknown in Home
+        // Assistant rather than passing the mean speed off as a gust
+        value: |w, _| w.wind.gust.map_or(Value::Null, round),
+        attributes: None,
+    },
+    Sensor {
+        key: "wind_bearing",
+        name: "Wind bearing",
+        device_class: None,
+        unit: Some("°"),
+        icon: Some("mdi:compass-outline"),
+        numeric: true,
+        value: |w, _| json!(w.wind.deg),
+        attributes: None,
+    },
+    Sensor {
+        key: "cloud_coverage",
+        name: "Cloud coverage",
+        device_class: None,
+        unit: Some("%"),
+        icon: Some("mdi:weather-cloudy"),
+        numeric: true,
+        value: |w, _| json!(w.clouds.all),
+        attributes: None,
+    },
+    Sensor {
+        key: "visibility",
+        name: "Visibility",
+        device_class: Some("distance"),
+        unit: Some("m"),
+        icon: None,
+        numeric: true,
+        value: |w, _| w.visibility.map_or(Value::Null, |v| json!(v)),
+        attributes: None,
+    },
+    Sensor {
+        key: "condition",
+        name: "Condition",
+        device_class: None,
+        unit: None,
+        icon: Some("mdi:weather-partly-cloudy"),
+        numeric: false,
+        value: |w, _| if w.weather.is_empty() { Value::Null } else { json!(w.description()) },
+        attributes: Some(|w| json!({ "condition_ids": w.weather.iter().map(|c| c.id).collect::<Vec<_>>() })),
+    },
+];
+
+/// Topic-safe identifier for a location: "New York" -> "new_york".
+pub fn slug(location: &str) -> String {
+    let slug: String = location
+        .trim()
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    slug.split('_').filter(|part| !part.is_empty()).collect::<Vec<_>>().join("_")
+}
+
+/// Where and what to publish for one location.
+pub struct Topics<'a> {
+    pub prefix: &'a str,
+    pub discovery_prefix: &'a str,
+    pub slug: &'a str,
+}
+
+impl Topics<'_> {
+    pub fn availability(&self) -> String {
+        format!("{}/status", self.prefix)
+    }
+
+    /// Birth/last-will topic Home Assistant itself publishes to.
+    pub fn home_assistant_status(&self) -> String {
+        format!("{}/status", self.discovery_prefix)
+    }
+
+    pub fn state(&self) -> String {
+        format!("{}/{}/state", self.prefix, self.slug)
+    }
+
+    pub fn config(&self, sensor: &Sensor) -> String {
+        format!("{}/sensor/weather_{}/{}/config", self.discovery_prefix, self.slug, sensor.key)
+    }
+}
+
+/// Home Assistant MQTT discovery config for `sensor`. All sensors of one
+/// location share a device, so they show up grouped in Home Assistant.
+pub fn config_payload(sensor: &Sensor, topics: &Topics, label: &str, unit: &TemperatureUnit) -> Value {
+    let mut config = Map::new();
+    config.insert("name".into(), json!(sensor.name));
+    config.insert("unique_id".into(), json!(format!("weather_{}_{}", topics.slug, sensor.key)));
+    config.insert("object_id".into(), json!(format!("weather_{}_{}", topics.slug, sensor.key)));
+    config.insert("state_topic".into(), json!(topics.state()));
+    config.insert("value_template".into(), json!(format!("{{{{ value_json.{} }}}}", sensor.key)));
+    config.insert("availability_topic".into(), json!(topics.availability()));
+    if sensor.attributes.is_some() {
+        config.insert("json_attributes_topic".into(), json!(topics.state()));
+        config.insert(
+            "json_attributes_template".into(),
+            json!(format!("{{{{ value_json.{}_attributes | tojson }}}}", sensor.key)),
+        );
+    }
+
+    let unit_of_measurement = match sensor.device_class {
+        Some("temperature") => Some(unit.to_string()),
+        _ => sensor.unit.map(str::to_string),
+    };
+    if let Some(unit) = unit_of_measurement {
+        config.insert("unit_of_measurement".into(), json!(unit));
+    }
+    if let Some(class) = sensor.device_class {
+        config.insert("device_class".into(), json!(class));
+    }
+    if sensor.numeric {
+        config.insert("state_class".into(), json!("measurement"));
+    }
+    if let Some(icon) = sensor.icon {
+        config.insert("icon".into(), json!(icon));
+    }
+
+    config.insert(
+        "device".into(),
+        json!({
+            "identifiers": [format!("weather_{}", topics.slug)],
+            "name": format!("Weather {}", label),
+            "manufacturer": "OpenWeatherMap",
+            "model": "weather-cli",
+            "sw_version": env!("CARGO_PKG_VERSION"),
+        }),
+    );
+    Value::Object(config)
+}
+
+/// One JSON document with every sensor value, published to the state topic.
+pub fn state_payload(weather: &WeatherResponse, unit: &TemperatureUnit) -> Value {
+    let mut values = Map::new();
+    for sensor in SENSORS {
+        values.insert(sensor.key.to_string(), (sensor.value)(weather, unit));
+        if let Some(attributes) = sensor.attributes {
+            values.insert(format!("{}_attributes", sensor.key), attributes(weather));
+        }
+    }
+    Value::Object(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_discovery_config() {
+        assert_eq!(slug("  New York, US "), "new_york_us");
+
+        let topics = Topics { prefix: "weather", discovery_prefix: "homeassistant", slug: "home" };
+        let temperature = &SENSORS[0];
+        assert_eq!(topics.config(temperature), "homeassistant/sensor/weather_home/temperature/config");
+
+        let config = config_payload(temperature, &topics, "Berlin, DE", &TemperatureUnit::Fahrenheit);
+        assert_eq!(config["device_class"], "temperature");
+        assert_eq!(config["unit_of_measurement"], "°F");
+        assert_eq!(config["state_topic"], "weather/home/state");
+        assert_eq!(config["availability_topic"], "weather/status");
+        assert_eq!(config["value_template"], "{{ value_json.temperature }}");
+        assert_eq!(config["device"]["identifiers"][0], "weather_home");
+
+        let condition = SENSORS.iter().find(|s| s.key == "condition").unwrap();
+        let config = config_payload(condition, &topics, "Berlin, DE", &TemperatureUnit::Celsius);
+        assert_eq!(config["json_attributes_topic"], "weather/home/state");
+        assert_eq!(config["json_attributes_template"], "{{ value_json.condition_attributes | tojson }}");
+    }
+}
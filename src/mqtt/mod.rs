@@ -0,0 +1,170 @@
+pub mod discovery;
+
+use crate::models::weather::{TemperatureUnit, WeatherResponse};
+use anyhow::{anyhow, Result};
+use discovery::{Topics, SENSORS};
+use rumqttc::{Client, Event, LastWill, MqttOptions, Outgoing, Packet, QoS};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+const ONLINE: &str = "online";
+const OFFLINE: &str = "offline";
+
+/// Delay before the connection thread retries after a broker error.
+const RETRY: Duration = Duration::from_secs(5);
+
+/// Broker address and credentials after merging flags and `[mqtt]`.
+#[derive(Debug, Clone)]
+pub struct Broker {
+    pub host: String,
+    pub port: u16,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub client_id: String,
+}
+
+/// What the background connection thread reports back.
+#[derive(Debug)]
+pub enum BrokerEvent {
+    /// (Re)connected; retained topics and subscriptions must be refreshed
+    Connected,
+    /// Home Assistant restarted and wants discovery configs again
+    HomeAssistantOnline,
+    Error(String),
+    Disconnected,
+}
+
+/// MQTT connection with a retained availability topic whose last will
+/// flips to "offline" if the process dies without saying goodbye.
+pub struct Publisher {
+    client: Client,
+    events: Receiver<BrokerEvent>,
+    availability: String,
+    prefix: String,
+    discovery_prefix: String,
+    unit: TemperatureUnit,
+}
+
+impl Publisher {
+    pub fn connect(broker: &Broker, prefix: &str, discovery_prefix: &str, unit: TemperatureUnit) -> Result<Self> {
+        if broker.client_id.trim().is_empty() || broker.client_id.starts_with(' ') {
+            return Err(anyhow!("MQTT client id must not be empty or start with a space."));
+        }
+        let topics = Topics { prefix, discovery_prefix, slug: "" };
+        let availability = topics.availability();
+        let ha_status = topics.home_assistant_status();
+
+        let mut options = MqttOptions::new(&broker.client_id, &broker.host, broker.port);
+        options.set_keep_alive(Duration::from_secs(30));
+        options.set_last_will(LastWill::new(availability.clone(), OFFLINE, QoS::AtLeastOnce, true));
+        if let Some(username) = &broker.username {
+            options.set_credentials(username, broker.password.clone().unwrap_or_default());
+        }
+
+        let (client, mut connection) = Client::new(options, 100);
+        let (tx, events) = mpsc::channel();
+
+        thread::spawn(move || {
+            for notification in connection.iter() {
+                let event = match notification {
+                    Ok(Event::Incoming(Packet::ConnAck(_))) => BrokerEvent::Connected,
+                    Ok(Event::Incoming(Packet::Publish(publish)))
+                        if publish.topic == ha_status && publish.payload.as_ref() == ONLINE.as_bytes() =>
+                    {
+                        BrokerEvent::HomeAssistantOnline
+                    }
+                    Ok(Event::Outgoing(Outgoing::Disconnect)) => {
+                        let _ = tx.send(BrokerEvent::Disconnected);
+                        break;
+                    }
+                    Ok(_) => continue,
+                    Err(e) => {
+                        if tx.send(BrokerEvent::Error(e.to_string())).is_err() {
+                            break;
+                        }
+                        // The iterator reconnects on the next call
+                        thread::sleep(RETRY);
+                        continue;
+                    }
+                };
+                if tx.send(event).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(Self {
+            client,
+            events,
+            availability,
+            prefix: prefix.to_string(),
+            discovery_prefix: discovery_prefix.to_string(),
+            unit,
+        })
+    }
+
+    pub fn unit(&self) -> &TemperatureUnit {
+        &self.unit
+    }
+
+    /// Wait up to `timeout` for something to happen on the connection.
+    pub fn next_event(&self, timeout: Duration) -> Option<BrokerEvent> {
+        match self.events.recv_timeout(timeout) {
+            Ok(event) => Some(event),
+            Err(RecvTimeoutError::Timeout) => None,
+            Err(RecvTimeoutError::Disconnected) => Some(BrokerEvent::Disconnected),
+        }
+    }
+
+    /// Mark the bridge online and listen for Home Assistant restarts.
+    pub fn online(&self) -> Result<()> {
+        self.publish(self.availability.clone(), ONLINE.as_bytes().to_vec(), true)?;
+        self.client
+            .subscribe(self.topics("").home_assistant_status(), QoS::AtLeastOnce)
+            .map_err(|e| anyhow!("MQTT subscribe failed: {}", e))
+    }
+
+    /// Publish retained discovery configs for every sensor of one location.
+    pub fn announce(&self, location: &str, label: &str) -> Result<()> {
+        let slug = discovery::slug(location);
+        let topics = self.topics(&slug);
+        for sensor in SENSORS {
+            let config = discovery::config_payload(sensor, &topics, label, &self.unit);
+            self.publish(topics.config(sensor), serde_json::to_vec(&config)?, true)?;
+        }
+        Ok(())
+    }
+
+    pub fn publish_state(&self, location: &str, weather: &WeatherResponse) -> Result<()> {
+        let slug = discovery::slug(location);
+        let state = discovery::state_payload(weather, &self.unit);
+        self.publish(self.topics(&slug).state(), serde_json::to_vec(&state)?, true)
+    }
+
+    /// Publish "offline" and disconnect cleanly, giving the broker a moment
+    /// to receive both before the process exits.
+    pub fn shutdown(&self) {
+        let _ = self.publish(self.availability.clone(), OFFLINE.as_bytes().to_vec(), true);
+        let _ = self.client.disconnect();
+        while let Some(event) = self.next_event(Duration::from_secs(2)) {
+            if matches!(event, BrokerEvent::Disconnected) {
+                break;
+            }
+        }
+    }
+
+    fn topics<'a>(&'a self, slug: &'a str) -> Topics<'a> {
+        Topics {
+            prefix: &self.prefix,
+            discovery_prefix: &self.discovery_prefix,
+            slug,
+        }
+    }
+
+    fn publish(&self, topic: String, payload: Vec<u8>, retain: bool) -> Result<()> {
+        self.client
+            .publish(topic, QoS::AtLeastOnce, retain, payload)
+            .map_err(|e| anyhow!("MQTT publish failed: {}", e))
+    }
+}
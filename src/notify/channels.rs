@@ -0,0 +1,180 @@
+use crate::config::notify::{ChannelConfig, ChannelKind};
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use reqwest::blocking::Client;
+use serde_json::{json, Value};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventKind {
+    Triggered,
+    Resolved,
+    Test,
+}
+
+impl EventKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventKind::Triggered => "triggered",
+            EventKind::Resolved => "resolved",
+            EventKind::Test => "test",
+        }
+    }
+
+    fn emoji(&self) -> &'static str {
+        match self {
+            EventKind::Triggered => "🚨",
+            EventKind::Resolved => "✅",
+            EventKind::Test => "🔔",
+        }
+    }
+}
+
+/// A notification about one rule at one location.
+#[derive(Debug, Clone)]
+pub struct Event {
+    pub kind: EventKind,
+    pub rule: String,
+    pub location: String,
+    /// Observed value, e.g. "31.2°C"
+    pub observed: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl Event {
+    /// One-line human readable summary used by chat payloads.
+    pub fn message(&self) -> String {
+        format!("{} {}", self.kind.emoji(), self.summary())
+    }
+
+    /// [`message`](Self::message) without the leading emoji.
+    pub fn summary(&self) -> String {
+        match self.kind {
+            EventKind::Triggered => format!("{} in {} (observed {})", self.rule, self.location, self.observed),
+            EventKind::Resolved => format!("Resolved: {} in {} (now {})", self.rule, self.location, self.observed),
+            EventKind::Test => "Test notification from weather CLI".to_string(),
+        }
+    }
+
+    /// Sidebar color for Slack attachments and Discord embeds.
+    fn color(&self) -> u32 {
+        match self.kind {
+            EventKind::Triggered => 0xd9534f,
+            EventKind::Resolved => 0x5cb85c,
+            EventKind::Test => 0x667eea,
+        }
+    }
+}
+
+/// Body to POST for `event` in the format a channel kind expects.
+pub fn payload(kind: ChannelKind, event: &Event) -> Value {
+    match kind {
+        ChannelKind::Webhook => json!({
+            "event": event.kind.as_str(),
+            "rule": event.rule,
+            "location": event.location,
+            "observed": event.observed,
+            "message": event.message(),
+            "timestamp": event.timestamp.to_rfc3339(),
+        }),
+        ChannelKind::Slack => json!({
+            "text": slack_escape(&event.message()),
+            "attachments": [{
+                "color": format!("#{:06x}", event.color()),
+                "fields": [
+                    { "title": "Rule", "value": slack_escape(&event.rule), "short": true },
+                    { "title": "Location", "value": slack_escape(&event.location), "short": true },
+                ],
+                "ts": event.timestamp.timestamp(),
+            }],
+        }),
+        ChannelKind::Discord => json!({
+            "content": discord_escape(&event.message()),
+            "embeds": [{
+                "title": format!("{}: {}", event.kind.as_str(), discord_escape(&event.rule)),
+                "description": format!("{} · observed {}", discord_escape(&event.location), event.observed),
+                "color": event.color(),
+                "timestamp": event.timestamp.to_rfc3339(),
+            }],
+            // A rule or place named "@everyone" must not ping anyone
+            "allowed_mentions": { "parse": [] },
+        }),
+    }
+}
+
+/// Slack reads `&`, `<` and `>` as markup for links and mentions.
+fn slack_escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// Backslash the characters Discord reads as markdown.
+fn discord_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '*' | '_' | '~' | '`' | '|' | '>') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+pub fn send(client: &Client, channel: &ChannelConfig, event: &Event) -> Result<()> {
+    // Webhook URLs carry their credentials, so keep them out of error messages
+    let response = client
+        .post(&channel.url)
+        .json(&payload(channel.kind, event))
+        .send()
+        .map_err(|e| e.without_url())?;
+    if !response.status().is_success() {
+        return Err(anyhow!("{} returned {}", channel.name, response.status()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn event(kind: EventKind) -> Event {
+        Event {
+            kind,
+            rule: "rain_<now> & *soon*".to_string(),
+            location: "@everyone's town".to_string(),
+            observed: "31.0°C".to_string(),
+            timestamp: Utc.with_ymd_and_hms(2026, 10, 19, 12, 0, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_webhook_payload() {
+        let body = payload(ChannelKind::Webhook, &event(EventKind::Triggered));
+        assert_eq!(body["event"], "triggered");
+        assert_eq!(body["rule"], "rain_<now> & *soon*");
+        assert_eq!(body["timestamp"], "2026-10-19T12:00:00+00:00");
+    }
+
+    #[test]
+    fn test_slack_payload_escapes_markup() {
+        let body = payload(ChannelKind::Slack, &event(EventKind::Triggered));
+        assert_eq!(
+            body["text"],
+            "🚨 rain_&lt;now&gt; &amp; *soon* in @everyone's town (observed 31.0°C)"
+        );
+        assert_eq!(body["attachments"][0]["color"], "#d9534f");
+        assert_eq!(body["attachments"][0]["fields"][0]["value"], "rain_&lt;now&gt; &amp; *soon*");
+        assert_eq!(body["attachments"][0]["ts"], 1_792_411_200);
+    }
+
+    #[test]
+    fn test_discord_payload_escapes_markdown_and_mentions() {
+        let body = payload(ChannelKind::Discord, &event(EventKind::Resolved));
+        assert_eq!(
+            body["content"],
+            r"✅ Resolved: rain\_<now\> & \*soon\* in @everyone's town (now 31.0°C)"
+        );
+        assert_eq!(body["embeds"][0]["title"], r"resolved: rain\_<now\> & \*soon\*");
+        assert_eq!(body["embeds"][0]["color"], 0x5cb85c);
+        assert_eq!(body["allowed_mentions"]["parse"], json!([]));
+    }
+}
@@ -0,0 +1,317 @@
+pub mod channels;
+
+use crate::config::notify::{ChannelConfig, NotifyConfig};
+use crate::models::weather::{TemperatureUnit, WeatherResponse};
+use crate::utils::glyphs::{icon, Charset};
+use crate::utils::rules::{Observation, Rule};
+use anyhow::{anyhow, Result};
+use channels::{Event, EventKind};
+use chrono::Utc;
+use reqwest::blocking::Client;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+const DEFAULT_COOLDOWN: Duration = Duration::from_secs(3600);
+
+/// Temperature thresholds in `[notify]` rules are in Celsius, whatever unit
+/// the output is shown in.
+const UNIT: TemperatureUnit = TemperatureUnit::Celsius;
+
+struct ActiveRule {
+    name: String,
+    location: Option<String>,
+    rule: Rule,
+    cooldown: Duration,
+    channels: Vec<usize>,
+}
+
+/// Firing state of one rule at one location.
+#[derive(Default)]
+struct RuleState {
+    firing: bool,
+    last_triggered: Option<Instant>,
+}
+
+/// Evaluates notification rules against fresh observations and delivers
+/// "triggered" / "resolved" events. A rule only notifies when its state
+/// changes, and a new trigger within the cooldown is held back.
+pub struct Notifier {
+    rules: Vec<ActiveRule>,
+    channels: Vec<ChannelConfig>,
+    client: Client,
+    states: HashMap<(usize, String), RuleState>,
+}
+
+impl Notifier {
+    pub fn new(config: NotifyConfig) -> Result<Self> {
+        let default_cooldown = config.cooldown.unwrap_or(DEFAULT_COOLDOWN);
+        let mut rules = Vec::new();
+
+        for (i, rule) in config.rules.iter().enumerate() {
+            let channels = if rule.channels.is_empty() {
+                (0..config.channels.len()).collect()
+            } else {
+                rule.channels
+                    .iter()
+                    .map(|name| {
+                        config.channels.iter().position(|c| &c.name == name).ok_or_else(|| {
+                            anyhow!("Notification rule refers to unknown channel '{}'", name)
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?
+            };
+            let parsed = rule.rule()?;
+
+            rules.push(ActiveRule {
+                name: rule.name.clone().unwrap_or_else(|| format!("rule-{}", i + 1)),
+                location: rule.location.clone(),
+                rule: parsed,
+                cooldown: rule.cooldown.unwrap_or(default_cooldown),
+                channels,
+            });
+        }
+
+        let client = Client::builder()
+            .timeout(Duration::from_secs(10))
+            .user_agent("rust-weather-cli/1.0")
+            .build()?;
+
+        Ok(Self {
+            rules,
+            channels: config.channels,
+            client,
+            states: HashMap::new(),
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty() || self.channels.is_empty()
+    }
+
+    /// Every location some rule needs, with `default` standing in for rules
+    /// that do not name one.
+    pub fn locations(&self, default: &str) -> Vec<String> {
+        let mut locations: Vec<String> = Vec::new();
+        for rule in &self.rules {
+            let location = rule.location.as_deref().unwrap_or(default);
+            if !locations.iter().any(|l| l.eq_ignore_ascii_case(location)) {
+                locations.push(location.to_string());
+            }
+        }
+        locations
+    }
+
+    /// Check the rules for `location` (as the user typed it) against a new
+    /// observation. `default` is the place rules without a location apply to.
+    /// Returns log lines describing what was sent or failed.
+    pub fn evaluate(&mut self, location: &str, default: &str, weather: &WeatherResponse) -> Vec<String> {
+        let observation = Observation::from(weather);
+        let label = if weather.sys.country.is_empty() {
+            weather.name.clone()
+        } else {
+            format!("{}, {}", weather.name, weather.sys.country)
+        };
+        let mut log = Vec::new();
+
+        for index in 0..self.rules.len() {
+            let rule = &self.rules[index];
+            if !rule.location.as_deref().unwrap_or(default).eq_ignore_ascii_case(location) {
+                continue;
+            }
+
+            let matched = rule.rule.evaluate(&observation, &UNIT);
+            let key = (index, location.to_lowercase());
+            let state = self.states.entry(key.clone()).or_default();
+
+            let kind = match (state.firing, &matched) {
+                (false, Some(_)) => {
+                    if state.last_triggered.is_some_and(|t| t.elapsed() < rule.cooldown) {
+                        continue;
+                    }
+                    EventKind::Triggered
+                }
+                (true, None) => EventKind::Resolved,
+                // Unchanged: already notified, or nothing to say
+                _ => continue,
+            };
+
+            let event = Event {
+                kind,
+                rule: format!("{} ({})", rule.name, rule.rule.describe(&UNIT)),
+                location: label.clone(),
+                observed: matched.unwrap_or_else(|| rule.rule.observed(&observation, &UNIT)),
+                timestamp: Utc::now(),
+            };
+
+            let (delivered, failures) = self.deliver(&self.rules[index].channels, &event);
+            log.extend(failures);
+            if delivered.is_empty() {
+                // Leave the state alone so the next evaluation retries
+                continue;
+            }
+
+            let state = self.states.get_mut(&key).expect("state inserted above");
+            state.firing = kind == EventKind::Triggered;
+            if state.firing {
+                state.last_triggered = Some(Instant::now());
+            }
+            let message = match Charset::current() {
+                Charset::Unicode => event.message(),
+                Charset::Ascii => Charset::Ascii.text(&event.summary()).into_owned(),
+            };
+            log.push(format!("{} {} {} {}", icon("🔔", "*"), message, icon("→", "->"), delivered.join(", ")));
+        }
+
+        log
+    }
+
+    /// Send a test event to one channel or all of them.
+    pub fn send_test(&self, channel: Option<&str>) -> Result<Vec<String>> {
+        let targets: Vec<usize> = match channel {
+            Some(name) => vec![self
+                .channels
+                .iter()
+                .position(|c| c.name == name)
+                .ok_or_else(|| anyhow!("Unknown channel '{}'", name))?],
+            None => (0..self.channels.len()).collect(),
+        };
+        if targets.is_empty() {
+            return Err(anyhow!("No notification channels configured under [notify]."));
+        }
+
+        let event = Event {
+            kind: EventKind::Test,
+            rule: "test".to_string(),
+            location: "test".to_string(),
+            observed: String::new(),
+            timestamp: Utc::now(),
+        };
+        let (delivered, mut log) = self.deliver(&targets, &event);
+        if delivered.is_empty() {
+            return Err(anyhow!("No test message could be delivered.\n{}", log.join("\n")));
+        }
+        log.extend(delivered.iter().map(|name| format!("{} Test sent to {}", icon("🔔", "*"), name)));
+        Ok(log)
+    }
+
+    fn deliver(&self, targets: &[usize], event: &Event) -> (Vec<String>, Vec<String>) {
+        let mut delivered = Vec::new();
+        let mut failures = Vec::new();
+        for channel in targets.iter().map(|i| &self.channels[*i]) {
+            match channels::send(&self.client, channel, event) {
+                Ok(()) => delivered.push(channel.name.clone()),
+                Err(e) => failures.push(format!("{} Delivery to {} failed: {}", icon("⚠️", "!"), channel.name, e)),
+            }
+        }
+        (delivered, failures)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::notify::{ChannelKind, RuleConfig};
+    use crate::models::weather::*;
+    use crate::utils::rules::{Comparator, Metric};
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::thread;
+
+    /// Minimal HTTP receiver: answers 200 and forwards each JSON body.
+    fn receiver() -> (String, mpsc::Receiver<serde_json::Value>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = line.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            length = value.trim().parse().unwrap();
+                        }
+                    }
+                }
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+                stream
+                    .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\nconnection: close\r\n\r\n")
+                    .unwrap();
+                tx.send(serde_json::from_slice(&body).unwrap()).unwrap();
+            }
+        });
+
+        (url, rx)
+    }
+
+    fn weather(temp_c: f64) -> WeatherResponse {
+        WeatherResponse {
+            coord: Coordinates { lon: 0.0, lat: 0.0 },
+            weather: vec![WeatherCondition {
+                id: 800,
+                main: "Clear".to_string(),
+                description: "clear sky".to_string(),
+                icon: "01d".to_string(),
+            }],
+            main: MainData {
+                temp: temp_c + 273.15,
+                feels_like: temp_c + 273.15,
+                temp_min: temp_c + 273.15,
+                temp_max: temp_c + 273.15,
+                pressure: 1013,
+                humidity: 50,
+                sea_level: None,
+                grnd_level: None,
+            },
+            wind: WindData { speed: 2.0, deg: 90, gust: None },
+            clouds: CloudData { all: 0 },
+            sys: SystemData { country: "GB".to_string(), sunrise: 0, sunset: 0 },
+            name: "London".to_string(),
+            visibility: Some(10000),
+            timezone: 0,
+            dt: 0,
+        }
+    }
+
+    #[test]
+    fn test_trigger_dedup_resolve_and_cooldown() {
+        let (url, received) = receiver();
+        let config = NotifyConfig {
+            cooldown: Some(Duration::from_secs(3600)),
+            channels: vec![ChannelConfig { name: "hook".to_string(), kind: ChannelKind::Webhook, url }],
+            rules: vec![RuleConfig {
+                name: Some("hot".to_string()),
+                metric: Some(Metric::Temp),
+                op: Some(Comparator::Above),
+                value: Some(30.0),
+                ..Default::default()
+            }],
+        };
+        let mut notifier = Notifier::new(config).unwrap();
+
+        notifier.evaluate("london", "london", &weather(31.0));
+        let event = received.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(event["event"], "triggered");
+        assert_eq!(event["observed"], "31.0°C");
+
+        // Still hot: no duplicate
+        assert!(notifier.evaluate("london", "london", &weather(32.0)).is_empty());
+
+        notifier.evaluate("london", "london", &weather(25.0));
+        let event = received.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(event["event"], "resolved");
+
+        // Hot again within the cooldown: held back
+        assert!(notifier.evaluate("london", "london", &weather(31.0)).is_empty());
+        assert!(received.recv_timeout(Duration::from_millis(200)).is_err());
+    }
+}
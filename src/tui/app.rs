@@ -0,0 +1,148 @@
+use crate::models::forecast::ForecastResponse;
+use crate::models::weather::{TemperatureUnit, WeatherResponse};
+use crate::utils::formatters::format_sun_time;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Everything known about one entry in the location list.
+pub struct LocationState {
+    pub query: String,
+    pub weather: Option<Arc<WeatherResponse>>,
+    pub forecast: Option<ForecastResponse>,
+    pub error: Option<String>,
+    pub updated: Option<Instant>,
+    pub loading: bool,
+}
+
+impl LocationState {
+    fn new(query: String) -> Self {
+        Self {
+            query,
+            weather: None,
+            forecast: None,
+            error: None,
+            updated: None,
+            loading: false,
+        }
+    }
+}
+
+/// Result of a background refresh for one location.
+pub struct Update {
+    pub query: String,
+    pub result: Result<(Arc<WeatherResponse>, Option<ForecastResponse>), String>,
+}
+
+pub struct App {
+    pub locations: Vec<LocationState>,
+    pub selected: usize,
+    pub unit: TemperatureUnit,
+    pub interval: Duration,
+    pub should_quit: bool,
+}
+
+impl App {
+    pub fn new(queries: Vec<String>, unit: TemperatureUnit, interval: Duration) -> Self {
+        Self {
+            locations: queries.into_iter().map(LocationState::new).collect(),
+            selected: 0,
+            unit,
+            interval,
+            should_quit: false,
+        }
+    }
+
+    pub fn selected(&self) -> &LocationState {
+        &self.locations[self.selected]
+    }
+
+    pub fn select_next(&mut self) {
+        self.selected = (self.selected + 1) % self.locations.len();
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = (self.selected + self.locations.len() - 1) % self.locations.len();
+    }
+
+    /// Cycle Celsius → Fahrenheit → Kelvin.
+    pub fn toggle_unit(&mut self) {
+        self.unit = match self.unit {
+            TemperatureUnit::Celsius => TemperatureUnit::Fahrenheit,
+            TemperatureUnit::Fahrenheit => TemperatureUnit::Kelvin,
+            TemperatureUnit::Kelvin => TemperatureUnit::Celsius,
+        };
+    }
+
+    /// Whether the selected location has no data yet or it is older than
+    /// the refresh interval.
+    pub fn selected_is_stale(&self) -> bool {
+        let location = self.selected();
+        !location.loading && location.updated.is_none_or(|t| t.elapsed() >= self.interval)
+    }
+
+    /// Mark the selected location as loading and return the query to fetch.
+    pub fn start_refresh(&mut self) -> Option<String> {
+        let location = &mut self.locations[self.selected];
+        if location.loading {
+            return None;
+        }
+        location.loading = true;
+        Some(location.query.clone())
+    }
+
+    pub fn apply(&mut self, update: Update) {
+        let Some(location) = self.locations.iter_mut().find(|l| l.query == update.query) else {
+            return;
+        };
+        location.loading = false;
+        location.updated = Some(Instant::now());
+        match update.result {
+            Ok((weather, forecast)) => {
+                location.weather = Some(weather);
+                // Keep the previous forecast if only the forecast request failed
+                if forecast.is_some() {
+                    location.forecast = forecast;
+                }
+                location.error = None;
+            }
+            Err(e) => location.error = Some(e),
+        }
+    }
+}
+
+/// Warnings derived from the next 24 hours of forecast. The free API has no
+/// official alerts feed, so these flag conditions worth a glance.
+pub fn forecast_alerts(forecast: &ForecastResponse, unit: &TemperatureUnit) -> Vec<String> {
+    let window: Vec<_> = forecast.list.iter().take(8).collect();
+    let timezone = forecast.city.timezone;
+    let mut alerts = Vec::new();
+
+    if let Some(storm) = window.iter().find(|i| i.weather.iter().any(|w| (200..300).contains(&w.id))) {
+        alerts.push(format!("⛈️  Thunderstorms around {}", format_sun_time(storm.dt, timezone)));
+    }
+
+    let gust = window
+        .iter()
+        .map(|i| i.wind.gust.unwrap_or(i.wind.speed))
+        .fold(0.0, f64::max);
+    if gust >= 17.0 {
+        alerts.push(format!("💨 Strong wind, gusts up to {:.0} m/s", gust));
+    }
+
+    let precipitation: f64 = window.iter().map(|i| i.precipitation()).sum();
+    if precipitation >= 10.0 {
+        alerts.push(format!("🌧️  Heavy precipitation, {:.1} mm in 24h", precipitation));
+    }
+
+    let coldest = window.iter().map(|i| i.main.temp_min).fold(f64::INFINITY, f64::min);
+    if coldest <= 273.15 {
+        alerts.push(format!("🧊 Frost, down to {:.1}{}", unit.convert(coldest), unit));
+    }
+
+    let hottest = window.iter().map(|i| i.main.temp_max).fold(f64::NEG_INFINITY, f64::max);
+    if hottest >= 305.15 {
+        alerts.push(format!("🔥 Heat, up to {:.1}{}", unit.convert(hottest), unit));
+    }
+
+    alerts
+}
@@ -0,0 +1,105 @@
+mod app;
+mod ui;
+
+use crate::api::cache::CachedWeatherClient;
+use crate::config::locations::Locations;
+use crate::models::weather::TemperatureUnit;
+use anyhow::Result;
+use app::{App, Update};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::DefaultTerminal;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+/// How long to wait for a key press before checking for finished fetches.
+const POLL: Duration = Duration::from_millis(200);
+
+/// Full-screen dashboard over `queries` (city names or saved aliases).
+pub fn run(
+    cache: Arc<CachedWeatherClient>,
+    locations: Locations,
+    queries: Vec<String>,
+    unit: TemperatureUnit,
+    interval: Duration,
+) -> Result<()> {
+    let (requests, updates) = spawn_fetcher(cache, locations);
+    let mut app = App::new(queries, unit, interval);
+
+    let mut terminal = ratatui::try_init()?;
+    let result = event_loop(&mut terminal, &mut app, &requests, &updates);
+    ratatui::restore();
+    result
+}
+
+fn event_loop(
+    terminal: &mut DefaultTerminal,
+    app: &mut App,
+    requests: &Sender<String>,
+    updates: &Receiver<Update>,
+) -> Result<()> {
+    while !app.should_quit {
+        while let Ok(update) = updates.try_recv() {
+            app.apply(update);
+        }
+        if app.selected_is_stale() {
+            request_refresh(app, requests);
+        }
+
+        terminal.draw(|frame| ui::draw(frame, app))?;
+
+        // Resize events need no handling: the next draw uses the new size
+        if event::poll(POLL)? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind != KeyEventKind::Press {
+                    continue;
+                }
+                match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc => app.should_quit = true,
+                    KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        app.should_quit = true
+                    }
+                    KeyCode::Down | KeyCode::Char('j') | KeyCode::Tab => app.select_next(),
+                    KeyCode::Up | KeyCode::Char('k') | KeyCode::BackTab => app.select_previous(),
+                    KeyCode::Char('u') => app.toggle_unit(),
+                    KeyCode::Char('r') => request_refresh(app, requests),
+                    _ => {}
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn request_refresh(app: &mut App, requests: &Sender<String>) {
+    if let Some(query) = app.start_refresh() {
+        let _ = requests.send(query);
+    }
+}
+
+/// Fetch on a background thread so the UI stays responsive while the
+/// blocking HTTP client waits. Current conditions go through the shared
+/// cache; the forecast is optional and only warns through a missing pane.
+fn spawn_fetcher(cache: Arc<CachedWeatherClient>, locations: Locations) -> (Sender<String>, Receiver<Update>) {
+    let (request_tx, request_rx) = mpsc::channel::<String>();
+    let (update_tx, update_rx) = mpsc::channel();
+
+    thread::spawn(move || {
+        for query in request_rx {
+            let result = cache
+                .get_weather(&query)
+                .map(|weather| {
+                    let forecast = cache.client().get_forecast_at(&locations.resolve(&query)).ok();
+                    (weather, forecast)
+                })
+                .map_err(|e| e.to_string());
+            if update_tx.send(Update { query, result }).is_err() {
+                break;
+            }
+        }
+    });
+
+    (request_tx, update_rx)
+}
+
@@ -0,0 +1,269 @@
+use crate::models::forecast::ForecastItem;
+use crate::models::weather::TemperatureUnit;
+use crate::tui::app::{forecast_alerts, App, LocationState};
+use crate::utils::condition;
+use crate::utils::formatters::*;
+use chrono::Datelike;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::symbols::Marker;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{
+    Axis, Block, Chart, Dataset, GraphType, List, ListItem, ListState, Paragraph, Row, Sparkline,
+    Table, Wrap,
+};
+use ratatui::Frame;
+
+/// Number of 3 hour forecast slots shown in the hourly chart (48 hours).
+const HOURLY_SLOTS: usize = 16;
+
+pub fn draw(frame: &mut Frame, app: &App) {
+    let [main, footer] = Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+    let [list, right] = Layout::horizontal([Constraint::Length(26), Constraint::Min(0)]).areas(main);
+    let [current, hourly, bottom] = Layout::vertical([
+        Constraint::Length(8),
+        Constraint::Min(10),
+        Constraint::Length(9),
+    ])
+    .areas(right);
+    let [daily, alerts] =
+        Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)]).areas(bottom);
+
+    draw_locations(frame, app, list);
+    draw_current(frame, app, current);
+    draw_hourly(frame, app, hourly);
+    draw_daily(frame, app, daily);
+    draw_alerts(frame, app, alerts);
+    draw_footer(frame, app, footer);
+}
+
+fn draw_locations(frame: &mut Frame, app: &App, area: Rect) {
+    let items: Vec<ListItem> = app
+        .locations
+        .iter()
+        .map(|location| {
+            let status = match (&location.weather, &location.error, location.loading) {
+                (_, _, true) => "…".to_string(),
+                (_, Some(_), _) => "!".to_string(),
+                (Some(w), None, _) => format!("{:.0}{}", app.unit.convert(w.main.temp), app.unit),
+                (None, None, _) => String::new(),
+            };
+            ListItem::new(Line::from(vec![
+                Span::raw(format!("{:<14}", location.query)),
+                Span::styled(status, Style::default().fg(Color::Yellow)),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(Block::bordered().title(" Locations "))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("› ");
+    let mut state = ListState::default().with_selected(Some(app.selected));
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+fn draw_current(frame: &mut Frame, app: &App, area: Rect) {
+    let location = app.selected();
+    let block = Block::bordered().title(format!(" {} ", location.query));
+
+    let Some(weather) = &location.weather else {
+        frame.render_widget(placeholder(location).block(block), area);
+        return;
+    };
+
+    let condition = weather.weather.first();
+    let name = if weather.sys.country.is_empty() {
+        weather.name.clone()
+    } else {
+        format!("{}, {}", weather.name, weather.sys.country)
+    };
+    let unit = &app.unit;
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::raw(format!("{} ", condition.map_or("🌈", |c| condition::emoji(c.id, &c.icon)))),
+            Span::raw(name).bold().cyan(),
+            Span::raw(format!("  {}", weather.description())),
+        ]),
+        Line::from(vec![
+            Span::raw(weather.format_temperature(unit)).bold().yellow(),
+            Span::raw(format!(
+                "  feels like {:.1}{}  ·  ↓{:.1}{} ↑{:.1}{}",
+                unit.convert(weather.main.feels_like),
+                unit,
+                unit.convert(weather.main.temp_min),
+                unit,
+                unit.convert(weather.main.temp_max),
+                unit
+            )),
+        ]),
+        Line::from(format!(
+            "💧 {}   🎈 {}   ☁️ {}%",
+            format_humidity(weather.main.humidity),
+            format_pressure(weather.main.pressure),
+            weather.clouds.all
+        )),
+        Line::from(format!(
+            "💨 {:.1} m/s {}{}   👁️ {}",
+            weather.wind.speed,
+            format_wind_direction(weather.wind.deg),
+            weather.wind.gust.map_or(String::new(), |g| format!(" (gusts {:.1})", g)),
+            format_visibility(weather.visibility)
+        )),
+        Line::from(format!(
+            "🌅 {}   🌇 {}",
+            format_sun_time(weather.sys.sunrise, weather.timezone),
+            format_sun_time(weather.sys.sunset, weather.timezone)
+        )),
+    ];
+    if let Some(error) = &location.error {
+        lines.push(Line::from(format!("⚠️ Refresh failed: {}", error)).red());
+    }
+
+    frame.render_widget(Paragraph::new(lines).block(block).wrap(Wrap { trim: true }), area);
+}
+
+fn draw_hourly(frame: &mut Frame, app: &App, area: Rect) {
+    let block = Block::bordered().title(" Next 48 hours ");
+    let Some(forecast) = &app.selected().forecast else {
+        frame.render_widget(Paragraph::new("No forecast yet").dim().block(block), area);
+        return;
+    };
+
+    let items: Vec<&ForecastItem> = forecast.list.iter().take(HOURLY_SLOTS).collect();
+    if items.len() < 2 {
+        frame.render_widget(Paragraph::new("Not enough forecast data").dim().block(block), area);
+        return;
+    }
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    let [chart_area, rain_area] =
+        Layout::vertical([Constraint::Min(5), Constraint::Length(3)]).areas(inner);
+
+    let start = items[0].dt as f64;
+    let points: Vec<(f64, f64)> = items
+        .iter()
+        .map(|i| ((i.dt as f64 - start) / 3600.0, app.unit.convert(i.main.temp)))
+        .collect();
+    let (min, max) = points
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), (_, t)| (lo.min(*t), hi.max(*t)));
+    let (min, max) = (min.floor() - 1.0, max.ceil() + 1.0);
+    let hours = points.last().map_or(0.0, |p| p.0);
+    let timezone = forecast.city.timezone;
+
+    let x_labels = [
+        items[0],
+        items[items.len() / 2],
+        items[items.len() - 1],
+    ]
+    .map(|i| format_day_time(i.dt, timezone));
+
+    let dataset = Dataset::default()
+        .name(format!("Temperature ({})", app.unit))
+        .marker(Marker::Braille)
+        .graph_type(GraphType::Line)
+        .style(Style::default().fg(Color::Yellow))
+        .data(&points);
+    let chart = Chart::new(vec![dataset])
+        .x_axis(Axis::default().bounds([0.0, hours]).labels(x_labels).style(Style::default().fg(Color::Gray)))
+        .y_axis(
+            Axis::default()
+                .bounds([min, max])
+                .labels([format!("{:.0}", min), format!("{:.0}", (min + max) / 2.0), format!("{:.0}", max)])
+                .style(Style::default().fg(Color::Gray)),
+        );
+    frame.render_widget(chart, chart_area);
+
+    let chances: Vec<u64> = items.iter().map(|i| (i.pop * 100.0).round() as u64).collect();
+    let sparkline = Sparkline::default()
+        .block(Block::default().title("Chance of precipitation (0-100%)").dim())
+        .data(&chances)
+        .max(100)
+        .style(Style::default().fg(Color::Blue));
+    frame.render_widget(sparkline, rain_area);
+}
+
+fn draw_daily(frame: &mut Frame, app: &App, area: Rect) {
+    let block = Block::bordered().title(" Daily ");
+    let Some(forecast) = &app.selected().forecast else {
+        frame.render_widget(Paragraph::new("No forecast yet").dim().block(block), area);
+        return;
+    };
+
+    let unit: &TemperatureUnit = &app.unit;
+    let rows: Vec<Row> = forecast
+        .daily()
+        .iter()
+        .map(|day| {
+            Row::new(vec![
+                format!("{} {:>2}", day.date.weekday(), day.date.day()),
+                condition::emoji(day.condition_id, &day.icon).to_string(),
+                format!("{:>5.1}{}", unit.convert(day.min), unit),
+                format!("{:>5.1}{}", unit.convert(day.max), unit),
+                format!("{:>4.1} mm", day.precipitation),
+                format!("{:>3.0}%", day.pop * 100.0),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(6),
+            Constraint::Length(3),
+            Constraint::Length(8),
+            Constraint::Length(8),
+            Constraint::Length(8),
+            Constraint::Length(4),
+        ],
+    )
+    .header(Row::new(["Day", "", "Low", "High", "Precip", "Pop"]).bold())
+    .block(block);
+    frame.render_widget(table, area);
+}
+
+fn draw_alerts(frame: &mut Frame, app: &App, area: Rect) {
+    let block = Block::bordered().title(" Alerts (24h) ");
+    let lines: Vec<Line> = match &app.selected().forecast {
+        Some(forecast) => {
+            let alerts = forecast_alerts(forecast, &app.unit);
+            if alerts.is_empty() {
+                vec![Line::from("✅ Nothing notable").green()]
+            } else {
+                alerts.into_iter().map(|a| Line::from(a).red()).collect()
+            }
+        }
+        None => vec![Line::from("No forecast yet").dim()],
+    };
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: true }).block(block), area);
+}
+
+fn draw_footer(frame: &mut Frame, app: &App, area: Rect) {
+    let updated = match app.selected().updated {
+        Some(t) => format!("updated {}s ago", t.elapsed().as_secs()),
+        None => "loading".to_string(),
+    };
+    let footer = Line::from(vec![
+        Span::raw(" ↑↓/jk").bold(),
+        Span::raw(" select  "),
+        Span::raw("u").bold(),
+        Span::raw(format!(" unit ({})  ", app.unit)),
+        Span::raw("r").bold(),
+        Span::raw(" refresh  "),
+        Span::raw("q").bold(),
+        Span::raw(" quit  "),
+        Span::raw(format!("· {} · auto-refresh every {}", updated, humantime::format_duration(app.interval))).dim(),
+    ]);
+    frame.render_widget(Paragraph::new(footer), area);
+}
+
+fn placeholder(location: &LocationState) -> Paragraph<'static> {
+    match (&location.error, location.loading) {
+        (Some(error), _) => Paragraph::new(format!("❌ {}", error)).red().wrap(Wrap { trim: true }),
+        (None, true) => Paragraph::new("⏳ Fetching weather data...").dim(),
+        (None, false) => Paragraph::new("Press r to load").dim(),
+    }
+}
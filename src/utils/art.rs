@@ -0,0 +1,322 @@
+//! Multi-line weather icons drawn in ASCII, in the style of wttr.in, keyed
+//! by OWM condition code with day and night variants.
+//!
+//! Icons are stacks of layers: a sun or moon, a cloud, rain or snow. Each
+//! layer has one color and its spaces are transparent, so later layers are
+//! drawn over earlier ones.
+
+use colored::{Color, Colorize};
+
+/// Cells per line of every icon.
+pub const ART_WIDTH: usize = 13;
+/// Lines per icon.
+pub const ART_HEIGHT: usize = 5;
+
+struct Layer {
+    color: Option<Color>,
+    rows: [&'static str; ART_HEIGHT],
+}
+
+const fn layer(color: Option<Color>, rows: [&'static str; ART_HEIGHT]) -> Layer {
+    Layer { color, rows }
+}
+
+const SUN: Color = Color::BrightYellow;
+const MOON: Color = Color::Yellow;
+const DARK: Color = Color::BrightBlack;
+const WATER: Color = Color::BrightBlue;
+const ICE: Color = Color::BrightCyan;
+
+const SUN_DISC: Layer = layer(Some(SUN), [
+    r"    \   /    ",
+    r"     .-.     ",
+    r"  - (   ) -  ",
+    r"     `-'     ",
+    r"    /   \    ",
+]);
+
+const MOON_DISC: Layer = layer(Some(MOON), [
+    r"             ",
+    r"     .--.    ",
+    r"    ( (      ",
+    r"     `--'    ",
+    r"             ",
+]);
+
+const STARS: Layer = layer(Some(DARK), [
+    r"  *       *  ",
+    r"             ",
+    r"           * ",
+    r" *           ",
+    r"      *    * ",
+]);
+
+/// Sun peeking out from behind [`HIGH_CLOUD`].
+const SMALL_SUN: Layer = layer(Some(SUN), [
+    r"   \  /      ",
+    r#" _ /""       "#,
+    r"   \_        ",
+    r"   /         ",
+    r"             ",
+]);
+
+const SMALL_MOON: Layer = layer(Some(MOON), [
+    r"  .-.    *   ",
+    r" ( (         ",
+    r"  `-'        ",
+    r" *           ",
+    r"             ",
+]);
+
+const HIGH_CLOUD: Layer = layer(None, [
+    r"             ",
+    r"      .-.    ",
+    r"     (   ).  ",
+    r"    (___(__) ",
+    r"             ",
+]);
+
+const BIG_CLOUD: [&str; ART_HEIGHT] = [
+    r"             ",
+    r"     .--.    ",
+    r"  .-(    ).  ",
+    r" (___.__)__) ",
+    r"             ",
+];
+
+/// Cloud with room underneath for rain or snow.
+const RAIN_CLOUD: [&str; ART_HEIGHT] = [
+    r"     .-.     ",
+    r"    (   ).   ",
+    r"   (___(__)  ",
+    r"             ",
+    r"             ",
+];
+
+/// Sun and cloud raised a line to make room for [`SHOWER_DROPS`].
+const SHOWER_SUN: Layer = layer(Some(SUN), [
+    r#" _`/""       "#,
+    r"  ,\_        ",
+    r"   /         ",
+    r"             ",
+    r"             ",
+]);
+
+const SHOWER_MOON: Layer = layer(Some(MOON), [
+    r" *.-.        ",
+    r" ( (         ",
+    r"  `-         ",
+    r"             ",
+    r"             ",
+]);
+
+const SHOWER_CLOUD: Layer = layer(None, [
+    r"      .-.    ",
+    r"     (   ).  ",
+    r"    (___(__) ",
+    r"             ",
+    r"             ",
+]);
+
+const SHOWER_DROPS: Layer = layer(Some(WATER), [
+    r"             ",
+    r"             ",
+    r"             ",
+    r"     ' ' ' ' ",
+    r"    ' ' ' '  ",
+]);
+
+const LIGHT_DROPS: Layer = layer(Some(WATER), [
+    r"             ",
+    r"             ",
+    r"             ",
+    r"    ' ' ' '  ",
+    r"   ' ' ' '   ",
+]);
+
+const HEAVY_DROPS: Layer = layer(Some(WATER), [
+    r"             ",
+    r"             ",
+    r"             ",
+    r"   ,',',','  ",
+    r"   ,',',','  ",
+]);
+
+const STORM_DROPS: Layer = layer(Some(WATER), [
+    r"             ",
+    r"             ",
+    r"             ",
+    r"   '    '    ",
+    r"  '    '     ",
+]);
+
+const BOLTS: Layer = layer(Some(SUN), [
+    r"             ",
+    r"             ",
+    r"             ",
+    r"     _/   _/ ",
+    r"     /    /  ",
+]);
+
+const LIGHT_FLAKES: Layer = layer(Some(ICE), [
+    r"             ",
+    r"             ",
+    r"             ",
+    r"    *  *  *  ",
+    r"   *  *  *   ",
+]);
+
+const HEAVY_FLAKES: Layer = layer(Some(ICE), [
+    r"             ",
+    r"             ",
+    r"             ",
+    r"   * * * *   ",
+    r"  * * * *    ",
+]);
+
+const SLEET: Layer = layer(Some(ICE), [
+    r"             ",
+    r"             ",
+    r"             ",
+    r"    ' * ' *  ",
+    r"   * ' * '   ",
+]);
+
+const MIST: Layer = layer(Some(DARK), [
+    r"             ",
+    r" _ - _ - _ - ",
+    r"  _ - _ - _  ",
+    r" _ - _ - _ - ",
+    r"             ",
+]);
+
+const QUESTION: Layer = layer(None, [
+    r"    .-.      ",
+    r"     __)     ",
+    r"    (        ",
+    r"     `-'     ",
+    r"      *      ",
+]);
+
+/// A weather icon, [`ART_WIDTH`] cells by [`ART_HEIGHT`] lines.
+#[derive(Clone, Copy)]
+pub struct Icon(&'static [Layer]);
+
+impl std::fmt::Debug for Icon {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Icon").field(&self.plain_lines()).finish()
+    }
+}
+
+pub const SUNNY: Icon = Icon(&[SUN_DISC]);
+pub const CLEAR_NIGHT: Icon = Icon(&[STARS, MOON_DISC]);
+pub const PARTLY_CLOUDY: Icon = Icon(&[SMALL_SUN, HIGH_CLOUD]);
+pub const PARTLY_CLOUDY_NIGHT: Icon = Icon(&[SMALL_MOON, HIGH_CLOUD]);
+pub const CLOUDY: Icon = Icon(&[layer(None, BIG_CLOUD)]);
+pub const OVERCAST: Icon = Icon(&[layer(Some(DARK), BIG_CLOUD)]);
+pub const FOG: Icon = Icon(&[MIST]);
+pub const LIGHT_RAIN: Icon = Icon(&[layer(None, RAIN_CLOUD), LIGHT_DROPS]);
+pub const HEAVY_RAIN: Icon = Icon(&[layer(Some(DARK), RAIN_CLOUD), HEAVY_DROPS]);
+pub const SHOWERS: Icon = Icon(&[SHOWER_SUN, SHOWER_CLOUD, SHOWER_DROPS]);
+pub const SHOWERS_NIGHT: Icon = Icon(&[SHOWER_MOON, SHOWER_CLOUD, SHOWER_DROPS]);
+pub const THUNDERSTORM: Icon = Icon(&[layer(Some(DARK), RAIN_CLOUD), STORM_DROPS, BOLTS]);
+pub const LIGHT_SNOW: Icon = Icon(&[layer(None, RAIN_CLOUD), LIGHT_FLAKES]);
+pub const HEAVY_SNOW: Icon = Icon(&[layer(Some(DARK), RAIN_CLOUD), HEAVY_FLAKES]);
+pub const SLEET_ICON: Icon = Icon(&[layer(None, RAIN_CLOUD), SLEET]);
+pub const UNKNOWN: Icon = Icon(&[QUESTION]);
+
+/// The icon for an OWM condition code (`WeatherCondition.id`).
+///
+/// ```
+/// use weather_cl::utils::art::icon;
+///
+/// assert_eq!(icon(800, false).plain_lines()[2], "  - (   ) -  ");
+/// assert_eq!(icon(500, true).plain_lines(), icon(500, false).plain_lines());
+/// ```
+pub fn icon(id: u32, night: bool) -> Icon {
+    match (id, night) {
+        (200..=232, _) => THUNDERSTORM,
+        (300..=321 | 500 | 501, _) => LIGHT_RAIN,
+        (502..=504 | 522, _) => HEAVY_RAIN,
+        (511 | 611..=616, _) => SLEET_ICON,
+        (520 | 521 | 531, false) => SHOWERS,
+        (520 | 521 | 531, true) => SHOWERS_NIGHT,
+        (600 | 620, _) => LIGHT_SNOW,
+        (601 | 602 | 621 | 622, _) => HEAVY_SNOW,
+        (701..=762, _) => FOG,
+        (771 | 781, _) => OVERCAST,
+        (800, false) => SUNNY,
+        (800, true) => CLEAR_NIGHT,
+        (801 | 802, false) => PARTLY_CLOUDY,
+        (801 | 802, true) => PARTLY_CLOUDY_NIGHT,
+        (803, _) => CLOUDY,
+        (804, _) => OVERCAST,
+        _ => UNKNOWN,
+    }
+}
+
+impl Icon {
+    /// The lines with their colors.
+    pub fn lines(&self) -> Vec<String> {
+        (0..ART_HEIGHT)
+            .map(|row| {
+                let cells = self.cells(row);
+                let mut line = String::new();
+                let mut start = 0;
+                // One escape sequence per run of equally colored cells
+                for end in 1..=cells.len() {
+                    if end == cells.len() || cells[end].1 != cells[start].1 {
+                        let run: String = cells[start..end].iter().map(|(c, _)| c).collect();
+                        match cells[start].1 {
+                            Some(color) => line.push_str(&run.color(color).to_string()),
+                            None => line.push_str(&run),
+                        }
+                        start = end;
+                    }
+                }
+                line
+            })
+            .collect()
+    }
+
+    /// The lines without colors.
+    pub fn plain_lines(&self) -> Vec<String> {
+        (0..ART_HEIGHT).map(|row| self.cells(row).iter().map(|(c, _)| c).collect()).collect()
+    }
+
+    fn cells(&self, row: usize) -> Vec<(char, Option<Color>)> {
+        let mut cells = vec![(' ', None); ART_WIDTH];
+        for layer in self.0 {
+            for (cell, c) in cells.iter_mut().zip(layer.rows[row].chars()) {
+                if c != ' ' {
+                    *cell = (c, layer.color);
+                }
+            }
+        }
+        cells
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_icon_is_a_full_ascii_block() {
+        let ids = [200, 300, 500, 502, 511, 520, 600, 602, 611, 701, 771, 800, 801, 803, 804, 999];
+        for id in ids {
+            for night in [false, true] {
+                let icon = icon(id, night);
+                for layer in icon.0 {
+                    assert!(layer.rows.iter().all(|row| row.len() == ART_WIDTH && row.is_ascii()), "{}", id);
+                }
+                assert_eq!(icon.lines().len(), ART_HEIGHT);
+            }
+        }
+
+        assert_eq!(
+            PARTLY_CLOUDY.plain_lines(),
+            ["   \\  /      ", " _ /\"\".-.    ", "   \\_(   ).  ", "   /(___(__) ", "             "]
+        );
+    }
+}
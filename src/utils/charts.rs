@@ -0,0 +1,249 @@
+use crate::models::forecast::{ForecastItem, ForecastResponse};
+use crate::models::weather::TemperatureUnit;
+use chrono::{FixedOffset, TimeZone, Utc};
+use std::fmt::Write;
+
+const WIDTH: f64 = 720.0;
+const HEIGHT: f64 = 240.0;
+const MARGIN_LEFT: f64 = 48.0;
+const MARGIN_RIGHT: f64 = 16.0;
+const MARGIN_TOP: f64 = 28.0;
+const MARGIN_BOTTOM: f64 = 32.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChartMetric {
+    Temperature,
+    Precipitation,
+    Wind,
+}
+
+impl ChartMetric {
+    pub const ALL: [ChartMetric; 3] = [
+        ChartMetric::Temperature,
+        ChartMetric::Precipitation,
+        ChartMetric::Wind,
+    ];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "temperature" | "temp" => Some(ChartMetric::Temperature),
+            "precipitation" | "rain" => Some(ChartMetric::Precipitation),
+            "wind" => Some(ChartMetric::Wind),
+            _ => None,
+        }
+    }
+
+}
+
+/// Render one forecast chart as a standalone SVG document.
+pub fn render_chart(metric: ChartMetric, forecast: &ForecastResponse, unit: &TemperatureUnit) -> String {
+    let items = &forecast.list;
+    let mut svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" class="chart" width="100%" viewBox="0 0 {} {}" font-family="'Segoe UI', Tahoma, Geneva, Verdana, sans-serif" font-size="11">"#,
+        WIDTH, HEIGHT
+    );
+
+    if items.is_empty() {
+        let _ = write!(
+            svg,
+            r##"<text x="{}" y="{}" text-anchor="middle" style="fill: var(--muted, #7f8c8d)">No forecast data</text></svg>"##,
+            WIDTH / 2.0,
+            HEIGHT / 2.0
+        );
+        return svg;
+    }
+
+    match metric {
+        ChartMetric::Temperature => temperature_chart(&mut svg, items, forecast.city.timezone, unit),
+        ChartMetric::Precipitation => precipitation_chart(&mut svg, items, forecast.city.timezone),
+        ChartMetric::Wind => wind_chart(&mut svg, items, forecast.city.timezone),
+    }
+
+    svg.push_str("</svg>");
+    svg
+}
+
+fn temperature_chart(svg: &mut String, items: &[ForecastItem], timezone: i32, unit: &TemperatureUnit) {
+    let temps: Vec<f64> = items.iter().map(|i| unit.convert(i.main.temp)).collect();
+    let mins: Vec<f64> = items.iter().map(|i| unit.convert(i.main.temp_min)).collect();
+    let maxs: Vec<f64> = items.iter().map(|i| unit.convert(i.main.temp_max)).collect();
+
+    let low = mins.iter().chain(temps.iter()).cloned().fold(f64::INFINITY, f64::min).floor() - 1.0;
+    let high = maxs.iter().chain(temps.iter()).cloned().fold(f64::NEG_INFINITY, f64::max).ceil() + 1.0;
+    let scale = Scale::new(items.len(), low, high);
+
+    draw_frame(svg, &scale, items, timezone, &format!("Temperature ({})", unit), |v| format!("{:.0}°", v));
+
+    // Min/max band: along temp_max, then back along temp_min
+    let band: Vec<String> = maxs
+        .iter()
+        .enumerate()
+        .chain(mins.iter().enumerate().rev())
+        .map(|(i, v)| format!("{:.1},{:.1}", scale.x(i), scale.y(*v)))
+        .collect();
+    let _ = write!(
+        svg,
+        r##"<polygon points="{}" fill-opacity="0.2" stroke="none" style="fill: var(--accent, #667eea)"/>"##,
+        band.join(" ")
+    );
+
+    let _ = write!(
+        svg,
+        r##"<polyline points="{}" fill="none" stroke-width="2.5" style="stroke: var(--accent, #667eea)" stroke-linejoin="round"/>"##,
+        polyline(&scale, &temps)
+    );
+}
+
+fn precipitation_chart(svg: &mut String, items: &[ForecastItem], timezone: i32) {
+    let volumes: Vec<f64> = items.iter().map(|i| i.precipitation()).collect();
+    let high = volumes.iter().cloned().fold(1.0, f64::max).ceil();
+    let scale = Scale::new(items.len(), 0.0, high);
+
+    draw_frame(svg, &scale, items, timezone, "Precipitation (mm / 3h)", |v| format!("{:.1}", v));
+
+    let bar_width = scale.step() * 0.7;
+    for (i, (volume, item)) in volumes.iter().zip(items).enumerate() {
+        if *volume <= 0.0 {
+            continue;
+        }
+        let top = scale.y(*volume);
+        let _ = write!(
+            svg,
+            r##"<rect x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" fill="#3498db" fill-opacity="{:.2}"><title>{:.1} mm, {:.0}% chance</title></rect>"##,
+            scale.x(i) - bar_width / 2.0,
+            top,
+            bar_width,
+            scale.y(0.0) - top,
+            0.3 + 0.7 * item.pop.clamp(0.0, 1.0),
+            volume,
+            item.pop * 100.0
+        );
+    }
+}
+
+fn wind_chart(svg: &mut String, items: &[ForecastItem], timezone: i32) {
+    let speeds: Vec<f64> = items.iter().map(|i| i.wind.speed).collect();
+    let gusts: Vec<f64> = items.iter().map(|i| i.wind.gust.unwrap_or(i.wind.speed)).collect();
+    let high = gusts.iter().chain(speeds.iter()).cloned().fold(1.0, f64::max).ceil() + 1.0;
+    let scale = Scale::new(items.len(), 0.0, high);
+
+    draw_frame(svg, &scale, items, timezone, "Wind (m/s)", |v| format!("{:.0}", v));
+
+    if items.iter().any(|i| i.wind.gust.is_some()) {
+        let _ = write!(
+            svg,
+            r##"<polyline points="{}" fill="none" stroke="#27ae60" stroke-width="1.5" stroke-dasharray="4 3" stroke-opacity="0.7"/>"##,
+            polyline(&scale, &gusts)
+        );
+    }
+
+    let _ = write!(
+        svg,
+        r##"<polyline points="{}" fill="none" stroke="#27ae60" stroke-width="2.5" stroke-linejoin="round"/>"##,
+        polyline(&scale, &speeds)
+    );
+
+    // Arrows point the way the wind blows, i.e. away from `deg`
+    let every = (items.len() / 20).max(1);
+    for (i, item) in items.iter().enumerate().step_by(every) {
+        let _ = write!(
+            svg,
+            r##"<path d="M0,-7 L4,5 L0,2 L-4,5 Z" style="fill: var(--text, #2c3e50)" transform="translate({:.1},{:.1}) rotate({})"/>"##,
+            scale.x(i),
+            scale.y(item.wind.speed) - 12.0,
+            (item.wind.deg + 180) % 360
+        );
+    }
+}
+
+struct Scale {
+    count: usize,
+    low: f64,
+    high: f64,
+}
+
+impl Scale {
+    fn new(count: usize, low: f64, high: f64) -> Self {
+        // Stretch the range to a multiple of 4 so the grid lines land on whole values
+        let span = (high - low).max(1.0);
+        let high = if span >= 4.0 { low + (span / 4.0).ceil() * 4.0 } else { low + span };
+        Self { count, low, high }
+    }
+
+    fn step(&self) -> f64 {
+        (WIDTH - MARGIN_LEFT - MARGIN_RIGHT) / self.count.max(1) as f64
+    }
+
+    fn x(&self, index: usize) -> f64 {
+        MARGIN_LEFT + self.step() * (index as f64 + 0.5)
+    }
+
+    fn y(&self, value: f64) -> f64 {
+        let plot_height = HEIGHT - MARGIN_TOP - MARGIN_BOTTOM;
+        HEIGHT - MARGIN_BOTTOM - (value - self.low) / (self.high - self.low) * plot_height
+    }
+}
+
+fn polyline(scale: &Scale, values: &[f64]) -> String {
+    values
+        .iter()
+        .enumerate()
+        .map(|(i, v)| format!("{:.1},{:.1}", scale.x(i), scale.y(*v)))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn draw_frame(
+    svg: &mut String,
+    scale: &Scale,
+    items: &[ForecastItem],
+    timezone: i32,
+    title: &str,
+    label: impl Fn(f64) -> String,
+) {
+    let _ = write!(
+        svg,
+        r##"<text x="{}" y="16" font-size="13" font-weight="bold" style="fill: var(--text, #2c3e50)">{}</text>"##,
+        MARGIN_LEFT, title
+    );
+
+    // Horizontal grid with value labels
+    for tick in 0..=4 {
+        let value = scale.low + (scale.high - scale.low) * tick as f64 / 4.0;
+        let y = scale.y(value);
+        let _ = write!(
+            svg,
+            r##"<line x1="{}" y1="{:.1}" x2="{}" y2="{:.1}" style="stroke: var(--border, #ecf0f1)"/><text x="{}" y="{:.1}" text-anchor="end" style="fill: var(--muted, #7f8c8d)">{}</text>"##,
+            MARGIN_LEFT,
+            y,
+            WIDTH - MARGIN_RIGHT,
+            y,
+            MARGIN_LEFT - 6.0,
+            y + 4.0,
+            label(value)
+        );
+    }
+
+    // Day separators in the location's local time
+    let offset = FixedOffset::east_opt(timezone).unwrap_or(FixedOffset::east_opt(0).unwrap());
+    let mut previous_day = None;
+    for (i, item) in items.iter().enumerate() {
+        let local = Utc.timestamp_opt(item.dt as i64, 0).unwrap().with_timezone(&offset);
+        let day = local.format("%a").to_string();
+        if previous_day.as_ref() != Some(&day) {
+            let x = scale.x(i) - scale.step() / 2.0;
+            let _ = write!(
+                svg,
+                r##"<line x1="{:.1}" y1="{}" x2="{:.1}" y2="{}" style="stroke: var(--border, #dfe4ea)"/><text x="{:.1}" y="{}" style="fill: var(--muted, #7f8c8d)">{}</text>"##,
+                x,
+                MARGIN_TOP,
+                x,
+                HEIGHT - MARGIN_BOTTOM,
+                x + 4.0,
+                HEIGHT - MARGIN_BOTTOM + 16.0,
+                day
+            );
+            previous_day = Some(day);
+        }
+    }
+}
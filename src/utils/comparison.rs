@@ -0,0 +1,149 @@
+use crate::models::weather::{TemperatureUnit, WeatherResponse};
+use crate::utils::condition;
+use crate::utils::formatters::*;
+use crate::utils::glyphs::Charset;
+use crate::utils::layout::{pad, pad_start, rule, width};
+use anyhow::Error;
+use colored::*;
+
+/// Comfortable reference points used to rank temperatures and humidity.
+const IDEAL_TEMP_C: f64 = 21.0;
+const IDEAL_HUMIDITY: f64 = 45.0;
+
+/// A metric column: how to read it and how "good" a value is (lower is better).
+struct Column {
+    title: &'static str,
+    value: fn(&WeatherResponse) -> f64,
+    badness: fn(f64) -> f64,
+    format: fn(&WeatherResponse, &TemperatureUnit) -> String,
+}
+
+const COLUMNS: [Column; 4] = [
+    Column {
+        title: "Temp",
+        value: |w| w.main.temp - 273.15,
+        badness: |c| (c - IDEAL_TEMP_C).abs(),
+        format: |w, unit| w.format_temperature(unit),
+    },
+    Column {
+        title: "Feels like",
+        value: |w| w.main.feels_like - 273.15,
+        badness: |c| (c - IDEAL_TEMP_C).abs(),
+        format: |w, unit| format!("{:.1}{}", unit.convert(w.main.feels_like), unit),
+    },
+    Column {
+        title: "Humidity",
+        value: |w| w.main.humidity as f64,
+        badness: |h| (h - IDEAL_HUMIDITY).abs(),
+        format: |w, _| format_humidity(w.main.humidity),
+    },
+    Column {
+        title: "Wind",
+        value: |w| w.wind.speed,
+        badness: |s| s,
+        format: |w, _| format!("{:.1} m/s {}", w.wind.speed, format_wind_direction(w.wind.deg)),
+    },
+];
+
+/// Side-by-side table of several locations. The best value in each column
+/// is green and the worst red; failed lookups are listed underneath. Drawn
+/// in the process-wide [`Charset::current`].
+pub fn comparison_table(
+    results: &[(String, Result<WeatherResponse, Error>)],
+    unit: &TemperatureUnit,
+) -> String {
+    let rows: Vec<(&str, &WeatherResponse)> = results
+        .iter()
+        .filter_map(|(query, r)| r.as_ref().ok().map(|w| (query.as_str(), w)))
+        .collect();
+
+    let charset = Charset::current();
+    let names: Vec<String> = rows.iter().map(|(_, w)| location_name(w)).collect();
+    let cells: Vec<Vec<String>> = rows
+        .iter()
+        .map(|(_, w)| COLUMNS.iter().map(|c| charset.text(&(c.format)(w, unit)).into_owned()).collect())
+        .collect();
+
+    let name_width = names.iter().map(|n| width(n)).chain([4]).max().unwrap_or(4);
+    let widths: Vec<usize> = COLUMNS
+        .iter()
+        .enumerate()
+        .map(|(i, c)| {
+            cells.iter().map(|r| width(&r[i])).chain([c.title.len()]).max().unwrap_or(0)
+        })
+        .collect();
+
+    let mut out = String::new();
+    if rows.is_empty() {
+        return failures(results, out);
+    }
+
+    let mut header = pad("City", name_width);
+    for (column, width) in COLUMNS.iter().zip(&widths) {
+        header.push_str(&format!("  {}", pad_start(column.title, *width)));
+    }
+    header.push_str("  Conditions");
+    out.push_str(&format!("{}\n", header.bold()));
+    let line = if charset.is_ascii() { '-' } else { '─' };
+    out.push_str(&format!("{}\n", rule(line, width(&header)).dimmed()));
+
+    let ranks: Vec<(Option<usize>, Option<usize>)> = COLUMNS
+        .iter()
+        .map(|c| best_and_worst(&rows.iter().map(|(_, w)| (c.badness)((c.value)(w))).collect::<Vec<_>>()))
+        .collect();
+
+    for (row, ((_, weather), name)) in rows.iter().zip(&names).enumerate() {
+        out.push_str(&format!("{}", pad(name, name_width).cyan().bold()));
+        for (i, width) in widths.iter().enumerate() {
+            let cell = pad_start(&cells[row][i], *width);
+            let cell = match ranks[i] {
+                (Some(best), _) if best == row => cell.green().bold(),
+                (_, Some(worst)) if worst == row => cell.red(),
+                _ => cell.normal(),
+            };
+            out.push_str(&format!("  {}", cell));
+        }
+        let condition = weather.weather.first();
+        let icon = match charset {
+            Charset::Unicode => condition.map_or("🌈", |c| condition::emoji(c.id, &c.icon)),
+            Charset::Ascii => condition.map_or("[?]", |c| condition::ascii(c.id, &c.icon)),
+        };
+        out.push_str(&format!("  {} {}\n", icon, weather.description()));
+    }
+
+    failures(results, out)
+}
+
+fn failures(results: &[(String, Result<WeatherResponse, Error>)], mut out: String) -> String {
+    let mark = Charset::current().pick("❌", "x");
+    for (query, result) in results {
+        if let Err(e) = result {
+            out.push_str(&format!("{} {}: {}\n", mark.red(), query.bold(), e));
+        }
+    }
+    out
+}
+
+/// Indices of the lowest and highest badness, or `None` when every value
+/// is the same (nothing to highlight).
+fn best_and_worst(badness: &[f64]) -> (Option<usize>, Option<usize>) {
+    if badness.len() < 2 {
+        return (None, None);
+    }
+    let order = |a: &(usize, &f64), b: &(usize, &f64)| a.1.total_cmp(b.1);
+    let best = badness.iter().enumerate().min_by(order).map(|(i, _)| i);
+    let worst = badness.iter().enumerate().max_by(order).map(|(i, _)| i);
+    match (best, worst) {
+        (Some(b), Some(w)) if badness[b] == badness[w] => (None, None),
+        other => other,
+    }
+}
+
+fn location_name(weather: &WeatherResponse) -> String {
+    if !weather.sys.country.is_empty() {
+        format!("{}, {}", weather.name, weather.sys.country)
+    } else {
+        weather.name.clone()
+    }
+}
+
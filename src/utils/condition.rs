@@ -0,0 +1,312 @@
+//! What an OpenWeatherMap condition code (`WeatherCondition.id`) means:
+//! its group, intensity and kind of precipitation, with an emoji, an ASCII
+//! label, art and a description to show for it.
+//!
+//! The `icon` the API sends alongside only distinguishes nine pictures, so
+//! a light drizzle and a tornado can look alike; the id does not.
+
+use crate::utils::art::{self, Icon};
+use crate::utils::formatters::{get_weather_ascii, get_weather_emoji};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Broad condition groups derived from OpenWeatherMap condition ids.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConditionGroup {
+    Thunderstorm,
+    Drizzle,
+    Rain,
+    Snow,
+    Mist,
+    Clear,
+    Clouds,
+}
+
+impl ConditionGroup {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "thunderstorm" | "storm" => Some(ConditionGroup::Thunderstorm),
+            "drizzle" => Some(ConditionGroup::Drizzle),
+            "rain" => Some(ConditionGroup::Rain),
+            "snow" => Some(ConditionGroup::Snow),
+            "mist" | "fog" | "atmosphere" => Some(ConditionGroup::Mist),
+            "clear" => Some(ConditionGroup::Clear),
+            "clouds" | "cloudy" => Some(ConditionGroup::Clouds),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ConditionGroup::Thunderstorm => "thunderstorm",
+            ConditionGroup::Drizzle => "drizzle",
+            ConditionGroup::Rain => "rain",
+            ConditionGroup::Snow => "snow",
+            ConditionGroup::Mist => "mist",
+            ConditionGroup::Clear => "clear",
+            ConditionGroup::Clouds => "clouds",
+        }
+    }
+
+    pub fn of(id: u32) -> Option<Self> {
+        match id {
+            200..=299 => Some(ConditionGroup::Thunderstorm),
+            300..=399 => Some(ConditionGroup::Drizzle),
+            500..=599 => Some(ConditionGroup::Rain),
+            600..=699 => Some(ConditionGroup::Snow),
+            700..=799 => Some(ConditionGroup::Mist),
+            800 => Some(ConditionGroup::Clear),
+            801..=899 => Some(ConditionGroup::Clouds),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for ConditionGroup {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_name(s).ok_or_else(|| {
+            format!("Unknown condition '{}'. Use thunderstorm, drizzle, rain, snow, mist, clear or clouds", s)
+        })
+    }
+}
+
+/// How strong the precipitation is or, without any, the storm or wind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Intensity {
+    Light,
+    Moderate,
+    Heavy,
+    Extreme,
+}
+
+/// What falls from the sky.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Precipitation {
+    Drizzle,
+    Rain,
+    /// Rain freezing on contact with the ground
+    FreezingRain,
+    /// Sleet, or rain and snow together
+    Sleet,
+    Snow,
+}
+
+/// One OpenWeatherMap condition code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Condition {
+    pub id: u32,
+    pub group: ConditionGroup,
+    pub intensity: Option<Intensity>,
+    pub precipitation: Option<Precipitation>,
+    /// Falls in short bursts rather than steadily
+    pub showers: bool,
+    /// Longer than the API's description, e.g. "Fog, visibility under 1 km"
+    pub text: &'static str,
+}
+
+use Intensity::*;
+use Precipitation::*;
+
+/// Id, intensity, precipitation, showers and text.
+type Entry = (u32, Option<Intensity>, Option<Precipitation>, bool, &'static str);
+
+/// Every code OpenWeatherMap documents.
+const CONDITIONS: &[Entry] = &[
+    (200, Some(Light), Some(Rain), false, "Thunderstorm with light rain"),
+    (201, Some(Moderate), Some(Rain), false, "Thunderstorm with rain"),
+    (202, Some(Heavy), Some(Rain), false, "Thunderstorm with heavy rain"),
+    (210, Some(Light), None, false, "Light thunderstorm"),
+    (211, Some(Moderate), None, false, "Thunderstorm"),
+    (212, Some(Heavy), None, false, "Heavy thunderstorm"),
+    (221, Some(Moderate), None, false, "Ragged thunderstorm, on and off"),
+    (230, Some(Light), Some(Drizzle), false, "Thunderstorm with light drizzle"),
+    (231, Some(Moderate), Some(Drizzle), false, "Thunderstorm with drizzle"),
+    (232, Some(Heavy), Some(Drizzle), false, "Thunderstorm with heavy drizzle"),
+    (300, Some(Light), Some(Drizzle), false, "Light drizzle"),
+    (301, Some(Moderate), Some(Drizzle), false, "Drizzle"),
+    (302, Some(Heavy), Some(Drizzle), false, "Heavy drizzle"),
+    (310, Some(Light), Some(Drizzle), false, "Light drizzle and rain"),
+    (311, Some(Moderate), Some(Drizzle), false, "Drizzle and rain"),
+    (312, Some(Heavy), Some(Drizzle), false, "Heavy drizzle and rain"),
+    (313, Some(Moderate), Some(Drizzle), true, "Rain showers and drizzle"),
+    (314, Some(Heavy), Some(Drizzle), true, "Heavy rain showers and drizzle"),
+    (321, Some(Moderate), Some(Drizzle), true, "Drizzle showers"),
+    (500, Some(Light), Some(Rain), false, "Light rain, under 2.5 mm an hour"),
+    (501, Some(Moderate), Some(Rain), false, "Moderate rain, 2.5–7.6 mm an hour"),
+    (502, Some(Heavy), Some(Rain), false, "Heavy rain, 7.6–50 mm an hour"),
+    (503, Some(Extreme), Some(Rain), false, "Very heavy rain, over 50 mm an hour"),
+    (504, Some(Extreme), Some(Rain), false, "Extreme rain"),
+    (511, Some(Moderate), Some(FreezingRain), false, "Freezing rain, icy surfaces"),
+    (520, Some(Light), Some(Rain), true, "Light rain showers"),
+    (521, Some(Moderate), Some(Rain), true, "Rain showers"),
+    (522, Some(Heavy), Some(Rain), true, "Heavy rain showers"),
+    (531, Some(Moderate), Some(Rain), true, "Ragged rain showers, on and off"),
+    (600, Some(Light), Some(Snow), false, "Light snow"),
+    (601, Some(Moderate), Some(Snow), false, "Snow"),
+    (602, Some(Heavy), Some(Snow), false, "Heavy snow"),
+    (611, Some(Moderate), Some(Sleet), false, "Sleet"),
+    (612, Some(Light), Some(Sleet), true, "Light sleet showers"),
+    (613, Some(Moderate), Some(Sleet), true, "Sleet showers"),
+    (615, Some(Light), Some(Sleet), false, "Light rain and snow"),
+    (616, Some(Moderate), Some(Sleet), false, "Rain and snow"),
+    (620, Some(Light), Some(Snow), true, "Light snow showers"),
+    (621, Some(Moderate), Some(Snow), true, "Snow showers"),
+    (622, Some(Heavy), Some(Snow), true, "Heavy snow showers"),
+    (701, None, None, false, "Mist, visibility reduced"),
+    (711, None, None, false, "Smoke"),
+    (721, None, None, false, "Haze"),
+    (731, None, None, false, "Sand and dust whirls"),
+    (741, None, None, false, "Fog, visibility under 1 km"),
+    (751, None, None, false, "Sand"),
+    (761, None, None, false, "Dust"),
+    (762, None, None, false, "Volcanic ash"),
+    (771, Some(Heavy), None, false, "Squalls, sudden strong winds"),
+    (781, Some(Extreme), None, false, "Tornado"),
+    (800, None, None, false, "Clear sky"),
+    (801, None, None, false, "Few clouds, 11–25% cover"),
+    (802, None, None, false, "Scattered clouds, 25–50% cover"),
+    (803, None, None, false, "Broken clouds, 51–84% cover"),
+    (804, None, None, false, "Overcast, 85–100% cover"),
+];
+
+impl Condition {
+    /// The condition for `id`, `None` for codes OpenWeatherMap does not
+    /// document.
+    ///
+    /// ```
+    /// use weather_cl::utils::condition::{Condition, Intensity, Precipitation};
+    ///
+    /// let showers = Condition::of(522).unwrap();
+    /// assert_eq!(showers.intensity, Some(Intensity::Heavy));
+    /// assert_eq!(showers.precipitation, Some(Precipitation::Rain));
+    /// assert!(showers.showers);
+    /// assert!(Condition::of(999).is_none());
+    /// ```
+    pub fn of(id: u32) -> Option<Self> {
+        let (id, intensity, precipitation, showers, text) = *CONDITIONS.iter().find(|c| c.0 == id)?;
+        Some(Self { id, group: ConditionGroup::of(id)?, intensity, precipitation, showers, text })
+    }
+
+    pub fn emoji(&self, night: bool) -> &'static str {
+        match self.group {
+            ConditionGroup::Thunderstorm if self.precipitation.is_some() => "⛈️",
+            ConditionGroup::Thunderstorm => "🌩️",
+            ConditionGroup::Drizzle | ConditionGroup::Rain => match self.precipitation {
+                Some(FreezingRain) => "🧊",
+                _ if night => "🌧️",
+                _ if self.showers || self.intensity == Some(Light) => "🌦️",
+                _ => "🌧️",
+            },
+            ConditionGroup::Snow => match (self.precipitation, self.intensity) {
+                (Some(Snow), Some(Heavy | Extreme)) => "❄️",
+                _ => "🌨️",
+            },
+            ConditionGroup::Mist => match self.id {
+                731 | 781 => "🌪️",
+                751 | 761 => "🏜️",
+                762 => "🌋",
+                771 => "💨",
+                _ => "🌫️",
+            },
+            ConditionGroup::Clear if night => "🌙",
+            ConditionGroup::Clear => "☀️",
+            ConditionGroup::Clouds => match (self.id, night) {
+                (801, false) => "🌤️",
+                (801 | 802, _) => "⛅",
+                (803, false) => "🌥️",
+                _ => "☁️",
+            },
+        }
+    }
+
+    /// Plain-ASCII counterpart of [`emoji`](Self::emoji).
+    pub fn ascii(&self, night: bool) -> &'static str {
+        match self.group {
+            ConditionGroup::Thunderstorm => "[storm]",
+            ConditionGroup::Drizzle => "[drizzle]",
+            ConditionGroup::Rain => match (self.precipitation, self.intensity) {
+                (Some(FreezingRain), _) => "[freezing rain]",
+                _ if self.showers => "[showers]",
+                (_, Some(Heavy | Extreme)) => "[heavy rain]",
+                _ => "[rain]",
+            },
+            ConditionGroup::Snow => match (self.precipitation, self.intensity) {
+                (Some(Sleet), _) => "[sleet]",
+                (_, Some(Heavy | Extreme)) => "[heavy snow]",
+                _ => "[snow]",
+            },
+            ConditionGroup::Mist => match self.id {
+                711 => "[smoke]",
+                721 => "[haze]",
+                731 | 751 | 761 => "[dust]",
+                741 => "[fog]",
+                762 => "[ash]",
+                771 => "[squalls]",
+                781 => "[tornado]",
+                _ => "[mist]",
+            },
+            ConditionGroup::Clear if night => "[moon]",
+            ConditionGroup::Clear => "[sun]",
+            ConditionGroup::Clouds => match self.id {
+                801 | 802 => "[partly cloudy]",
+                803 => "[cloudy]",
+                _ => "[overcast]",
+            },
+        }
+    }
+
+    pub fn art(&self, night: bool) -> Icon {
+        art::icon(self.id, night)
+    }
+}
+
+/// Whether an API `icon` such as "01n" is the night variant.
+pub fn is_night(icon: &str) -> bool {
+    icon.ends_with('n')
+}
+
+/// Emoji for a reported condition: by id, or by icon for codes outside
+/// the table.
+///
+/// ```
+/// use weather_cl::utils::condition::emoji;
+///
+/// assert_eq!(emoji(211, "11d"), "🌩️");
+/// assert_eq!(emoji(201, "11d"), "⛈️");
+/// assert_eq!(emoji(999, "13n"), "❄️");
+/// ```
+pub fn emoji(id: u32, icon: &str) -> &'static str {
+    Condition::of(id).map_or_else(|| get_weather_emoji(icon), |c| c.emoji(is_night(icon)))
+}
+
+/// Plain-ASCII counterpart of [`emoji`].
+pub fn ascii(id: u32, icon: &str) -> &'static str {
+    Condition::of(id).map_or_else(|| get_weather_ascii(icon), |c| c.ascii(is_night(icon)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_documented_code() {
+        assert_eq!(CONDITIONS.len(), 55);
+        for (id, _, precipitation, _, text) in CONDITIONS {
+            let condition = Condition::of(*id).unwrap();
+            assert!(!text.is_empty());
+            for night in [false, true] {
+                assert_ne!(condition.emoji(night), "🌈", "{}", id);
+                assert!(condition.ascii(night).is_ascii());
+            }
+            let wet = matches!(condition.group, ConditionGroup::Drizzle | ConditionGroup::Rain | ConditionGroup::Snow);
+            assert!(!wet || precipitation.is_some(), "{}", id);
+        }
+
+        assert_eq!(emoji(800, "01n"), "🌙");
+        assert_eq!(ascii(511, "13d"), "[freezing rain]");
+        assert_eq!(ascii(999, "50d"), "[fog]");
+    }
+}
@@ -1,22 +1,159 @@
-use crate::models::weather::{WeatherResponse, TemperatureUnit};
+use crate::models::forecast::ForecastResponse;
+use crate::models::weather::{WeatherCondition, WeatherResponse, TemperatureUnit};
+use crate::utils::art::{self, ART_WIDTH};
+use crate::utils::condition::{self, is_night, Condition};
 use crate::utils::formatters::*;
+use crate::utils::glyphs::{to_ascii, Charset};
+use crate::utils::layout::{box_width, fit_with, pad, rule, BoxChars, Frame, ASCII, ASCII_DOUBLE, DOUBLE, LIGHT};
+use crate::utils::term_charts::{ascii_sparkline, line_chart, sparkline, ChartStyle, Trend};
 use colored::*;
 use console::Term;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use anyhow::Result;
+use std::io::{self, Write};
+
+/// Columns assumed when the output is not a terminal, and the widest the
+/// default template gets.
+pub const DEFAULT_WIDTH: usize = 80;
+
+/// Widest box of the compact and detailed templates.
+const COMPACT_WIDTH: usize = 50;
+const DETAILED_WIDTH: usize = 62;
 
 #[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
 pub enum DisplayTemplate {
     Default,
     Compact,
     Detailed,
     Minimal,
+    Art,
+}
+
+impl std::str::FromStr for DisplayTemplate {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "default" => Ok(DisplayTemplate::Default),
+            "compact" => Ok(DisplayTemplate::Compact),
+            "detailed" => Ok(DisplayTemplate::Detailed),
+            "minimal" => Ok(DisplayTemplate::Minimal),
+            "art" => Ok(DisplayTemplate::Art),
+            _ => Err(format!("Unknown display template: {}", s)),
+        }
+    }
+}
+
+/// A terminal layout for one observation. Every [`DisplayTemplate`] is one;
+/// implement it to draw your own layout from a [`WeatherDisplay`].
+pub trait Renderer {
+    fn render(&self, view: &WeatherDisplay<'_>, out: &mut Canvas<'_>) -> io::Result<()>;
+}
+
+impl Renderer for DisplayTemplate {
+    fn render(&self, view: &WeatherDisplay<'_>, out: &mut Canvas<'_>) -> io::Result<()> {
+        match self {
+            DisplayTemplate::Default => view.display_default(out),
+            DisplayTemplate::Compact => view.display_compact(out),
+            DisplayTemplate::Detailed => view.display_detailed(out),
+            DisplayTemplate::Minimal => view.display_minimal(out),
+            DisplayTemplate::Art => view.display_art(out),
+        }
+    }
+}
+
+/// Where templates write: any writer plus what the far end can show.
+/// Without color, ANSI escape sequences are dropped on the way through, so
+/// templates can style text unconditionally. With the ASCII charset, degree
+/// signs, dashes and ellipses are spelled in ASCII on the way through too;
+/// emoji and drawing characters are up to the template.
+pub struct Canvas<'w> {
+    out: &'w mut dyn Write,
+    color: bool,
+    width: usize,
+    charset: Charset,
+    escape: Escape,
+}
+
+/// Progress through an escape sequence, which may span several writes.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Escape {
+    None,
+    Start,
+    Csi,
+}
+
+impl<'w> Canvas<'w> {
+    /// `width` is the number of columns charts may use.
+    pub fn new(out: &'w mut dyn Write, color: bool, width: usize) -> Self {
+        Self { out, color, width, charset: Charset::Unicode, escape: Escape::None }
+    }
+
+    pub fn with_charset(mut self, charset: Charset) -> Self {
+        self.charset = charset;
+        self
+    }
+
+    pub fn color(&self) -> bool {
+        self.color
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn charset(&self) -> Charset {
+        self.charset
+    }
+}
+
+impl Write for Canvas<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // Formatted text arrives a whole `str` at a time
+        let ascii = match self.charset {
+            Charset::Ascii => std::str::from_utf8(buf).ok().map(to_ascii),
+            Charset::Unicode => None,
+        };
+        let text = ascii.as_ref().map_or(buf, |text| text.as_bytes());
+
+        if self.color {
+            self.out.write_all(text)?;
+            return Ok(buf.len());
+        }
+
+        let mut plain = Vec::with_capacity(text.len());
+        for &byte in text {
+            self.escape = match (self.escape, byte) {
+                (Escape::None, 0x1b) => Escape::Start,
+                (Escape::None, _) => {
+                    plain.push(byte);
+                    Escape::None
+                }
+                (Escape::Start, b'[') => Escape::Csi,
+                (Escape::Start, _) => Escape::None,
+                // Parameters and intermediates until the final byte
+                (Escape::Csi, 0x40..=0x7e) => Escape::None,
+                (Escape::Csi, _) => Escape::Csi,
+            };
+        }
+        self.out.write_all(&plain)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.out.flush()
+    }
 }
 
 pub struct WeatherDisplay<'a> {
     weather: &'a WeatherResponse,
     unit: TemperatureUnit,
     template: DisplayTemplate,
+    trend: Option<&'a Trend>,
+    forecast: Option<&'a ForecastResponse>,
+    updated: DateTime<Utc>,
+    charset: Charset,
 }
 
 impl<'a> WeatherDisplay<'a> {
@@ -25,6 +162,10 @@ impl<'a> WeatherDisplay<'a> {
             weather, 
             unit,
             template: DisplayTemplate::Default,
+            trend: None,
+            forecast: None,
+            updated: Utc::now(),
+            charset: Charset::current(),
         }
     }
 
@@ -33,110 +174,303 @@ impl<'a> WeatherDisplay<'a> {
         self
     }
 
+    /// Forecast or history shown as sparklines (compact) or charts (detailed).
+    pub fn with_trend(mut self, trend: Option<&'a Trend>) -> Self {
+        self.trend = trend.filter(|t| t.is_plottable());
+        self
+    }
+
+    /// Days shown as art blocks under the art template.
+    pub fn with_forecast(mut self, forecast: Option<&'a ForecastResponse>) -> Self {
+        self.forecast = forecast;
+        self
+    }
+
+    /// Time shown in the footers instead of now.
+    pub fn with_updated(mut self, updated: DateTime<Utc>) -> Self {
+        self.updated = updated;
+        self
+    }
+
+    /// Characters to draw with instead of the process-wide
+    /// [`Charset::current`].
+    pub fn with_charset(mut self, charset: Charset) -> Self {
+        self.charset = charset;
+        self
+    }
+
+    pub fn weather(&self) -> &WeatherResponse {
+        self.weather
+    }
+
+    pub fn unit(&self) -> TemperatureUnit {
+        self.unit
+    }
+
+    pub fn trend(&self) -> Option<&Trend> {
+        self.trend
+    }
+
+    pub fn forecast(&self) -> Option<&ForecastResponse> {
+        self.forecast
+    }
+
+    pub fn updated(&self) -> DateTime<Utc> {
+        self.updated
+    }
+
+    pub fn charset(&self) -> Charset {
+        self.charset
+    }
+
+    /// Draw the template on stdout. On a terminal the screen is cleared
+    /// first; piped output gets neither the clear nor colors, and neither
+    /// does a terminal with colors turned off through `colored::control`.
     pub fn display(&self) -> Result<()> {
         let term = Term::stdout();
-        let _ = term.clear_screen();
-
-        match self.template {
-            DisplayTemplate::Default => self.display_default(),
-            DisplayTemplate::Compact => self.display_compact(),
-            DisplayTemplate::Detailed => self.display_detailed(),
-            DisplayTemplate::Minimal => self.display_minimal(),
-        }
+        let color = term.is_term() && colored::control::SHOULD_COLORIZE.should_colorize();
+        let width = if term.is_term() {
+            if color {
+                let _ = term.clear_screen();
+            }
+            term.size().1 as usize
+        } else {
+            DEFAULT_WIDTH
+        };
 
+        let mut stdout = io::stdout().lock();
+        self.render_to(&mut stdout, color, width)?;
+        stdout.flush()?;
         Ok(())
     }
 
+    /// Draw the template into any writer, `width` columns wide.
+    ///
+    /// ```
+    /// use weather_cl::utils::display_templates::{DisplayTemplate, WeatherDisplay};
+    /// use weather_cl::{TemperatureUnit, WeatherResponse};
+    ///
+    /// # let weather: WeatherResponse = serde_json::from_str(r#"{"coord": {"lon": 13.4, "lat": 52.5},
+    /// #     "weather": [{"id": 800, "main": "Clear", "description": "clear sky", "icon": "01d"}],
+    /// #     "main": {"temp": 293.15, "feels_like": 293.15, "temp_min": 292.0, "temp_max": 294.0,
+    /// #              "pressure": 1012, "humidity": 40},
+    /// #     "wind": {"speed": 2.0, "deg": 90}, "clouds": {"all": 0},
+    /// #     "sys": {"country": "DE", "sunrise": 0, "sunset": 0},
+    /// #     "name": "Berlin", "visibility": null, "timezone": 7200}"#)?;
+    /// let mut out = Vec::new();
+    /// WeatherDisplay::new(&weather, TemperatureUnit::Celsius)
+    ///     .with_template(DisplayTemplate::Minimal)
+    ///     .render_to(&mut out, false, 80)?;
+    ///
+    /// let text = String::from_utf8(out)?;
+    /// assert!(text.contains("clear sky 20.0°C in Berlin"));
+    /// assert!(!text.contains('\x1b'));
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn render_to(&self, out: &mut dyn Write, color: bool, width: usize) -> io::Result<()> {
+        self.render_with(&self.template, out, color, width)
+    }
+
+    /// Like [`render_to`](Self::render_to) with a layout of your own.
+    pub fn render_with(&self, renderer: &dyn Renderer, out: &mut dyn Write, color: bool, width: usize) -> io::Result<()> {
+        renderer.render(self, &mut Canvas::new(out, color, width).with_charset(self.charset))
+    }
+
     // MINIMAL TEMPLATE - Single line summary
-    fn display_minimal(&self) {
-        let weather = &self.weather.weather[0];
+    fn display_minimal(&self, out: &mut Canvas<'_>) -> io::Result<()> {
         let temp_c = self.weather.main.temp - 273.15;
-        let emoji = get_weather_emoji(&weather.icon);
+        let emoji = condition_icon(out.charset(), self.weather.weather.first());
+        let feeling = match out.charset() {
+            Charset::Unicode => format_temperature_feeling(temp_c),
+            Charset::Ascii => temperature_feeling(temp_c).1.to_string(),
+        };
         
-        println!("\n{} {} {} in {} | {}", 
+        writeln!(out, "\n{} {} {} in {} | {}", 
             emoji, 
-            weather.description,
+            self.weather.description(),
             self.weather.format_temperature(&self.unit).bold(),
             self.weather.name.bold().cyan(),
-            format_temperature_feeling(temp_c)
-        );
-        println!();
+            feeling
+        )?;
+        writeln!(out)?;
+        Ok(())
     }
 
     // COMPACT TEMPLATE - Small but informative
-    fn display_compact(&self) {
-        let weather = &self.weather.weather[0];
-        let temp_c = self.weather.main.temp - 273.15;
-        let emoji = get_weather_emoji(&weather.icon);
+    fn display_compact(&self, out: &mut Canvas<'_>) -> io::Result<()> {
+        let g = out.charset();
+        let emoji = condition_icon(g, self.weather.weather.first());
+        let frame = Frame::new(box_chars(g).0, box_width(out.width(), COMPACT_WIDTH));
 
-        println!("\n┌─ {} {} ─┐", "🌤️".bold(), "WEATHER".bold().cyan());
+        writeln!(out, "\n{}", frame.top(&format!("{}{}", g.pick("🌤️ ", "").bold(), "WEATHER".bold().cyan())))?;
         
-        println!("│ {} {} {} {}", 
+        writeln!(out, "{}", frame.row(&format!("{} {} {} {}", 
             emoji,
-            weather.description.bold(),
+            self.weather.description().bold(),
             self.weather.format_temperature(&self.unit).bold().yellow(),
             format!("(feels {})", self.weather.format_feels_like(&self.unit)).dimmed()
-        );
+        )))?;
         
-        println!("│");
-        println!("│ 📍 {} | 💧 {} | 💨 {:.1}m/s", 
+        writeln!(out, "{}", frame.row(""))?;
+        writeln!(out, "{}", frame.row(&format!("{}{} | {} {} | {} {:.1}m/s", 
+            g.pick("📍 ", ""),
             self.weather.name.bold(),
+            g.pick("💧", "Humidity"),
             format_humidity(self.weather.main.humidity),
+            g.pick("💨", "Wind"),
             self.weather.wind.speed
-        );
+        )))?;
         
-        println!("│ 🌅 {} | 🌇 {}", 
+        writeln!(out, "{}", frame.row(&format!("{} {} | {} {}", 
+            g.pick("🌅", "Sunrise"),
             format_sun_time(self.weather.sys.sunrise, self.weather.timezone),
+            g.pick("🌇", "Sunset"),
             format_sun_time(self.weather.sys.sunset, self.weather.timezone)
-        );
+        )))?;
+
+        if let Some(trend) = self.trend {
+            self.display_sparklines(out, &frame, trend)?;
+        }
         
-        println!("└────────────────────────────────────────────────┘");
-        println!();
+        writeln!(out, "{}", frame.bottom())?;
+        writeln!(out)?;
+        Ok(())
+    }
+
+    // ART TEMPLATE - Large condition art beside the key metrics
+    fn display_art(&self, out: &mut Canvas<'_>) -> io::Result<()> {
+        let g = out.charset();
+        let art = self.weather.weather.first()
+            .map_or(art::UNKNOWN, |c| art::icon(c.id, is_night(&c.icon)))
+            .lines();
+
+        let location = if !self.weather.sys.country.is_empty() {
+            format!("{}, {}", self.weather.name, self.weather.sys.country)
+        } else {
+            self.weather.name.clone()
+        };
+        let gust = match self.weather.wind.gust {
+            Some(gust) => format!(" (gusts {:.1})", gust),
+            None => String::new(),
+        };
+
+        let metrics = [
+            self.weather.description().bold().to_string(),
+            format!("{} {}",
+                self.weather.format_temperature(&self.unit).bold().yellow(),
+                format!("(feels {})", self.weather.format_feels_like(&self.unit)).dimmed()
+            ),
+            format!("{} {} {:.1} m/s{}",
+                g.pick("💨", "Wind"),
+                format_wind_direction(self.weather.wind.deg),
+                self.weather.wind.speed,
+                gust
+            ),
+            format!("{} {}", g.pick("👁️", "Visibility"), format_visibility(self.weather.visibility)),
+            format!("{} {} | {} {}",
+                g.pick("💧", "Humidity"),
+                format_humidity(self.weather.main.humidity),
+                g.pick("🎈", "Pressure"),
+                format_pressure(self.weather.main.pressure)
+            ),
+        ];
+
+        writeln!(out, "\n{}{}", g.pick("📍 ", ""), location.bold().cyan())?;
+        writeln!(out)?;
+        for (line, metric) in art.iter().zip(metrics.iter()) {
+            writeln!(out, "  {}  {}", line, metric)?;
+        }
+        writeln!(out)?;
+
+        if let Some(forecast) = self.forecast {
+            self.display_art_days(out, forecast)?;
+        }
+        Ok(())
+    }
+
+    fn display_art_days(&self, out: &mut Canvas<'_>, forecast: &ForecastResponse) -> io::Result<()> {
+        let g = out.charset();
+        let unit = self.unit;
+        // As many blocks plus a gap as fit after the indent
+        let per_row = (out.width().saturating_sub(2) / (ART_WIDTH + 2)).max(1);
+        let cell = |text: &str| fit_with(&g.text(text), ART_WIDTH, g.pick("…", "..."));
+
+        let blocks: Vec<Vec<String>> = forecast
+            .daily()
+            .iter()
+            .map(|day| {
+                let mut block = vec![cell(&day.date.format("%a %d").to_string()).bold().to_string()];
+                block.extend(art::icon(day.condition_id, false).lines());
+                block.push(cell(&day.description));
+                block.push(cell(&format!("{:.0}–{:.0}{}", unit.convert(day.min), unit.convert(day.max), unit)).yellow().to_string());
+                block.push(cell(&format!("{:.0}% {:.1} mm", day.pop * 100.0, day.precipitation)).blue().to_string());
+                block
+            })
+            .collect();
+
+        writeln!(out, "{}", g.pick("📅 FORECAST", "FORECAST").bold())?;
+        writeln!(out, "{}", thin_rule(g, 40).dimmed())?;
+        for row in blocks.chunks(per_row) {
+            for line in 0..row[0].len() {
+                let cells: Vec<&str> = row.iter().map(|block| block[line].as_str()).collect();
+                writeln!(out, "  {}", cells.join("  "))?;
+            }
+            writeln!(out)?;
+        }
+        Ok(())
     }
 
     // DEFAULT TEMPLATE - Current layout
-    fn display_default(&self) {
-        self.display_header();
-        self.display_location();
-        self.display_current_weather();
-        self.display_details();
-        self.display_footer();
+    fn display_default(&self, out: &mut Canvas<'_>) -> io::Result<()> {
+        self.display_header(out)?;
+        self.display_location(out)?;
+        self.display_current_weather(out)?;
+        self.display_details(out)?;
+        self.display_footer(out)
     }
 
     // DETAILED TEMPLATE - Full information
-    fn display_detailed(&self) {
-        let weather = &self.weather.weather[0];
+    fn display_detailed(&self, out: &mut Canvas<'_>) -> io::Result<()> {
         let temp_c = self.weather.main.temp - 273.15;
-
-        println!("\n{}", "╔════════════════════════════════════════════════════════════╗".bright_cyan());
-        println!("{}", "║           🌤️  ADVANCED WEATHER INFORMATION 🌤️              ║".bright_cyan().bold());
-        println!("{}", "╚════════════════════════════════════════════════════════════╝".bright_cyan());
+        let g = out.charset();
+        let width = box_width(out.width(), DETAILED_WIDTH);
+        let (light, double) = box_chars(g);
+        let banner = Frame::new(double, width);
+        let section = Frame::new(light, width);
+
+        writeln!(out, "\n{}", banner.top("").bright_cyan())?;
+        writeln!(out, "{}", banner.center(g.pick(
+            "🌤️  ADVANCED WEATHER INFORMATION 🌤️",
+            "ADVANCED WEATHER INFORMATION"
+        )).bright_cyan().bold())?;
+        writeln!(out, "{}", banner.bottom().bright_cyan())?;
         
         // Location Section
-        println!("\n{}", "┌─ 📍 LOCATION ─────────────────────────────────────────────┐".cyan());
-        println!("│ City: {:<52}│", self.weather.name.bold().green());
-        println!("│ Country: {:<48}│", self.weather.sys.country.bold());
-        println!("│ Coordinates: {:.3}°N, {:.3}°E{:<32}│", 
+        writeln!(out, "\n{}", section.top(g.pick("📍 LOCATION", "LOCATION")).cyan())?;
+        writeln!(out, "{}", section.row(&format!("City: {}", self.weather.name.bold().green())))?;
+        writeln!(out, "{}", section.row(&format!("Country: {}", self.weather.sys.country.bold())))?;
+        writeln!(out, "{}", section.row(&format!("Coordinates: {:.3}°N, {:.3}°E", 
             self.weather.coord.lat, 
-            self.weather.coord.lon, 
-            ""
-        );
+            self.weather.coord.lon
+        )))?;
         let offset_hours = self.weather.timezone / 3600;
-        println!("│ Timezone: UTC{}{:<46}│", 
-            if offset_hours >= 0 { format!("+{}", offset_hours) } else { offset_hours.to_string() },
-            ""
-        );
-        println!("{}", "└────────────────────────────────────────────────────────────┘".cyan());
+        writeln!(out, "{}", section.row(&format!("Timezone: UTC{}", 
+            if offset_hours >= 0 { format!("+{}", offset_hours) } else { offset_hours.to_string() }
+        )))?;
+        writeln!(out, "{}", section.bottom().cyan())?;
 
         // Current Weather Section
-        println!("\n{}", "┌─ 🌡️  CURRENT CONDITIONS ────────────────────────────────────┐".yellow());
-        println!("│ Condition: {:<51}│", 
-            format!("{} {}", 
-                get_weather_emoji(&weather.icon),
+        writeln!(out, "\n{}", section.top(g.pick("🌡️  CURRENT CONDITIONS", "CURRENT CONDITIONS")).yellow())?;
+        for weather in &self.weather.weather {
+            writeln!(out, "{}", section.row(&format!("Condition: {} {}", 
+                condition_icon(g, Some(weather)),
                 weather.description.to_uppercase().bold()
-            )
-        );
-        println!("│ Temperature: {:<48}│", 
+            )))?;
+            if let Some(condition) = Condition::of(weather.id) {
+                writeln!(out, "{}", section.row(&format!("           {}", condition.text).dimmed().to_string()))?;
+            }
+        }
+        writeln!(out, "{}", section.row(&format!("Temperature: {}", 
             self.weather.format_temperature(&self.unit).bold().color(
                 match temp_c {
                     t if t < 0.0 => Color::BrightBlue,
@@ -147,66 +481,134 @@ impl<'a> WeatherDisplay<'a> {
                     _ => Color::Red,
                 }
             )
-        );
-        println!("│ Feels Like: {:<51}│", self.weather.format_feels_like(&self.unit));
+        )))?;
+        writeln!(out, "{}", section.row(&format!("Feels Like: {}", self.weather.format_feels_like(&self.unit))))?;
         
         let temp_min = self.weather.main.temp_min - 273.15;
         let temp_max = self.weather.main.temp_max - 273.15;
-        println!("│ Daily Range: {:.1}°C - {:.1}°C{:<36}│", temp_min, temp_max, "");
+        writeln!(out, "{}", section.row(&format!("Daily Range: {:.1}°C - {:.1}°C", temp_min, temp_max)))?;
         
-        println!("│ Sensation: {:<52}│", get_temperature_feeling(temp_c).dimmed());
-        println!("{}", "└────────────────────────────────────────────────────────────┘".yellow());
+        let sensation = match g {
+            Charset::Unicode => get_temperature_feeling(temp_c),
+            Charset::Ascii => temperature_feeling(temp_c).1.to_string(),
+        };
+        writeln!(out, "{}", section.row(&format!("Sensation: {}", sensation.dimmed())))?;
+        writeln!(out, "{}", section.bottom().yellow())?;
 
         // Atmospheric Conditions
-        println!("\n{}", "┌─ 💨 ATMOSPHERIC CONDITIONS ────────────────────────────────┐".cyan());
-        println!("│ Humidity: {:<53}│", format_humidity(self.weather.main.humidity).bold().blue());
-        println!("│ Pressure: {:<53}│", format_pressure(self.weather.main.pressure));
-        println!("│ Wind Speed: {:<51}│", format!("{:.2} m/s", self.weather.wind.speed).bold());
-        println!("│ Wind Direction: {:<46}│", format_wind_direction(self.weather.wind.deg).bold());
+        writeln!(out, "\n{}", section.top(g.pick("💨 ATMOSPHERIC CONDITIONS", "ATMOSPHERIC CONDITIONS")).cyan())?;
+        writeln!(out, "{}", section.row(&format!("Humidity: {}", format_humidity(self.weather.main.humidity).bold().blue())))?;
+        writeln!(out, "{}", section.row(&format!("Pressure: {}", format_pressure(self.weather.main.pressure))))?;
+        writeln!(out, "{}", section.row(&format!("Wind Speed: {}", format!("{:.2} m/s", self.weather.wind.speed).bold())))?;
+        writeln!(out, "{}", section.row(&format!("Wind Direction: {}", format_wind_direction(self.weather.wind.deg).bold())))?;
         if let Some(gust) = self.weather.wind.gust {
-            println!("│ Wind Gust: {:<52}│", format!("{:.2} m/s", gust));
+            writeln!(out, "{}", section.row(&format!("Wind Gust: {:.2} m/s", gust)))?;
         }
-        println!("│ Cloudiness: {:<50}│", format_cloudiness(self.weather.clouds.all).bold());
-        println!("│ Visibility: {:<51}│", format_visibility(self.weather.visibility));
-        println!("{}", "└────────────────────────────────────────────────────────────┘".cyan());
+        writeln!(out, "{}", section.row(&format!("Cloudiness: {}", format_cloudiness(self.weather.clouds.all).bold())))?;
+        writeln!(out, "{}", section.row(&format!("Visibility: {}", format_visibility(self.weather.visibility))))?;
+        writeln!(out, "{}", section.bottom().cyan())?;
 
         // Sun & Moon Section
-        println!("\n{}", "┌─ ☀️  SUN & MOON ──────────────────────────────────────────┐".bright_yellow());
-        println!("│ Sunrise: {:<54}│", 
+        writeln!(out, "\n{}", section.top(g.pick("☀️  SUN & MOON", "SUN & MOON")).bright_yellow())?;
+        writeln!(out, "{}", section.row(&format!("Sunrise: {}", 
             format_sun_time(self.weather.sys.sunrise, self.weather.timezone).bold()
-        );
-        println!("│ Sunset: {:<55}│", 
+        )))?;
+        writeln!(out, "{}", section.row(&format!("Sunset: {}", 
             format_sun_time(self.weather.sys.sunset, self.weather.timezone).bold()
-        );
-        println!("│ Moon Phase: {:<52}│", format_moon_phase(None));
-        println!("{}", "└────────────────────────────────────────────────────────────┘".bright_yellow());
+        )))?;
+        writeln!(out, "{}", section.row(&format!("Moon Phase: {}", moon_phase(g))))?;
+        writeln!(out, "{}", section.bottom().bright_yellow())?;
+
+        if let Some(trend) = self.trend {
+            self.display_charts(out, &section, trend)?;
+        }
 
         // Footer
-        println!("\n{}", "╔════════════════════════════════════════════════════════════╗".bright_cyan());
-        let updated = Utc::now();
-        println!("║ {} Last updated: {}{}║", 
-            "🔄".dimmed(),
-            updated.format("%Y-%m-%d %H:%M:%S UTC").to_string().dimmed(),
-            " ".repeat(9)
-        );
-        println!("║ {} Powered by OpenWeatherMap API{}║", 
-            "⚡".dimmed(),
-            " ".repeat(21)
-        );
-        println!("{}", "╚════════════════════════════════════════════════════════════╝".bright_cyan());
-        println!();
+        writeln!(out, "\n{}", banner.top("").bright_cyan())?;
+        writeln!(out, "{}", banner.row(&format!("{}Last updated: {}", 
+            g.pick("🔄 ", "").dimmed(),
+            self.updated.format("%Y-%m-%d %H:%M:%S UTC").to_string().dimmed()
+        )))?;
+        writeln!(out, "{}", banner.row(&format!("{}Powered by OpenWeatherMap API", g.pick("⚡ ", "").dimmed())))?;
+        writeln!(out, "{}", banner.bottom().bright_cyan())?;
+        writeln!(out)?;
+        Ok(())
+    }
+
+    fn display_sparklines(&self, out: &mut Canvas<'_>, frame: &Frame, trend: &Trend) -> io::Result<()> {
+        // Room for the icon in front and the range after the line
+        let width = chart_width(frame.inner(), 18, 8, 28);
+        let g = out.charset();
+        let unit = self.unit;
+        let line = match g {
+            Charset::Unicode => sparkline,
+            Charset::Ascii => ascii_sparkline,
+        };
+        // Letters as wide as the emoji they stand in for
+        let rows = [
+            (g.pick("🌡️", "T "), trend.series(|p| unit.convert(p.temp)), unit.to_string()),
+            (g.pick("🔽", "P "), trend.series(|p| p.pressure), " hPa".to_string()),
+            (g.pick("💧", "H "), trend.series(|p| p.humidity), "%".to_string()),
+        ];
+
+        writeln!(out, "{}", frame.row(""))?;
+        for (icon, series, suffix) in &rows {
+            let (min, max) = series_range(series);
+            writeln!(out, "{}", frame.row(&format!("{} {} {}",
+                icon,
+                line(series, width).cyan(),
+                format!("{:.0}–{:.0}{}", min, max, suffix).dimmed()
+            )))?;
+        }
+        writeln!(out, "{}", frame.row(&format!("   {}", trend.label).dimmed().to_string()))?;
+        Ok(())
     }
 
-    fn display_header(&self) {
-        println!("\n{}", "=".repeat(80).cyan());
-        println!("{}", "🌤️  RUST WEATHER CLI".bold().cyan());
-        println!("{}", "=".repeat(80).cyan());
-        println!();
+    fn display_charts(&self, out: &mut Canvas<'_>, frame: &Frame, trend: &Trend) -> io::Result<()> {
+        // Room for the axis labels in front of the plot area
+        let width = chart_width(frame.inner(), 16, 16, 42);
+        let span = trend.span_labels();
+        let g = out.charset();
+        let unit = self.unit;
+        let (line, bars) = match g {
+            Charset::Unicode => (ChartStyle::Braille, ChartStyle::Block),
+            Charset::Ascii => (ChartStyle::Ascii, ChartStyle::Ascii),
+        };
+
+        writeln!(out, "\n{}", frame.top(&format!("{}TRENDS ({})", g.pick("📈 ", ""), trend.label)).green())?;
+        // Axis labels are measured before the frame could respell them
+        let charts = [
+            ("Temperature", trend.series(|p| unit.convert(p.temp)), line, g.text(&unit.to_string()).into_owned()),
+            ("Pressure", trend.series(|p| p.pressure), line, " hPa".to_string()),
+            ("Humidity", trend.series(|p| p.humidity), bars, "%".to_string()),
+        ];
+        for (i, (name, series, style, suffix)) in charts.iter().enumerate() {
+            if i > 0 {
+                writeln!(out, "{}", frame.row(""))?;
+            }
+            writeln!(out, "{}", frame.row(&name.bold().to_string()))?;
+            let label = |v: f64| format!("{:.0}{}", v, suffix);
+            for line in line_chart(series, width, 4, *style, label, span.as_ref()) {
+                writeln!(out, "{}", frame.row(&line))?;
+            }
+        }
+        writeln!(out, "{}", frame.bottom().green())?;
+        Ok(())
     }
 
-    fn display_location(&self) {
-        println!("{}", "📍 LOCATION".bold());
-        println!("{}", "─".repeat(40).dimmed());
+    fn display_header(&self, out: &mut Canvas<'_>) -> io::Result<()> {
+        let width = box_width(out.width(), DEFAULT_WIDTH);
+        writeln!(out, "\n{}", rule('=', width).cyan())?;
+        writeln!(out, "{}", out.charset().pick("🌤️  RUST WEATHER CLI", "RUST WEATHER CLI").bold().cyan())?;
+        writeln!(out, "{}", rule('=', width).cyan())?;
+        writeln!(out)?;
+        Ok(())
+    }
+
+    fn display_location(&self, out: &mut Canvas<'_>) -> io::Result<()> {
+        let g = out.charset();
+        writeln!(out, "{}", g.pick("📍 LOCATION", "LOCATION").bold())?;
+        writeln!(out, "{}", thin_rule(g, 40).dimmed())?;
 
         let location = if !self.weather.sys.country.is_empty() {
             format!("{}, {}", self.weather.name, self.weather.sys.country)
@@ -214,39 +616,53 @@ impl<'a> WeatherDisplay<'a> {
             self.weather.name.clone()
         };
 
-        println!("  {} {}", "🏙️".bold(), location.bold().green());
-        println!(
-            "  {} Coordinates: {:.3}°N, {:.3}°E",
-            "🗺️".bold(),
+        writeln!(out, "  {}{}", g.pick("🏙️ ", "City: ").bold(), location.bold().green())?;
+        writeln!(out, 
+            "  {}Coordinates: {:.3}°N, {:.3}°E",
+            g.pick("🗺️ ", "").bold(),
             self.weather.coord.lat,
             self.weather.coord.lon
-        );
+        )?;
 
         let offset_hours = self.weather.timezone / 3600;
-        println!(
-            "  {} Timezone: UTC{}{}",
-            "🕐".bold(),
+        writeln!(out, 
+            "  {}Timezone: UTC{}{}",
+            g.pick("🕐 ", "").bold(),
             if offset_hours >= 0 { "+" } else { "" },
             offset_hours
-        );
+        )?;
 
-        println!();
+        writeln!(out)?;
+        Ok(())
     }
 
-    fn display_current_weather(&self) {
-        println!("{}", "🌡️  CURRENT WEATHER".bold());
-        println!("{}", "─".repeat(40).dimmed());
+    fn display_current_weather(&self, out: &mut Canvas<'_>) -> io::Result<()> {
+        let g = out.charset();
+        writeln!(out, "{}", g.pick("🌡️  CURRENT WEATHER", "CURRENT WEATHER").bold())?;
+        writeln!(out, "{}", thin_rule(g, 40).dimmed())?;
 
-        let weather = &self.weather.weather[0];
         let temp_c = self.weather.main.temp - 273.15;
 
-        let emoji = get_weather_emoji(&weather.icon);
-        println!(
-            "  {} {} {}",
-            emoji.bold(),
-            weather.description.to_uppercase().bold(),
-            get_weather_emoji(&weather.icon)
-        );
+        for weather in &self.weather.weather {
+            match g {
+                Charset::Unicode => {
+                    let emoji = condition::emoji(weather.id, &weather.icon);
+                    writeln!(out, 
+                        "  {} {} {}",
+                        emoji.bold(),
+                        weather.description.to_uppercase().bold(),
+                        emoji
+                    )?;
+                }
+                Charset::Ascii => {
+                    writeln!(out, 
+                        "  {} {}",
+                        condition::ascii(weather.id, &weather.icon).bold(),
+                        weather.description.to_uppercase().bold()
+                    )?;
+                }
+            }
+        }
 
         let temp_color = match temp_c {
             t if t < 0.0 => Color::BrightBlue,
@@ -257,152 +673,207 @@ impl<'a> WeatherDisplay<'a> {
             _ => Color::Red,
         };
 
-        println!(
-            "  {} Temperature: {}",
-            "🌡️".bold(),
+        writeln!(out, 
+            "  {}Temperature: {}",
+            g.pick("🌡️ ", "").bold(),
             self.weather
                 .format_temperature(&self.unit)
                 .color(temp_color)
                 .bold()
-        );
+        )?;
 
-        println!(
-            "  {} Feels like: {}",
-            "🤚".bold(),
+        writeln!(out, 
+            "  {}Feels like: {}",
+            g.pick("🤚 ", "").bold(),
             self.weather.format_feels_like(&self.unit)
-        );
+        )?;
 
-        println!("  {} {}", "💭".bold(), get_temperature_feeling(temp_c));
+        match g {
+            Charset::Unicode => writeln!(out, "  {} {}", "💭".bold(), get_temperature_feeling(temp_c))?,
+            Charset::Ascii => writeln!(out, "  Feeling: {}", temperature_feeling(temp_c).1)?,
+        }
 
         let temp_min = self.weather.main.temp_min - 273.15;
         let temp_max = self.weather.main.temp_max - 273.15;
 
-        println!(
-            "  {} Daily range: {:.1}°C - {:.1}°C",
-            "📊".bold(),
+        writeln!(out, 
+            "  {}Daily range: {:.1}°C - {:.1}°C",
+            g.pick("📊 ", "").bold(),
             temp_min,
             temp_max
-        );
+        )?;
 
         let normalized = ((temp_c + 20.0) / 60.0 * 20.0).clamp(0.0, 20.0) as usize;
 
-        let bar = format!(
+        let (filled, empty) = match g {
+            Charset::Unicode => ('█', '░'),
+            Charset::Ascii => ('#', '.'),
+        };
+        let mut bar = format!(
             "[{}{}]",
-            "█".repeat(normalized).color(temp_color),
-            "░".repeat(20 - normalized).dimmed()
+            rule(filled, normalized).color(temp_color),
+            rule(empty, 20 - normalized).dimmed()
         );
-        println!("  {} {}", "📈".bold(), bar);
+        if !out.color() {
+            // The color says how warm it is; spell out the scale instead
+            let end = |kelvin: f64| format!("{:.0}{}", self.unit.convert(kelvin), self.unit);
+            bar = format!("{} {} {}", end(253.15), bar, end(313.15));
+        }
+        writeln!(out, "  {}{}", g.pick("📈 ", "").bold(), bar)?;
 
-        println!();
+        writeln!(out)?;
+        Ok(())
     }
 
-    fn display_details(&self) {
-        println!("{}", "📊 DETAILED INFORMATION".bold());
-        println!("{}", "─".repeat(40).dimmed());
+    fn display_details(&self, out: &mut Canvas<'_>) -> io::Result<()> {
+        let g = out.charset();
+        writeln!(out, "{}", g.pick("📊 DETAILED INFORMATION", "DETAILED INFORMATION").bold())?;
+        writeln!(out, "{}", thin_rule(g, 40).dimmed())?;
 
-        let left_col = vec![
-            format!("{} Humidity: {}", "💧".bold(), format_humidity(self.weather.main.humidity)),
-            format!("{} Pressure: {}", "🎈".bold(), format_pressure(self.weather.main.pressure)),
+        let left_col = [
+            format!("{}Humidity: {}", g.pick("💧 ", "").bold(), format_humidity(self.weather.main.humidity)),
+            format!("{}Pressure: {}", g.pick("🎈 ", "").bold(), format_pressure(self.weather.main.pressure)),
             format!(
-                "{} Wind: {:.1} m/s {}",
-                "💨".bold(),
+                "{}Wind: {:.1} m/s {}",
+                g.pick("💨 ", "").bold(),
                 self.weather.wind.speed,
                 format_wind_direction(self.weather.wind.deg)
             ),
-            format!("{} Clouds: {}", "☁️".bold(), format_cloudiness(self.weather.clouds.all)),
+            format!("{}Clouds: {}", g.pick("☁️ ", "").bold(), format_cloudiness(self.weather.clouds.all)),
         ];
 
-        let right_col = vec![
-            format!("{} Visibility: {}", "👁️".bold(), format_visibility(self.weather.visibility)),
+        let right_col = [
+            format!("{}Visibility: {}", g.pick("👁️ ", "").bold(), format_visibility(self.weather.visibility)),
             format!(
-                "{} Sunrise: {}",
-                "🌅".bold(),
+                "{}Sunrise: {}",
+                g.pick("🌅 ", "").bold(),
                 format_sun_time(self.weather.sys.sunrise, self.weather.timezone)
             ),
             format!(
-                "{} Sunset: {}",
-                "🌇".bold(),
+                "{}Sunset: {}",
+                g.pick("🌇 ", "").bold(),
                 format_sun_time(self.weather.sys.sunset, self.weather.timezone)
             ),
-            format!("{} Moon: {}", "🌙".bold(), format_moon_phase(None)),
+            format!("{}Moon: {}", g.pick("🌙 ", "").bold(), moon_phase(g)),
         ];
 
-        for i in 0..left_col.len() {
-            println!(
-                "  {:<35}  {}",
-                left_col[i],
-                right_col.get(i).unwrap_or(&String::new())
-            );
+        // Two columns side by side, the left one at most 35 cells wide
+        let column = (box_width(out.width(), DEFAULT_WIDTH) - 6).div_ceil(2).min(35);
+        for (left, right) in left_col.iter().zip(right_col.iter()) {
+            writeln!(out, "  {}  {}", pad(left, column), right)?;
         }
 
-        println!();
+        writeln!(out)?;
+        Ok(())
     }
 
-    fn display_footer(&self) {
-        println!("{}", "=".repeat(80).cyan());
+    fn display_footer(&self, out: &mut Canvas<'_>) -> io::Result<()> {
+        let width = box_width(out.width(), DEFAULT_WIDTH);
+        writeln!(out, "{}", rule('=', width).cyan())?;
 
-        let updated = Utc::now();
-        println!(
-            "{} Last updated: {}",
-            "🔄".dimmed(),
+        let g = out.charset();
+        let updated = self.updated;
+        writeln!(out, 
+            "{}Last updated: {}",
+            g.pick("🔄 ", "").dimmed(),
             updated.format("%Y-%m-%d %H:%M:%S UTC").to_string().dimmed()
-        );
+        )?;
+
+        writeln!(out, "{}Powered by OpenWeatherMap API", g.pick("⚡ ", "").dimmed())?;
+        writeln!(out, "{}", rule('=', width).cyan())?;
+        writeln!(out)?;
+        Ok(())
+    }
+}
+
+/// Chart width for `columns` of output: minus `reserved`, kept between
+/// `min` and `max`.
+fn chart_width(columns: usize, reserved: usize, min: usize, max: usize) -> usize {
+    columns.saturating_sub(reserved).clamp(min, max)
+}
 
-        println!("{} Powered by OpenWeatherMap API", "⚡".dimmed());
-        println!("{}", "=".repeat(80).cyan());
-        println!();
+/// Section and banner borders.
+fn box_chars(charset: Charset) -> (BoxChars, BoxChars) {
+    match charset {
+        Charset::Unicode => (LIGHT, DOUBLE),
+        Charset::Ascii => (ASCII, ASCII_DOUBLE),
     }
 }
 
+fn thin_rule(charset: Charset, width: usize) -> String {
+    rule(if charset.is_ascii() { '-' } else { '─' }, width)
+}
+
+fn condition_icon(charset: Charset, weather: Option<&WeatherCondition>) -> &'static str {
+    match charset {
+        Charset::Unicode => weather.map_or("🌈", |c| condition::emoji(c.id, &c.icon)),
+        Charset::Ascii => weather.map_or("[?]", |c| condition::ascii(c.id, &c.icon)),
+    }
+}
+
+fn moon_phase(charset: Charset) -> String {
+    match charset {
+        Charset::Unicode => format_moon_phase(None),
+        Charset::Ascii => "N/A".to_string(),
+    }
+}
+
+fn series_range(series: &[(u64, f64)]) -> (f64, f64) {
+    series.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), (_, v)| (min.min(*v), max.max(*v)))
+}
+
 pub fn display_error(error: &str) {
-    println!("\n{} {}", "❌ ERROR:".red().bold(), error);
-    println!("{}", "─".repeat(60).red());
-    println!("{} Check your internet connection and API key", "💡".yellow());
-    println!("{} Make sure the city name is correct", "🔍".yellow());
-    println!("{}", "─".repeat(60).red());
+    let g = Charset::current();
+    println!("\n{} {}", g.pick("❌ ERROR:", "ERROR:").red().bold(), error);
+    println!("{}", thin_rule(g, 60).red());
+    println!("{} Check your internet connection and API key", g.pick("💡", "-").yellow());
+    println!("{} Make sure the city name is correct", g.pick("🔍", "-").yellow());
+    println!("{}", thin_rule(g, 60).red());
 }
 
 pub fn display_help() {
-    println!("\n{}", "ℹ️  WEATHER CLI HELP".bold().cyan());
-    println!("{}", "═".repeat(60).cyan());
+    let g = Charset::current();
+    println!("\n{}", g.pick("ℹ️  WEATHER CLI HELP", "WEATHER CLI HELP").bold().cyan());
+    println!("{}", g.pick("═", "=").repeat(60).cyan());
 
-    println!("\n{}", "📝 USAGE:".bold());
+    println!("\n{}", g.pick("📝 USAGE:", "USAGE:").bold());
     println!("  weather <city> [options]");
     println!("  weather --help");
 
-    println!("\n{}", "📍 EXAMPLES:".bold());
+    println!("\n{}", g.pick("📍 EXAMPLES:", "EXAMPLES:").bold());
     println!("  weather Nairobi");
     println!("  weather \"New York\" --unit fahrenheit");
     println!("  weather London --unit celsius");
     println!("  weather Tokyo -u k");
 
-    println!("\n{}", "⚙️  OPTIONS:".bold());
+    println!("\n{}", g.pick("⚙️  OPTIONS:", "OPTIONS:").bold());
     println!("  -u, --unit <UNIT>    Temperature unit (celsius, fahrenheit, kelvin)");
     println!("  -h, --help           Show this help message");
     println!("  -v, --version        Show version information");
 
-    println!("\n{}", "🌡️  TEMPERATURE UNITS:".bold());
+    println!("\n{}", g.pick("🌡️  TEMPERATURE UNITS:", "TEMPERATURE UNITS:").bold());
     println!("  celsius (c)     - Degrees Celsius (°C)");
     println!("  fahrenheit (f)  - Degrees Fahrenheit (°F)");
     println!("  kelvin (k)      - Kelvin (K)");
 
-    println!("\n{}", "🔧 CONFIGURATION:".bold());
+    println!("\n{}", g.pick("🔧 CONFIGURATION:", "CONFIGURATION:").bold());
     println!("  export WEATHER_API_KEY=\"your_api_key_here\"");
 
-    println!("\n{}", "🔗 LINKS:".bold());
+    println!("\n{}", g.pick("🔗 LINKS:", "LINKS:").bold());
     println!("  GitHub: https://github.com/yourusername/weather-cli");
     println!("  OpenWeatherMap: https://openweathermap.org/api");
 
-    println!("\n{}", "═".repeat(60).cyan());
+    println!("\n{}", g.pick("═", "=").repeat(60).cyan());
 }
 
 pub fn display_version() {
-    println!("🌤️  Weather CLI v{}", env!("CARGO_PKG_VERSION"));
-    println!("🦀 Built with Rust");
+    let g = Charset::current();
+    println!("{}Weather CLI v{}", g.pick("🌤️  ", ""), env!("CARGO_PKG_VERSION"));
+    println!("{}Built with Rust", g.pick("🦀 ", ""));
 }
 
 pub fn display_loading(message: &str) {
-    print!("{} {}...", "⏳".yellow(), message);
+    print!("{} {}...", Charset::current().pick("⏳", "*").yellow(), message);
     std::io::Write::flush(&mut std::io::stdout()).unwrap();
 }
 
@@ -0,0 +1,159 @@
+use crate::models::forecast::ForecastResponse;
+use crate::models::weather::{WeatherResponse, TemperatureUnit};
+use crate::utils::condition;
+use crate::utils::formatters::*;
+use crate::utils::themes::Theme;
+use crate::utils::web;
+use anyhow::{anyhow, Result};
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+/// File formats `write_output` and `write_to` can produce.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Format {
+    /// Standalone HTML page, with forecast charts when a forecast is given
+    Html,
+    /// Self-contained SVG card
+    Svg,
+}
+
+impl Format {
+    /// The format for a file name's extension, if it is one we can write.
+    ///
+    /// ```
+    /// use std::path::Path;
+    /// use weather_cl::utils::export::Format;
+    ///
+    /// assert_eq!(Format::from_path(Path::new("out/London.HTML")), Some(Format::Html));
+    /// assert_eq!(Format::from_path(Path::new("card.svg")), Some(Format::Svg));
+    /// assert_eq!(Format::from_path(Path::new("notes.txt")), None);
+    /// ```
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_lowercase();
+        match extension.as_str() {
+            "html" | "htm" => Some(Format::Html),
+            "svg" => Some(Format::Svg),
+            _ => None,
+        }
+    }
+}
+
+/// Render the weather as `format` into any writer.
+///
+/// ```
+/// use weather_cl::utils::export::{write_to, Format};
+/// use weather_cl::utils::themes::ThemeRegistry;
+/// use weather_cl::{TemperatureUnit, WeatherResponse};
+///
+/// # let weather: WeatherResponse = serde_json::from_str(r#"{"coord": {"lon": 13.4, "lat": 52.5},
+/// #     "weather": [{"id": 800, "main": "Clear", "description": "clear sky", "icon": "01d"}],
+/// #     "main": {"temp": 293.15, "feels_like": 293.15, "temp_min": 292.0, "temp_max": 294.0,
+/// #              "pressure": 1012, "humidity": 40},
+/// #     "wind": {"speed": 2.0, "deg": 90}, "clouds": {"all": 0},
+/// #     "sys": {"country": "DE", "sunrise": 0, "sunset": 0},
+/// #     "name": "Berlin", "visibility": null, "timezone": 7200}"#)?;
+/// let themes = ThemeRegistry::default();
+/// let mut svg = Vec::new();
+/// write_to(&mut svg, Format::Svg, &weather, None, &TemperatureUnit::Celsius, themes.get(None))?;
+///
+/// let svg = String::from_utf8(svg)?;
+/// assert!(svg.starts_with("<?xml"));
+/// assert!(svg.contains("Berlin, DE"));
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn write_to(
+    out: &mut impl Write,
+    format: Format,
+    weather: &WeatherResponse,
+    forecast: Option<&ForecastResponse>,
+    unit: &TemperatureUnit,
+    theme: &Theme,
+) -> Result<()> {
+    let content = match format {
+        Format::Html => web::generate_html(weather, unit, forecast, theme),
+        Format::Svg => generate_svg_card(weather, unit),
+    };
+    out.write_all(content.as_bytes())?;
+    Ok(())
+}
+
+/// Write the weather to `path`, picking the format from the file extension.
+/// Forecast charts are included in the HTML page when a forecast is given.
+pub fn write_output(
+    path: &Path,
+    weather: &WeatherResponse,
+    forecast: Option<&ForecastResponse>,
+    unit: &TemperatureUnit,
+    theme: &Theme,
+) -> Result<()> {
+    let format = Format::from_path(path).ok_or_else(|| {
+        anyhow!("Unsupported output format '{}'. Use a .html or .svg file name.", path.display())
+    })?;
+
+    let mut file = fs::File::create(path)?;
+    write_to(&mut file, format, weather, forecast, unit, theme)
+}
+
+/// Render a standalone SVG weather card with no external references.
+pub fn generate_svg_card(weather: &WeatherResponse, unit: &TemperatureUnit) -> String {
+    let condition = weather.weather.first();
+    let temp_c = weather.main.temp - 273.15;
+
+    let temp_color = match temp_c {
+        t if t < 0.0 => "#3498db",
+        t if t < 10.0 => "#2980b9",
+        t if t < 20.0 => "#27ae60",
+        t if t < 30.0 => "#f39c12",
+        t if t < 40.0 => "#e67e22",
+        _ => "#e74c3c",
+    };
+
+    let location = if !weather.sys.country.is_empty() {
+        format!("{}, {}", weather.name, weather.sys.country)
+    } else {
+        weather.name.clone()
+    };
+
+    format!(r##"<?xml version="1.0" encoding="UTF-8"?>
+<svg xmlns="http://www.w3.org/2000/svg" width="420" height="240" viewBox="0 0 420 240" font-family="'Segoe UI', Tahoma, Geneva, Verdana, sans-serif">
+  <defs>
+    <linearGradient id="header" x1="0" y1="0" x2="1" y2="1">
+      <stop offset="0%" stop-color="#667eea"/>
+      <stop offset="100%" stop-color="#764ba2"/>
+    </linearGradient>
+  </defs>
+  <rect width="420" height="240" rx="20" fill="white"/>
+  <path d="M0 20 A20 20 0 0 1 20 0 H400 A20 20 0 0 1 420 20 V70 H0 Z" fill="url(#header)"/>
+  <text x="24" y="36" font-size="20" font-weight="bold" fill="white">{}</text>
+  <text x="24" y="58" font-size="12" fill="white" fill-opacity="0.85">{:.3}°N, {:.3}°E</text>
+  <text x="24" y="135" font-size="48">{}</text>
+  <text x="96" y="118" font-size="40" font-weight="bold" fill="{}">{}</text>
+  <text x="98" y="142" font-size="14" fill="#2c3e50">{} · feels like {}</text>
+  <line x1="24" y1="162" x2="396" y2="162" stroke="#ecf0f1" stroke-width="2"/>
+  <text x="24" y="188" font-size="13" fill="#7f8c8d">💧 {}</text>
+  <text x="120" y="188" font-size="13" fill="#7f8c8d">🎈 {}</text>
+  <text x="240" y="188" font-size="13" fill="#7f8c8d">💨 {:.1} m/s {}</text>
+  <text x="24" y="214" font-size="13" fill="#7f8c8d">🌅 {}</text>
+  <text x="120" y="214" font-size="13" fill="#7f8c8d">🌇 {}</text>
+  <text x="240" y="214" font-size="13" fill="#7f8c8d">👁️ {}</text>
+</svg>
+"##,
+        escape_html(&location),
+        weather.coord.lat,
+        weather.coord.lon,
+        condition.map_or("🌈", |c| condition::emoji(c.id, &c.icon)),
+        temp_color,
+        weather.format_temperature(unit),
+        escape_html(&weather.description()),
+        weather.format_feels_like(unit),
+        format_humidity(weather.main.humidity),
+        format_pressure(weather.main.pressure),
+        weather.wind.speed,
+        format_wind_direction(weather.wind.deg),
+        format_sun_time(weather.sys.sunrise, weather.timezone),
+        format_sun_time(weather.sys.sunset, weather.timezone),
+        format_visibility(weather.visibility),
+    )
+}
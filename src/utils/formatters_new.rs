@@ -12,6 +12,22 @@ pub fn format_sun_time(timestamp: u64, timezone: i32) -> String {
     dt.format("%H:%M").to_string()
 }
 
+/// Weekday and time at the location, e.g. "Tue 15:00".
+pub fn format_day_time(timestamp: u64, timezone: i32) -> String {
+    let offset = FixedOffset::east_opt(timezone).unwrap();
+    let dt = Utc.timestamp_opt(timestamp as i64, 0).unwrap().with_timezone(&offset);
+    dt.format("%a %H:%M").to_string()
+}
+
+/// 16-point compass direction for a wind bearing.
+///
+/// ```
+/// use weather_cl::utils::formatters::format_wind_direction;
+///
+/// assert_eq!(format_wind_direction(0), "N");
+/// assert_eq!(format_wind_direction(200), "SSW");
+/// assert_eq!(format_wind_direction(355), "N");
+/// ```
 pub fn format_wind_direction(degrees: u32) -> String {
     let directions = [
         "N", "NNE", "NE", "ENE", "E", "ESE", "SE", "SSE",
@@ -49,6 +65,21 @@ pub fn format_cloudiness(cloudiness: u32) -> String {
     }
 }
 
+/// Escape text for HTML and SVG content and attribute values.
+///
+/// ```
+/// use weather_cl::utils::formatters::escape_html;
+///
+/// assert_eq!(escape_html("<b>\"A&B\"</b>"), "&lt;b&gt;&quot;A&amp;B&quot;&lt;/b&gt;");
+/// ```
+pub fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
 pub fn get_weather_emoji(icon: &str) -> &'static str {
     match icon {
         "01d" => "☀️",
@@ -64,15 +95,44 @@ pub fn get_weather_emoji(icon: &str) -> &'static str {
     }
 }
 
+/// Plain-ASCII counterpart of [`get_weather_emoji`].
+///
+/// ```
+/// use weather_cl::utils::formatters::get_weather_ascii;
+///
+/// assert_eq!(get_weather_ascii("10n"), "[showers]");
+/// assert_eq!(get_weather_ascii("99x"), "[?]");
+/// ```
+pub fn get_weather_ascii(icon: &str) -> &'static str {
+    match icon {
+        "01d" => "[sun]",
+        "01n" => "[moon]",
+        "02d" | "02n" => "[partly cloudy]",
+        "03d" | "03n" | "04d" | "04n" => "[cloudy]",
+        "09d" | "09n" => "[rain]",
+        "10d" | "10n" => "[showers]",
+        "11d" | "11n" => "[storm]",
+        "13d" | "13n" => "[snow]",
+        "50d" | "50n" => "[fog]",
+        _ => "[?]",
+    }
+}
+
 pub fn get_temperature_feeling(temp_c: f64) -> String {
+    let (emoji, label) = temperature_feeling(temp_c);
+    format!("{} {}", emoji, label)
+}
+
+/// The emoji and label [`get_temperature_feeling`] puts together.
+pub(crate) fn temperature_feeling(temp_c: f64) -> (ColoredString, &'static str) {
     match temp_c {
-        t if t < -10.0 => format!("{} Freezing", "🥶".red()),
-        t if t < 0.0 => format!("{} Very Cold", "🧊".bright_blue()),
-        t if t < 10.0 => format!("{} Cold", "🌬️".blue()),
-        t if t < 20.0 => format!("{} Cool", "😎".green()),
-        t if t < 30.0 => format!("{} Warm", "🌤️".yellow()),
-        t if t < 40.0 => format!("{} Hot", "🔥".bright_yellow()),
-        _ => format!("{} Extremely Hot", "🥵".red()),
+        t if t < -10.0 => ("🥶".red(), "Freezing"),
+        t if t < 0.0 => ("🧊".bright_blue(), "Very Cold"),
+        t if t < 10.0 => ("🌬️".blue(), "Cold"),
+        t if t < 20.0 => ("😎".green(), "Cool"),
+        t if t < 30.0 => ("🌤️".yellow(), "Warm"),
+        t if t < 40.0 => ("🔥".bright_yellow(), "Hot"),
+        _ => ("🥵".red(), "Extremely Hot"),
     }
 }
 
@@ -0,0 +1,117 @@
+//! Plain-ASCII stand-ins for emoji and Unicode drawing characters, so output
+//! stays readable over serial consoles, on `TERM=dumb` and in log files.
+//!
+//! Like `colored::control` does for colors, the charset is set once for the
+//! whole process and read by everything that prints; templates can also be
+//! given one explicitly with `WeatherDisplay::with_charset`.
+
+use std::borrow::Cow;
+use std::env;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ASCII: AtomicBool = AtomicBool::new(false);
+
+/// Characters the output may use.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Charset {
+    /// Emoji plus box drawing, block and braille characters
+    #[default]
+    Unicode,
+    /// Printable ASCII only
+    Ascii,
+}
+
+impl Charset {
+    /// ASCII when asked for or when the terminal is `TERM=dumb`.
+    pub fn detect(ascii: bool) -> Self {
+        if ascii || is_dumb_terminal() {
+            Charset::Ascii
+        } else {
+            Charset::Unicode
+        }
+    }
+
+    /// The charset set with [`set_charset`], Unicode unless changed.
+    pub fn current() -> Self {
+        if ASCII.load(Ordering::Relaxed) {
+            Charset::Ascii
+        } else {
+            Charset::Unicode
+        }
+    }
+
+    pub fn is_ascii(self) -> bool {
+        self == Charset::Ascii
+    }
+
+    /// `unicode`, or its `ascii` stand-in.
+    ///
+    /// ```
+    /// use weather_cl::utils::glyphs::Charset;
+    ///
+    /// assert_eq!(Charset::Unicode.pick("💧", "Humidity"), "💧");
+    /// assert_eq!(Charset::Ascii.pick("💧", "Humidity"), "Humidity");
+    /// ```
+    pub fn pick<'a>(self, unicode: &'a str, ascii: &'a str) -> &'a str {
+        match self {
+            Charset::Unicode => unicode,
+            Charset::Ascii => ascii,
+        }
+    }
+
+    /// `text` as is, or with typographic characters spelled in ASCII.
+    pub fn text(self, text: &str) -> Cow<'_, str> {
+        match self {
+            Charset::Unicode => Cow::Borrowed(text),
+            Charset::Ascii => to_ascii(text),
+        }
+    }
+}
+
+/// `emoji`, or its `ascii` stand-in under the current charset.
+pub fn icon(emoji: &'static str, ascii: &'static str) -> &'static str {
+    Charset::current().pick(emoji, ascii)
+}
+
+/// Use `charset` for everything printed from now on.
+pub fn set_charset(charset: Charset) {
+    ASCII.store(charset.is_ascii(), Ordering::Relaxed);
+}
+
+/// Whether stdout should get colors: not with `--no-color`, `NO_COLOR`,
+/// `TERM=dumb`, or when it is not a terminal.
+pub fn color_enabled(no_color: bool) -> bool {
+    !no_color && !is_dumb_terminal() && colored::control::SHOULD_COLORIZE.should_colorize()
+}
+
+fn is_dumb_terminal() -> bool {
+    env::var("TERM").is_ok_and(|term| term == "dumb")
+}
+
+/// `text` with degree signs dropped and dashes and ellipses spelled in
+/// ASCII. Emoji are left alone; pick a stand-in for those.
+///
+/// ```
+/// use weather_cl::utils::glyphs::to_ascii;
+///
+/// assert_eq!(to_ascii("10–17°C"), "10-17C");
+/// assert_eq!(to_ascii("feels 13.6°…"), "feels 13.6...");
+/// ```
+pub fn to_ascii(text: &str) -> Cow<'_, str> {
+    if text.is_ascii() {
+        return Cow::Borrowed(text);
+    }
+
+    let mut ascii = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '°' => {}
+            '–' | '—' | '─' | '━' => ascii.push('-'),
+            '…' => ascii.push_str("..."),
+            '│' | '┃' => ascii.push('|'),
+            '═' => ascii.push('='),
+            c => ascii.push(c),
+        }
+    }
+    Cow::Owned(ascii)
+}
@@ -0,0 +1,192 @@
+//! Column-accurate layout for the terminal templates. Widths are counted in
+//! terminal cells: ANSI escape sequences take none, emoji and CJK take two,
+//! so colored and decorated text lines up with the box borders.
+
+use crate::utils::glyphs::to_ascii;
+use console::Alignment;
+
+/// Narrowest box the templates draw, whatever the terminal says.
+pub const MIN_WIDTH: usize = 40;
+
+/// Cells `text` takes up on a terminal.
+///
+/// ```
+/// use weather_cl::utils::layout::width;
+///
+/// assert_eq!(width("\x1b[1mBerlin\x1b[0m"), 6);
+/// assert_eq!(width("☁️ 東京"), 7);
+/// ```
+pub fn width(text: &str) -> usize {
+    console::measure_text_width(text)
+}
+
+/// `text` followed by spaces up to `width` cells. Longer text is kept whole.
+pub fn pad(text: &str, width: usize) -> String {
+    console::pad_str(text, width, Alignment::Left, None).into_owned()
+}
+
+/// `text` preceded by spaces up to `width` cells.
+pub fn pad_start(text: &str, width: usize) -> String {
+    console::pad_str(text, width, Alignment::Right, None).into_owned()
+}
+
+/// `text` padded or cut with "…" to exactly `width` cells.
+pub fn fit(text: &str, width: usize) -> String {
+    fit_with(text, width, "…")
+}
+
+/// [`fit`] with `tail` in place of "…", e.g. "..." for ASCII output.
+pub fn fit_with(text: &str, width: usize, tail: &str) -> String {
+    // `truncate_str` cuts anything wider than `width` less the tail, counts
+    // some emoji narrower than `measure_text_width` does, and cutting before
+    // a double-width character leaves a cell over: only cut what does not
+    // fit, until it does, and pad what is left.
+    if self::width(text) <= width {
+        return pad(text, width);
+    }
+    let mut target = width;
+    loop {
+        let cut = console::truncate_str(text, target, tail);
+        if self::width(&cut) <= width || target == 0 {
+            return pad(&cut, width);
+        }
+        target -= 1;
+    }
+}
+
+/// A line of `ch` `width` cells wide.
+pub fn rule(ch: char, width: usize) -> String {
+    ch.to_string().repeat(width)
+}
+
+/// Box width for `columns` of output: as wide as the terminal allows, up to
+/// `max`.
+pub fn box_width(columns: usize, max: usize) -> usize {
+    columns.min(max).max(MIN_WIDTH)
+}
+
+/// Characters for drawing a box.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoxChars {
+    pub top_left: char,
+    pub top_right: char,
+    pub bottom_left: char,
+    pub bottom_right: char,
+    pub horizontal: char,
+    pub vertical: char,
+}
+
+pub const LIGHT: BoxChars = BoxChars {
+    top_left: '┌',
+    top_right: '┐',
+    bottom_left: '└',
+    bottom_right: '┘',
+    horizontal: '─',
+    vertical: '│',
+};
+
+pub const DOUBLE: BoxChars = BoxChars {
+    top_left: '╔',
+    top_right: '╗',
+    bottom_left: '╚',
+    bottom_right: '╝',
+    horizontal: '═',
+    vertical: '║',
+};
+
+/// [`LIGHT`] for terminals without Unicode.
+pub const ASCII: BoxChars = BoxChars {
+    top_left: '+',
+    top_right: '+',
+    bottom_left: '+',
+    bottom_right: '+',
+    horizontal: '-',
+    vertical: '|',
+};
+
+/// [`DOUBLE`] for terminals without Unicode.
+pub const ASCII_DOUBLE: BoxChars = BoxChars {
+    top_left: '+',
+    top_right: '+',
+    bottom_left: '+',
+    bottom_right: '+',
+    horizontal: '=',
+    vertical: '|',
+};
+
+/// A box of a fixed outer width. Each method returns one line, so callers
+/// can color borders and write rows however they like. Boxes with ASCII
+/// borders keep their content ASCII too, apart from emoji.
+///
+/// ```
+/// use weather_cl::utils::layout::{width, Frame, LIGHT};
+///
+/// let frame = Frame::new(LIGHT, 24);
+/// assert_eq!(frame.top("📍 CITY"), "┌─ 📍 CITY ────────────┐");
+/// assert_eq!(frame.row("Tokyo 東京"), "│ Tokyo 東京           │");
+/// assert_eq!(frame.bottom(), "└──────────────────────┘");
+/// assert!([frame.top("📍 CITY"), frame.row("Tokyo 東京"), frame.bottom()].iter().all(|l| width(l) == 24));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Frame {
+    chars: BoxChars,
+    width: usize,
+}
+
+impl Frame {
+    pub fn new(chars: BoxChars, width: usize) -> Self {
+        Self { chars, width: width.max(4) }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Cells available for content between the borders and their padding.
+    pub fn inner(&self) -> usize {
+        self.width - 4
+    }
+
+    /// Top border, with `title` set into it unless empty.
+    pub fn top(&self, title: &str) -> String {
+        let c = self.chars;
+        if title.is_empty() {
+            return format!("{}{}{}", c.top_left, rule(c.horizontal, self.width - 2), c.top_right);
+        }
+        let title = format!("{} {} ", c.horizontal, self.fit(title, self.width.saturating_sub(6)).trim_end());
+        format!(
+            "{}{}{}{}",
+            c.top_left,
+            title,
+            rule(c.horizontal, (self.width - 2).saturating_sub(width(&title))),
+            c.top_right
+        )
+    }
+
+    /// `content` between the side borders, padded or cut to fit.
+    pub fn row(&self, content: &str) -> String {
+        let v = self.chars.vertical;
+        format!("{} {} {}", v, self.fit(content, self.inner()), v)
+    }
+
+    /// `content` centered between the side borders.
+    pub fn center(&self, content: &str) -> String {
+        let content = self.fit(content, self.inner());
+        let content = content.trim_end();
+        let left = (self.inner() - width(content)) / 2;
+        self.row(&format!("{}{}", " ".repeat(left), content))
+    }
+
+    pub fn bottom(&self) -> String {
+        let c = self.chars;
+        format!("{}{}{}", c.bottom_left, rule(c.horizontal, self.width - 2), c.bottom_right)
+    }
+
+    fn fit(&self, text: &str, width: usize) -> String {
+        if self.chars.vertical.is_ascii() {
+            fit_with(&to_ascii(text), width, "...")
+        } else {
+            fit(text, width)
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use crate::api::cache::CachedWeatherClient;
+use crate::api::stats::ErrorKind;
+use crate::models::weather::WeatherResponse;
+use std::fmt::Write;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+type Gauge = (&'static str, &'static str, fn(&WeatherResponse) -> Option<f64>);
+
+/// Collects weather gauges for a fixed set of locations in the Prometheus
+/// text exposition format. Observations are refreshed in the background
+/// through the shared cache, so scraping `/metrics` never waits on the
+/// provider and a location is fetched at most once per cache TTL.
+pub struct MetricsCollector {
+    cache: Arc<CachedWeatherClient>,
+    locations: Vec<String>,
+    samples: Mutex<Vec<(String, Arc<WeatherResponse>)>>,
+}
+
+impl MetricsCollector {
+    pub fn new(cache: Arc<CachedWeatherClient>, mut locations: Vec<String>) -> Self {
+        // Each location is one series, so the same one twice would repeat it
+        let mut seen = Vec::new();
+        locations.retain(|l| {
+            let key = l.trim().to_lowercase();
+            let new = !seen.contains(&key);
+            seen.push(key);
+            new
+        });
+        Self { cache, locations, samples: Mutex::new(Vec::new()) }
+    }
+
+    /// Fetch stale locations into the snapshot [`render`](Self::render)
+    /// serves.
+    pub fn refresh(&self) {
+        // Keep serving the previous sample if a refresh fails; the error is
+        // reflected in weather_api_errors_total.
+        let samples = self
+            .locations
+            .iter()
+            .filter_map(|l| {
+                let weather = self.cache.get_weather(l).ok().or_else(|| self.cache.stale(l))?;
+                Some((l.clone(), weather))
+            })
+            .collect();
+        *self.samples.lock().unwrap() = samples;
+    }
+
+    /// Call [`refresh`](Self::refresh) now and then once per `period` on a
+    /// background thread.
+    pub fn spawn_refresh(self: &Arc<Self>, period: Duration) {
+        let collector = Arc::clone(self);
+        std::thread::spawn(move || loop {
+            collector.refresh();
+            std::thread::sleep(period);
+        });
+    }
+
+    /// Render the last snapshot and the provider counters.
+    pub fn render(&self) -> String {
+        let samples = self.samples.lock().unwrap().clone();
+        let client = self.cache.client();
+        let provider = client.provider();
+
+        let mut out = String::new();
+        let samples: Vec<(&str, &WeatherResponse)> = samples.iter().map(|(l, w)| (l.as_str(), w.as_ref())).collect();
+        write_gauges(&mut out, &samples, provider);
+
+        let stats = client.stats();
+
+        let _ = writeln!(out, "# HELP weather_api_requests_total Requests sent to the weather provider.");
+        let _ = writeln!(out, "# TYPE weather_api_requests_total counter");
+        let _ = writeln!(out, "weather_api_requests_total{{provider=\"{}\"}} {}", provider, stats.requests());
+
+        let _ = writeln!(out, "# HELP weather_api_errors_total Failed provider requests by kind.");
+        let _ = writeln!(out, "# TYPE weather_api_errors_total counter");
+        for kind in ErrorKind::ALL {
+            let _ = writeln!(
+                out,
+                "weather_api_errors_total{{provider=\"{}\",kind=\"{}\"}} {}",
+                provider,
+                kind.as_str(),
+                stats.errors(kind)
+            );
+        }
+
+        let _ = writeln!(out, "# HELP weather_cache_hits_total Observations served from cache instead of the provider.");
+        let _ = writeln!(out, "# TYPE weather_cache_hits_total counter");
+        let _ = writeln!(out, "weather_cache_hits_total {}", stats.cache_hits());
+
+        let _ = writeln!(out, "# HELP weather_api_last_success_timestamp_seconds Unix time of the last successful provider request.");
+        let _ = writeln!(out, "# TYPE weather_api_last_success_timestamp_seconds gauge");
+        let _ = writeln!(
+            out,
+            "weather_api_last_success_timestamp_seconds{{provider=\"{}\"}} {}",
+            provider,
+            stats.last_success()
+        );
+
+        out
+    }
+}
+
+/// The per-location weather gauges in the Prometheus text format, without
+/// the provider counters that need a live client. Samples are keyed by the
+/// location as it was requested, which must be unique: two aliases can
+/// resolve to the same city.
+pub fn write_gauges(out: &mut String, samples: &[(&str, &WeatherResponse)], provider: &str) {
+    let gauges: [Gauge; 9] = [
+        ("weather_temperature_celsius", "Current temperature in degrees Celsius.",
+            |w| Some(w.main.temp - 273.15)),
+        ("weather_feels_like_celsius", "Perceived temperature in degrees Celsius.",
+            |w| Some(w.main.feels_like - 273.15)),
+        ("weather_humidity_percent", "Relative humidity in percent.",
+            |w| Some(w.main.humidity as f64)),
+        ("weather_pressure_hpa", "Atmospheric pressure at sea level in hPa.",
+            |w| Some(w.main.pressure as f64)),
+        ("weather_wind_speed_meters_per_second", "Wind speed in m/s.",
+            |w| Some(w.wind.speed)),
+        ("weather_wind_gust_meters_per_second", "Wind gust speed in m/s.",
+            |w| w.wind.gust),
+        ("weather_wind_direction_degrees", "Meteorological wind direction in degrees.",
+            |w| Some(w.wind.deg as f64)),
+        ("weather_cloudiness_percent", "Cloud cover in percent.",
+            |w| Some(w.clouds.all as f64)),
+        ("weather_visibility_meters", "Visibility in meters.",
+            |w| w.visibility.map(|v| v as f64)),
+    ];
+
+    for (name, help, value) in gauges {
+        let _ = writeln!(out, "# HELP {} {}", name, help);
+        let _ = writeln!(out, "# TYPE {} gauge", name);
+        for (location, weather) in samples {
+            if let Some(v) = value(weather) {
+                let _ = writeln!(
+                    out,
+                    "{}{{location=\"{}\",city=\"{}\",country=\"{}\",provider=\"{}\"}} {}",
+                    name,
+                    escape_label(location),
+                    escape_label(&weather.name),
+                    escape_label(&weather.sys.country),
+                    provider,
+                    v
+                );
+            }
+        }
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::clients::WeatherApiClient;
+
+    #[test]
+    fn test_render_keeps_one_series_per_location() {
+        let client = WeatherApiClient::with_api_key("test").unwrap();
+        let cache = Arc::new(CachedWeatherClient::new(client, Duration::from_secs(300)).unwrap());
+        // An alias and the city it points at both come back as "Berlin"
+        for location in ["home", "Berlin"] {
+            let weather = serde_json::from_str(include_str!("../../tests/fixtures/berlin.json")).unwrap();
+            cache.insert(location, weather);
+        }
+
+        let collector = MetricsCollector::new(cache, vec!["home".into(), "Berlin".into(), "berlin".into()]);
+        collector.refresh();
+        let out = collector.render();
+
+        let temperatures: Vec<&str> = out.lines().filter(|l| l.starts_with("weather_temperature_celsius{")).collect();
+        assert_eq!(temperatures.len(), 2, "{}", out);
+        assert!(temperatures[0].starts_with(r#"weather_temperature_celsius{location="home",city="Berlin""#));
+        assert!(temperatures[1].starts_with(r#"weather_temperature_celsius{location="Berlin",city="Berlin""#));
+        assert!(out.contains("weather_cache_hits_total 2\n"), "{}", out);
+    }
+
+    #[test]
+    fn test_escape_label() {
+        assert_eq!(escape_label("Saint \"Pierre\""), r#"Saint \"Pierre\""#);
+        assert_eq!(escape_label(r"C:\path"), r"C:\\path");
+        assert_eq!(escape_label("two\nlines"), r"two\nlines");
+    }
+}
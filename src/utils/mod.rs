@@ -1,5 +1,18 @@
 pub mod formatters_new;
 pub use formatters_new as formatters;
+pub mod art;
+pub mod charts;
+pub mod comparison;
+pub mod condition;
 pub mod display_templates;
+pub mod export;
+pub mod glyphs;
+pub mod layout;
+pub mod metrics;
+pub mod rules;
+pub mod term_charts;
 pub mod web;
+#[cfg(feature = "web")]
 pub mod server;
+pub mod themes;
+pub mod widget;
@@ -0,0 +1,415 @@
+use crate::models::forecast::ForecastItem;
+use crate::models::weather::{TemperatureUnit, WeatherResponse};
+use crate::utils::condition::ConditionGroup;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// A value that rules can compare against a threshold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Metric {
+    Temp,
+    FeelsLike,
+    Humidity,
+    Wind,
+    Gust,
+    Pressure,
+    Clouds,
+    Visibility,
+    /// Rain plus snow volume, mm per 3 hours (forecast only)
+    Precipitation,
+    /// Probability of precipitation, percent (forecast only)
+    Pop,
+}
+
+impl Metric {
+    pub const ALL: [Metric; 10] = [
+        Metric::Temp,
+        Metric::FeelsLike,
+        Metric::Humidity,
+        Metric::Wind,
+        Metric::Gust,
+        Metric::Pressure,
+        Metric::Clouds,
+        Metric::Visibility,
+        Metric::Precipitation,
+        Metric::Pop,
+    ];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().replace('-', "_").as_str() {
+            "temp" | "temperature" => Some(Metric::Temp),
+            "feels_like" | "feels" => Some(Metric::FeelsLike),
+            "humidity" => Some(Metric::Humidity),
+            "wind" | "wind_speed" => Some(Metric::Wind),
+            "gust" | "wind_gust" => Some(Metric::Gust),
+            "pressure" => Some(Metric::Pressure),
+            "clouds" | "cloudiness" => Some(Metric::Clouds),
+            "visibility" => Some(Metric::Visibility),
+            "precipitation" | "precip" => Some(Metric::Precipitation),
+            "pop" => Some(Metric::Pop),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Metric::Temp => "temp",
+            Metric::FeelsLike => "feels_like",
+            Metric::Humidity => "humidity",
+            Metric::Wind => "wind",
+            Metric::Gust => "gust",
+            Metric::Pressure => "pressure",
+            Metric::Clouds => "clouds",
+            Metric::Visibility => "visibility",
+            Metric::Precipitation => "precipitation",
+            Metric::Pop => "pop",
+        }
+    }
+
+    fn suffix(&self, unit: &TemperatureUnit) -> String {
+        match self {
+            Metric::Temp | Metric::FeelsLike => unit.to_string(),
+            Metric::Humidity | Metric::Clouds | Metric::Pop => "%".to_string(),
+            Metric::Wind | Metric::Gust => " m/s".to_string(),
+            Metric::Pressure => " hPa".to_string(),
+            Metric::Visibility => " m".to_string(),
+            Metric::Precipitation => " mm".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Comparator {
+    #[serde(rename = ">", alias = "above")]
+    Above,
+    #[serde(rename = "<", alias = "below")]
+    Below,
+}
+
+impl Comparator {
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Comparator::Above => ">",
+            Comparator::Below => "<",
+        }
+    }
+
+    fn compare(&self, observed: f64, threshold: f64) -> bool {
+        match self {
+            Comparator::Above => observed > threshold,
+            Comparator::Below => observed < threshold,
+        }
+    }
+}
+
+/// One point in time to evaluate rules against: the current observation or
+/// a forecast slot. Temperatures are kept in Kelvin like the API models.
+#[derive(Debug, Clone)]
+pub struct Observation {
+    /// Forecast time (UTC seconds), `None` for the current observation
+    pub time: Option<u64>,
+    pub temp: f64,
+    pub feels_like: f64,
+    pub humidity: u32,
+    pub wind: f64,
+    pub gust: Option<f64>,
+    pub pressure: u32,
+    pub clouds: u32,
+    pub visibility: Option<u32>,
+    /// Rain and snow, mm (forecast only)
+    pub precipitation: Option<f64>,
+    /// Rain alone, mm (forecast only)
+    pub rain: Option<f64>,
+    pub pop: Option<f64>,
+    pub condition_ids: Vec<u32>,
+}
+
+impl From<&WeatherResponse> for Observation {
+    fn from(weather: &WeatherResponse) -> Self {
+        Self {
+            time: None,
+            temp: weather.main.temp,
+            feels_like: weather.main.feels_like,
+            humidity: weather.main.humidity,
+            wind: weather.wind.speed,
+            gust: weather.wind.gust,
+            pressure: weather.main.pressure,
+            clouds: weather.clouds.all,
+            visibility: weather.visibility,
+            precipitation: None,
+            rain: None,
+            pop: None,
+            condition_ids: weather.weather.iter().map(|w| w.id).collect(),
+        }
+    }
+}
+
+impl From<&ForecastItem> for Observation {
+    fn from(item: &ForecastItem) -> Self {
+        Self {
+            time: Some(item.dt),
+            temp: item.main.temp,
+            feels_like: item.main.feels_like,
+            humidity: item.main.humidity,
+            wind: item.wind.speed,
+            gust: item.wind.gust,
+            pressure: item.main.pressure,
+            clouds: item.clouds.all,
+            visibility: item.visibility,
+            precipitation: Some(item.precipitation()),
+            rain: Some(item.rain.as_ref().map_or(0.0, |r| r.three_hours)),
+            pop: Some(item.pop * 100.0),
+            condition_ids: item.weather.iter().map(|w| w.id).collect(),
+        }
+    }
+}
+
+impl Observation {
+    /// Value of `metric`, with temperatures in `unit`. `None` when this kind
+    /// of observation does not report the metric.
+    pub fn value(&self, metric: Metric, unit: &TemperatureUnit) -> Option<f64> {
+        match metric {
+            Metric::Temp => Some(unit.convert(self.temp)),
+            Metric::FeelsLike => Some(unit.convert(self.feels_like)),
+            Metric::Humidity => Some(self.humidity as f64),
+            Metric::Wind => Some(self.wind),
+            Metric::Gust => self.gust,
+            Metric::Pressure => Some(self.pressure as f64),
+            Metric::Clouds => Some(self.clouds as f64),
+            Metric::Visibility => self.visibility.map(f64::from),
+            Metric::Precipitation => self.precipitation,
+            Metric::Pop => self.pop,
+        }
+    }
+
+    pub fn groups(&self) -> Vec<ConditionGroup> {
+        self.condition_ids.iter().filter_map(|id| ConditionGroup::of(*id)).collect()
+    }
+}
+
+/// A single check, e.g. `temp > 30` or `condition = thunderstorm`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Rule {
+    Threshold {
+        metric: Metric,
+        comparator: Comparator,
+        value: f64,
+    },
+    Condition(ConditionGroup),
+}
+
+impl Rule {
+    /// What was observed when the rule matched, or `None` if it did not.
+    pub fn evaluate(&self, observation: &Observation, unit: &TemperatureUnit) -> Option<String> {
+        match self {
+            Rule::Threshold { metric, comparator, value } => {
+                let observed = observation.value(*metric, unit)?;
+                comparator
+                    .compare(observed, *value)
+                    .then(|| format!("{:.1}{}", observed, metric.suffix(unit)))
+            }
+            Rule::Condition(group) => {
+                let groups = observation.groups();
+                // Anyone asking about rain wants to hear about drizzle too
+                let rainy = *group == ConditionGroup::Rain
+                    && (groups.contains(&ConditionGroup::Drizzle)
+                        || observation.rain.is_some_and(|mm| mm > 0.0));
+                (groups.contains(group) || rainy).then(|| group.name().to_string())
+            }
+        }
+    }
+
+    /// The observed value the rule looks at, whether or not it matches.
+    pub fn observed(&self, observation: &Observation, unit: &TemperatureUnit) -> String {
+        match self {
+            Rule::Threshold { metric, .. } => observation
+                .value(*metric, unit)
+                .map_or("n/a".to_string(), |v| format!("{:.1}{}", v, metric.suffix(unit))),
+            Rule::Condition(_) => {
+                let names: Vec<_> = observation.groups().iter().map(|g| g.name()).collect();
+                names.join(", ")
+            }
+        }
+    }
+
+    pub fn describe(&self, unit: &TemperatureUnit) -> String {
+        match self {
+            Rule::Threshold { metric, comparator, value } => format!(
+                "{} {} {}{}",
+                metric.name(),
+                comparator.symbol(),
+                value,
+                metric.suffix(unit)
+            ),
+            Rule::Condition(group) => format!("condition = {}", group.name()),
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rule::Threshold { metric, comparator, value } => {
+                write!(f, "{}{}{}", metric.name(), comparator.symbol(), value)
+            }
+            Rule::Condition(group) => write!(f, "condition={}", group.name()),
+        }
+    }
+}
+
+/// Parses `temp>30`, `wind < 2.5` or `condition=thunderstorm`.
+impl FromStr for Rule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some((key, value)) = s.split_once('=') {
+            if key.trim().eq_ignore_ascii_case("condition") {
+                return value.parse().map(Rule::Condition);
+            }
+        }
+
+        let (metric, comparator, value) = if let Some((m, v)) = s.split_once('>') {
+            (m, Comparator::Above, v)
+        } else if let Some((m, v)) = s.split_once('<') {
+            (m, Comparator::Below, v)
+        } else {
+            return Err(format!("Invalid rule '{}'. Use METRIC>VALUE, METRIC<VALUE or condition=NAME", s));
+        };
+
+        let metric = Metric::from_name(metric).ok_or_else(|| {
+            let names: Vec<_> = Metric::ALL.iter().map(|m| m.name()).collect();
+            format!("Unknown metric '{}'. Available: {}", metric.trim(), names.join(", "))
+        })?;
+        let value = value
+            .trim()
+            .parse()
+            .map_err(|_| format!("Invalid number '{}' in rule '{}'", value.trim(), s))?;
+
+        Ok(Rule::Threshold { metric, comparator, value })
+    }
+}
+
+/// First observation (in order) that matches `rule`, with what was observed.
+pub fn first_match<'a>(
+    rule: &Rule,
+    observations: &'a [Observation],
+    unit: &TemperatureUnit,
+) -> Option<(&'a Observation, String)> {
+    observations
+        .iter()
+        .find_map(|o| rule.evaluate(o, unit).map(|observed| (o, observed)))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn observation(temp_c: f64, condition_ids: Vec<u32>) -> Observation {
+        Observation {
+            time: None,
+            temp: temp_c + 273.15,
+            feels_like: temp_c + 273.15,
+            humidity: 80,
+            wind: 4.0,
+            gust: None,
+            pressure: 1012,
+            clouds: 75,
+            visibility: Some(10000),
+            precipitation: None,
+            rain: None,
+            pop: None,
+            condition_ids,
+        }
+    }
+
+    #[test]
+    fn test_parse_rules() {
+        assert_eq!(
+            "temp>30".parse::<Rule>().unwrap(),
+            Rule::Threshold { metric: Metric::Temp, comparator: Comparator::Above, value: 30.0 }
+        );
+        assert_eq!(
+            " wind-speed < 2.5 ".parse::<Rule>().unwrap(),
+            Rule::Threshold { metric: Metric::Wind, comparator: Comparator::Below, value: 2.5 }
+        );
+        assert_eq!("Condition=Storm".parse::<Rule>().unwrap(), Rule::Condition(ConditionGroup::Thunderstorm));
+        assert_eq!("pop>60".parse::<Rule>().unwrap().to_string(), "pop>60");
+
+        assert!("temp=30".parse::<Rule>().unwrap_err().starts_with("Invalid rule"));
+        assert!("heat>30".parse::<Rule>().unwrap_err().starts_with("Unknown metric 'heat'"));
+        assert!("temp>hot".parse::<Rule>().unwrap_err().starts_with("Invalid number 'hot'"));
+        assert!("condition=hail".parse::<Rule>().unwrap_err().starts_with("Unknown condition 'hail'"));
+    }
+
+    #[test]
+    fn test_comparators_are_strict() {
+        let windy = observation(20.0, vec![800]);
+        let above = |value| Rule::Threshold { metric: Metric::Wind, comparator: Comparator::Above, value };
+        let below = |value| Rule::Threshold { metric: Metric::Wind, comparator: Comparator::Below, value };
+        let unit = TemperatureUnit::Celsius;
+
+        assert_eq!(above(3.5).evaluate(&windy, &unit).as_deref(), Some("4.0 m/s"));
+        assert_eq!(above(4.0).evaluate(&windy, &unit), None);
+        assert_eq!(below(4.0).evaluate(&windy, &unit), None);
+        assert_eq!(below(4.5).evaluate(&windy, &unit).as_deref(), Some("4.0 m/s"));
+
+        // Metrics an observation does not report never match
+        let gusts: Rule = "gust>0".parse().unwrap();
+        assert_eq!(gusts.evaluate(&windy, &unit), None);
+        assert_eq!(gusts.observed(&windy, &unit), "n/a");
+    }
+
+    #[test]
+    fn test_temperatures_use_the_unit() {
+        let warm = observation(30.0, vec![800]);
+        let rule: Rule = "temp>80".parse().unwrap();
+
+        assert_eq!(rule.evaluate(&warm, &TemperatureUnit::Celsius), None);
+        assert_eq!(rule.evaluate(&warm, &TemperatureUnit::Fahrenheit).as_deref(), Some("86.0°F"));
+        assert_eq!(rule.describe(&TemperatureUnit::Kelvin), "temp > 80K");
+        // Only temperatures convert
+        let humid: Rule = "humidity>70".parse().unwrap();
+        assert_eq!(humid.evaluate(&warm, &TemperatureUnit::Fahrenheit).as_deref(), Some("80.0%"));
+    }
+
+    #[test]
+    fn test_condition_groups() {
+        assert_eq!(ConditionGroup::of(232), Some(ConditionGroup::Thunderstorm));
+        assert_eq!(ConditionGroup::of(321), Some(ConditionGroup::Drizzle));
+        assert_eq!(ConditionGroup::of(781), Some(ConditionGroup::Mist));
+        assert_eq!(ConditionGroup::of(800), Some(ConditionGroup::Clear));
+        assert_eq!(ConditionGroup::of(804), Some(ConditionGroup::Clouds));
+        assert_eq!(ConditionGroup::of(400), None);
+
+        let unit = TemperatureUnit::Celsius;
+        let rain = Rule::Condition(ConditionGroup::Rain);
+        let drizzle = observation(12.0, vec![301, 701]);
+        assert_eq!(rain.evaluate(&drizzle, &unit).as_deref(), Some("rain"));
+        assert_eq!(rain.observed(&drizzle, &unit), "drizzle, mist");
+        assert_eq!(Rule::Condition(ConditionGroup::Drizzle).evaluate(&observation(12.0, vec![500]), &unit), None);
+
+        // Forecast slots with a rain volume count as rain
+        let mut wet = observation(2.0, vec![804]);
+        assert_eq!(rain.evaluate(&wet, &unit), None);
+        wet.precipitation = Some(0.4);
+        wet.rain = Some(0.4);
+        assert_eq!(rain.evaluate(&wet, &unit).as_deref(), Some("rain"));
+        assert_eq!(Rule::Condition(ConditionGroup::Snow).evaluate(&wet, &unit), None);
+    }
+
+    #[test]
+    fn test_snow_only_slot_is_not_rain() {
+        let unit = TemperatureUnit::Celsius;
+        let mut snowy = observation(-2.0, vec![600]);
+        snowy.precipitation = Some(1.2);
+        snowy.rain = Some(0.0);
+
+        assert_eq!(Rule::Condition(ConditionGroup::Rain).evaluate(&snowy, &unit), None);
+        assert_eq!(Rule::Condition(ConditionGroup::Snow).evaluate(&snowy, &unit).as_deref(), Some("snow"));
+        let precipitation: Rule = "precipitation>1".parse().unwrap();
+        assert_eq!(precipitation.evaluate(&snowy, &unit).as_deref(), Some("1.2 mm"));
+    }
+}
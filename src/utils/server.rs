@@ -1,37 +1,447 @@
-use actix_web::{web, App, HttpResponse, HttpServer};
+use crate::api::cache::CachedWeatherClient;
+use crate::models::forecast::ForecastResponse;
+use crate::models::weather::{TemperatureUnit, WeatherResponse};
+use crate::utils::charts::{self, ChartMetric};
+use crate::utils::metrics::MetricsCollector;
+use crate::utils::themes::{Theme, ThemeRegistry};
+use crate::utils::web as page;
+use crate::utils::widget::{self, WidgetSize};
+use actix_web::{web, App, HttpRequest, HttpResponse, HttpResponseBuilder, HttpServer};
 use anyhow::Result;
+use serde::Deserialize;
+use std::net::{IpAddr, SocketAddr};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
 pub struct WeatherState {
-    pub html: String,
+    pub city: String,
+    pub weather: Arc<WeatherResponse>,
+    pub forecast: Option<ForecastResponse>,
+    pub unit: TemperatureUnit,
+    pub themes: ThemeRegistry,
 }
 
-pub async fn index(data: web::Data<Arc<Mutex<WeatherState>>>) -> HttpResponse {
+impl WeatherState {
+    /// Theme named in `?theme=`, or the configured default when absent.
+    fn theme(&self, name: Option<&str>) -> Result<Theme, String> {
+        match name {
+            Some(n) => self
+                .themes
+                .find(n)
+                .cloned()
+                .ok_or_else(|| format!("Unknown theme '{}'", n)),
+            None => Ok(self.themes.get(None).clone()),
+        }
+    }
+}
+
+/// Plain-text error response. Messages can echo query parameters, so they
+/// must never be sniffed as HTML.
+fn error(mut response: HttpResponseBuilder, message: impl Into<String>) -> HttpResponse {
+    response.content_type("text/plain; charset=utf-8").body(message.into())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PageQuery {
+    pub theme: Option<String>,
+}
+
+pub async fn index(
+    data: web::Data<Arc<Mutex<WeatherState>>>,
+    query: web::Query<PageQuery>,
+) -> HttpResponse {
     let state = data.lock().await;
+    let theme = match state.theme(query.theme.as_deref()) {
+        Ok(t) => t,
+        Err(e) => return error(HttpResponse::BadRequest(), e),
+    };
+
     HttpResponse::Ok()
         .content_type("text/html; charset=utf-8")
-        .body(state.html.clone())
+        .body(page::generate_html(&state.weather, &state.unit, state.forecast.as_ref(), &theme))
+}
+
+pub async fn chart(
+    data: web::Data<Arc<Mutex<WeatherState>>>,
+    metric: web::Path<String>,
+) -> HttpResponse {
+    let metric = match ChartMetric::from_name(&metric) {
+        Some(m) => m,
+        None => return error(HttpResponse::NotFound(), "Unknown chart metric"),
+    };
+
+    let state = data.lock().await;
+    match &state.forecast {
+        Some(forecast) => HttpResponse::Ok()
+            .content_type("image/svg+xml")
+            .body(charts::render_chart(metric, forecast, &state.unit)),
+        None => error(HttpResponse::NotFound(), "No forecast data available"),
+    }
+}
+
+/// Which host pages may embed the widget (frame-ancestors) or fetch the
+/// loader and oEmbed data cross-origin (CORS), and which cities it shows.
+#[derive(Debug, Clone, Default)]
+pub struct EmbedPolicy {
+    pub allowed_origins: Vec<String>,
+    /// Cities `?city=` may ask for; `None` lets any visitor spend the
+    /// provider quota on any place
+    pub widget_cities: Option<Vec<String>>,
+}
+
+impl EmbedPolicy {
+    fn allows_city(&self, city: &str) -> bool {
+        self.widget_cities
+            .as_ref()
+            .is_none_or(|cities| cities.iter().any(|c| c.eq_ignore_ascii_case(city)))
+    }
+
+    fn frame_ancestors(&self) -> String {
+        let mut sources = vec!["'self'".to_string()];
+        sources.extend(self.allowed_origins.iter().cloned());
+        format!("frame-ancestors {}", sources.join(" "))
+    }
+
+    fn allow_origin(&self, req: &HttpRequest) -> Option<String> {
+        let origin = req.headers().get("Origin")?.to_str().ok()?;
+        self.allowed_origins
+            .iter()
+            .find(|o| o.as_str() == "*" || o.trim_end_matches('/') == origin)
+            .map(|o| if o == "*" { "*".to_string() } else { origin.to_string() })
+    }
+
+    fn apply(&self, req: &HttpRequest, mut response: HttpResponse) -> HttpResponse {
+        let headers = response.headers_mut();
+        if let Ok(value) = self.frame_ancestors().parse() {
+            headers.insert(actix_web::http::header::CONTENT_SECURITY_POLICY, value);
+        }
+        if let Some(origin) = self.allow_origin(req).and_then(|o| o.parse().ok()) {
+            headers.insert(actix_web::http::header::ACCESS_CONTROL_ALLOW_ORIGIN, origin);
+            headers.insert(actix_web::http::header::VARY, actix_web::http::header::HeaderValue::from_static("Origin"));
+        }
+        response
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WidgetQuery {
+    pub city: Option<String>,
+    pub theme: Option<String>,
+    pub size: Option<String>,
+    pub unit: Option<String>,
+}
+
+pub async fn widget(
+    req: HttpRequest,
+    query: web::Query<WidgetQuery>,
+    data: web::Data<Arc<Mutex<WeatherState>>>,
+    cache: web::Data<Arc<CachedWeatherClient>>,
+    embed: web::Data<EmbedPolicy>,
+) -> HttpResponse {
+    let (default_city, default_unit, theme) = {
+        let state = data.lock().await;
+        match state.theme(query.theme.as_deref()) {
+            Ok(theme) => (state.city.clone(), state.unit, theme),
+            Err(e) => return error(HttpResponse::BadRequest(), e),
+        }
+    };
+
+    let size = match query.size.as_deref().map(WidgetSize::from_name) {
+        None => WidgetSize::Medium,
+        Some(Some(s)) => s,
+        Some(None) => return error(HttpResponse::BadRequest(), "Unknown size"),
+    };
+    let unit = match query.unit.as_deref().map(str::parse::<TemperatureUnit>) {
+        None => default_unit,
+        Some(Ok(u)) => u,
+        Some(Err(e)) => return error(HttpResponse::BadRequest(), e),
+    };
+    let city = query.city.clone().unwrap_or(default_city);
+    if !embed.allows_city(&city) {
+        return embed.apply(&req, error(HttpResponse::NotFound(), "Unknown city"));
+    }
+
+    let response = match cache.get_weather_async(&city).await {
+        Ok(weather) => HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(widget::generate_widget(&weather, &unit, &theme, size)),
+        Err(e) => {
+            // The error chain can name the place and the provider's reply;
+            // keep it in the server log
+            eprintln!("Widget lookup for '{}' failed: {}", city, e);
+            error(HttpResponse::BadGateway(), "Weather data is unavailable right now")
+        }
+    };
+
+    embed.apply(&req, response)
 }
 
-pub async fn start_server(html: String, port: u16) -> Result<()> {
-    let state = Arc::new(Mutex::new(WeatherState { html }));
+pub async fn widget_js(req: HttpRequest, embed: web::Data<EmbedPolicy>) -> HttpResponse {
+    let response = HttpResponse::Ok()
+        .content_type("application/javascript; charset=utf-8")
+        .body(widget::WIDGET_LOADER_JS);
+    embed.apply(&req, response)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OEmbedQuery {
+    pub url: String,
+    pub maxwidth: Option<u32>,
+    pub maxheight: Option<u32>,
+    pub format: Option<String>,
+}
+
+pub async fn oembed(
+    req: HttpRequest,
+    query: web::Query<OEmbedQuery>,
+    data: web::Data<Arc<Mutex<WeatherState>>>,
+    embed: web::Data<EmbedPolicy>,
+) -> HttpResponse {
+    if query.format.as_deref().is_some_and(|f| f != "json") {
+        return error(HttpResponse::NotImplemented(), "Only the json format is supported");
+    }
+
+    let url = match reqwest::Url::parse(&query.url) {
+        Ok(u) => u,
+        Err(_) => return error(HttpResponse::BadRequest(), "Invalid url"),
+    };
+    let base = {
+        let info = req.connection_info();
+        format!("{}://{}/widget", info.scheme(), info.host())
+    };
+    let mut widget_url = match reqwest::Url::parse(&base) {
+        Ok(u) => u,
+        Err(_) => return error(HttpResponse::BadRequest(), "Invalid Host header"),
+    };
+    // Only describe this server's own widget; the scheme may differ behind a proxy
+    let authority = |u: &reqwest::Url| (u.host_str().map(str::to_ascii_lowercase), u.port_or_known_default());
+    if url.path() != "/widget" || authority(&url) != authority(&widget_url) {
+        return embed.apply(&req, error(HttpResponse::NotFound(), "Not a widget of this server"));
+    }
+    let param = |name: &str| {
+        url.query_pairs()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.into_owned())
+    };
+
+    let city = match param("city") {
+        Some(c) => c,
+        None => data.lock().await.city.clone(),
+    };
+    if !embed.allows_city(&city) {
+        return embed.apply(&req, error(HttpResponse::NotFound(), "Unknown city"));
+    }
+
+    // Largest widget that fits the consumer's limits
+    let requested = param("size").and_then(|s| WidgetSize::from_name(&s)).unwrap_or(WidgetSize::Medium);
+    let size = [requested, WidgetSize::Medium, WidgetSize::Small]
+        .into_iter()
+        .find(|s| {
+            let (w, h) = s.dimensions();
+            query.maxwidth.is_none_or(|m| w <= m) && query.maxheight.is_none_or(|m| h <= m)
+        })
+        .unwrap_or(WidgetSize::Small);
+
+    {
+        let mut pairs = widget_url.query_pairs_mut();
+        pairs.append_pair("city", &city).append_pair("size", size.name());
+        for key in ["theme", "unit"] {
+            if let Some(value) = param(key) {
+                pairs.append_pair(key, &value);
+            }
+        }
+    }
+
+    let body = widget::oembed_json(widget_url.as_str(), &format!("Weather in {}", city), size);
+    let response = HttpResponse::Ok().json(body);
+    embed.apply(&req, response)
+}
+
+pub async fn metrics(collector: web::Data<Arc<MetricsCollector>>) -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type("text/plain; version=0.0.4; charset=utf-8")
+        .body(collector.render())
+}
+
+pub async fn start_server(
+    state: WeatherState,
+    bind: IpAddr,
+    port: u16,
+    cache: Arc<CachedWeatherClient>,
+    collector: Arc<MetricsCollector>,
+    embed: EmbedPolicy,
+) -> Result<()> {
+    let state = Arc::new(Mutex::new(state));
     let data = web::Data::new(state);
+    let cache = web::Data::new(cache);
+    let collector = web::Data::new(collector);
+    let embed = web::Data::new(embed);
 
-    println!("🌐 Starting web server on http://localhost:{}", port);
+    let url = if bind.is_loopback() || bind.is_unspecified() {
+        format!("http://localhost:{}", port)
+    } else {
+        format!("http://{}", SocketAddr::new(bind, port))
+    };
+    println!("🌐 Starting web server on {}", url);
+    println!("📈 Metrics available at {}/metrics", url);
+    println!("🧩 Widget available at {}/widget", url);
     println!("🔗 Opening browser...\n");
 
-    let url = format!("http://localhost:{}", port);
     let _ = webbrowser::open(&url);
 
     HttpServer::new(move || {
         App::new()
             .app_data(data.clone())
+            .app_data(cache.clone())
+            .app_data(collector.clone())
+            .app_data(embed.clone())
             .route("/", web::get().to(index))
+            .route("/chart/{metric}.svg", web::get().to(chart))
+            .route("/metrics", web::get().to(metrics))
+            .route("/widget", web::get().to(widget))
+            .route("/widget.js", web::get().to(widget_js))
+            .route("/oembed", web::get().to(oembed))
     })
-    .bind(("127.0.0.1", port))?
+    .bind((bind, port))?
     .run()
     .await?;
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::http::{header, StatusCode};
+    use actix_web::test;
+
+    fn berlin() -> WeatherResponse {
+        serde_json::from_str(include_str!("../../tests/fixtures/berlin.json")).unwrap()
+    }
+
+    fn state() -> web::Data<Arc<Mutex<WeatherState>>> {
+        let weather = berlin();
+        web::Data::new(Arc::new(Mutex::new(WeatherState {
+            city: "Berlin".to_string(),
+            weather: Arc::new(weather),
+            forecast: None,
+            unit: TemperatureUnit::Celsius,
+            themes: ThemeRegistry::default(),
+        })))
+    }
+
+    #[actix_web::test]
+    async fn test_bad_requests_get_plain_text_errors() {
+        let app = test::init_service(
+            App::new()
+                .app_data(state())
+                .app_data(web::Data::new(EmbedPolicy::default()))
+                .route("/", web::get().to(index))
+                .route("/oembed", web::get().to(oembed)),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/oembed?url=http%3A%2F%2Flocalhost%2Fwidget")
+            .insert_header((header::HOST, "a b"))
+            .to_request();
+        let response = test::call_service(&app, req).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(test::read_body(response).await, "Invalid Host header");
+
+        let req = test::TestRequest::get().uri("/?theme=%3Cscript%3E").to_request();
+        let response = test::call_service(&app, req).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(response.headers().get(header::CONTENT_TYPE).unwrap(), "text/plain; charset=utf-8");
+    }
+
+    fn policy() -> web::Data<EmbedPolicy> {
+        web::Data::new(EmbedPolicy {
+            allowed_origins: vec!["https://intranet.example.com/".to_string()],
+            widget_cities: Some(vec!["Berlin".to_string()]),
+        })
+    }
+
+    #[std::prelude::v1::test]
+    fn test_widget_serves_listed_cities_with_embed_headers() {
+        // The blocking client inside must be created and dropped outside the
+        // runtime; seeded so the listed city never reaches the provider
+        let cache = Arc::new(
+            CachedWeatherClient::new(
+                crate::api::clients::WeatherApiClient::with_api_key("test").unwrap(),
+                std::time::Duration::from_secs(300),
+            )
+            .unwrap(),
+        );
+        cache.insert("berlin", berlin());
+        actix_web::rt::System::new().block_on(widget_requests(Arc::clone(&cache)));
+    }
+
+    async fn widget_requests(cache: Arc<CachedWeatherClient>) {
+        let app = test::init_service(
+            App::new()
+                .app_data(state())
+                .app_data(web::Data::new(cache))
+                .app_data(policy())
+                .route("/widget", web::get().to(widget)),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/widget?city=berlin&size=small")
+            .insert_header((header::ORIGIN, "https://intranet.example.com"))
+            .to_request();
+        let response = test::call_service(&app, req).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let headers = response.headers();
+        assert_eq!(
+            headers.get(header::CONTENT_SECURITY_POLICY).unwrap(),
+            "frame-ancestors 'self' https://intranet.example.com/"
+        );
+        assert_eq!(headers.get(header::ACCESS_CONTROL_ALLOW_ORIGIN).unwrap(), "https://intranet.example.com");
+        assert_eq!(headers.get(header::VARY).unwrap(), "Origin");
+
+        let req = test::TestRequest::get()
+            .uri("/widget?city=Paris")
+            .insert_header((header::ORIGIN, "https://elsewhere.example.com"))
+            .to_request();
+        let response = test::call_service(&app, req).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert!(response.headers().get(header::CONTENT_SECURITY_POLICY).is_some());
+        assert!(response.headers().get(header::ACCESS_CONTROL_ALLOW_ORIGIN).is_none());
+    }
+
+    #[actix_web::test]
+    async fn test_oembed_only_describes_own_widget() {
+        let app = test::init_service(
+            App::new()
+                .app_data(state())
+                .app_data(policy())
+                .route("/oembed", web::get().to(oembed)),
+        )
+        .await;
+        let oembed_for = |url: &str| {
+            test::TestRequest::get()
+                .uri(&format!("/oembed?url={}&maxwidth=250", url))
+                .insert_header((header::HOST, "localhost:8080"))
+                .to_request()
+        };
+
+        let response = test::call_service(&app, oembed_for("http%3A%2F%2Flocalhost%3A8080%2Fwidget%3Fsize%3Dlarge")).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: serde_json::Value = test::read_body_json(response).await;
+        assert!(body["html"].as_str().unwrap().contains("http://localhost:8080/widget?city=Berlin&amp;size=small"), "{}", body);
+
+        for foreign in [
+            "http%3A%2F%2Fevil.example%2Fwidget",
+            "http%3A%2F%2Flocalhost%3A9090%2Fwidget",
+            "http%3A%2F%2Flocalhost%3A8080%2Fmetrics",
+        ] {
+            let response = test::call_service(&app, oembed_for(foreign)).await;
+            assert_eq!(response.status(), StatusCode::NOT_FOUND, "{}", foreign);
+        }
+
+        let response = test::call_service(&app, oembed_for("http%3A%2F%2Flocalhost%3A8080%2Fwidget%3Fcity%3DParis")).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
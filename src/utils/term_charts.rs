@@ -0,0 +1,319 @@
+//! Unicode charts for the terminal. The SVG charts for web pages and exports
+//! live in `charts.rs`.
+
+use crate::history::Record;
+use crate::models::forecast::ForecastResponse;
+use crate::utils::formatters::format_day_time;
+
+const SPARK_LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+const BLOCK_EIGHTHS: [char; 9] = [' ', '▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+/// Sparkline levels by ink density, for terminals without Unicode.
+const ASCII_LEVELS: [char; 8] = ['.', ',', ':', '-', '=', '+', '*', '#'];
+
+/// Braille dot bits for (column, row) inside one 2x4 character cell.
+const BRAILLE_DOTS: [[u32; 4]; 2] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ChartStyle {
+    /// Thin line drawn with braille dots, 2x4 points per character
+    Braille,
+    /// Filled columns of block elements, 8 levels per character
+    Block,
+    /// One `*` per column with ASCII axes, for terminals without Unicode
+    Ascii,
+}
+
+/// One sample of the values the terminal charts show.
+#[derive(Debug, Clone, Copy)]
+pub struct TrendPoint {
+    /// UTC seconds
+    pub time: u64,
+    /// Kelvin, like the API models
+    pub temp: f64,
+    pub pressure: f64,
+    pub humidity: f64,
+}
+
+/// Temperature, pressure and humidity over the forecast horizon or a stretch
+/// of recorded history.
+#[derive(Debug, Clone)]
+pub struct Trend {
+    /// Shown next to the charts, e.g. "next 5 days"
+    pub label: String,
+    pub points: Vec<TrendPoint>,
+    /// Offset from UTC in seconds for axis labels
+    pub timezone: i32,
+}
+
+impl Trend {
+    pub fn from_forecast(forecast: &ForecastResponse) -> Self {
+        Self {
+            label: "next 5 days".to_string(),
+            points: forecast
+                .list
+                .iter()
+                .map(|item| TrendPoint {
+                    time: item.dt,
+                    temp: item.main.temp,
+                    pressure: item.main.pressure as f64,
+                    humidity: item.main.humidity as f64,
+                })
+                .collect(),
+            timezone: forecast.city.timezone,
+        }
+    }
+
+    pub fn from_history(records: &[Record], label: &str, timezone: i32) -> Self {
+        Self {
+            label: label.to_string(),
+            points: records
+                .iter()
+                .map(|record| TrendPoint {
+                    time: record.observed_at,
+                    temp: record.temp,
+                    pressure: record.pressure as f64,
+                    humidity: record.humidity as f64,
+                })
+                .collect(),
+            timezone,
+        }
+    }
+
+    /// A chart needs at least two points in time.
+    pub fn is_plottable(&self) -> bool {
+        self.points.len() >= 2
+    }
+
+    pub fn series(&self, value: impl Fn(&TrendPoint) -> f64) -> Vec<(u64, f64)> {
+        self.points.iter().map(|p| (p.time, value(p))).collect()
+    }
+
+    /// "Mon 12:00" labels for the first and last point.
+    pub fn span_labels(&self) -> Option<(String, String)> {
+        let first = self.points.first()?;
+        let last = self.points.last()?;
+        Some((format_day_time(first.time, self.timezone), format_day_time(last.time, self.timezone)))
+    }
+}
+
+/// Average `points` into `width` equal time buckets. Empty buckets (gaps in
+/// recorded history) repeat the previous value so lines stay continuous.
+pub fn resample(points: &[(u64, f64)], width: usize) -> Vec<f64> {
+    let (Some(first), Some(last)) = (points.first(), points.last()) else {
+        return Vec::new();
+    };
+    if width == 0 {
+        return Vec::new();
+    }
+
+    let span = last.0.saturating_sub(first.0).max(1) as f64;
+    let mut sums = vec![(0.0, 0usize); width];
+    for (time, value) in points {
+        let position = (time.saturating_sub(first.0) as f64 / span * (width - 1) as f64).round() as usize;
+        let bucket = &mut sums[position.min(width - 1)];
+        bucket.0 += value;
+        bucket.1 += 1;
+    }
+
+    let mut previous = first.1;
+    sums.into_iter()
+        .map(|(sum, count)| {
+            if count > 0 {
+                previous = sum / count as f64;
+            }
+            previous
+        })
+        .collect()
+}
+
+fn bounds(values: &[f64]) -> (f64, f64) {
+    let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    (min, max)
+}
+
+/// Position of `value` between `min` and `max` as 0.0..=1.0; flat series sit
+/// in the middle.
+fn level(value: f64, min: f64, max: f64) -> f64 {
+    if (max - min).abs() < f64::EPSILON {
+        0.5
+    } else {
+        ((value - min) / (max - min)).clamp(0.0, 1.0)
+    }
+}
+
+/// Inline sparkline such as `▁▂▄▆█▆▄▂`, `width` characters wide.
+///
+/// ```
+/// use weather_cl::utils::term_charts::sparkline;
+///
+/// let pressure = [(0, 1008.0), (3600, 1012.0), (7200, 1016.0), (10800, 1012.0)];
+/// assert_eq!(sparkline(&pressure, 4), "▁▅█▅");
+/// ```
+pub fn sparkline(points: &[(u64, f64)], width: usize) -> String {
+    spark(points, width, &SPARK_LEVELS)
+}
+
+/// [`sparkline`] in plain ASCII, such as `.-=#=-.`.
+///
+/// ```
+/// use weather_cl::utils::term_charts::ascii_sparkline;
+///
+/// let pressure = [(0, 1008.0), (3600, 1012.0), (7200, 1016.0), (10800, 1012.0)];
+/// assert_eq!(ascii_sparkline(&pressure, 4), ".=#=");
+/// ```
+pub fn ascii_sparkline(points: &[(u64, f64)], width: usize) -> String {
+    spark(points, width, &ASCII_LEVELS)
+}
+
+fn spark(points: &[(u64, f64)], width: usize, levels: &[char; 8]) -> String {
+    let values = resample(points, width);
+    let (min, max) = bounds(&values);
+    values
+        .iter()
+        .map(|v| levels[(level(*v, min, max) * 7.0).round() as usize])
+        .collect()
+}
+
+/// Multi-line chart with min/max labels on the left and, given a `span`,
+/// start and end times underneath. `width` and `height` are the plot area in
+/// characters, labels come on top of that.
+pub fn line_chart(
+    points: &[(u64, f64)],
+    width: usize,
+    height: usize,
+    style: ChartStyle,
+    label: impl Fn(f64) -> String,
+    span: Option<&(String, String)>,
+) -> Vec<String> {
+    let height = height.max(2);
+    let columns = match style {
+        ChartStyle::Braille => width * 2,
+        ChartStyle::Block | ChartStyle::Ascii => width,
+    };
+    let values = resample(points, columns);
+    if values.is_empty() {
+        return Vec::new();
+    }
+    let (min, max) = bounds(&values);
+
+    let rows = match style {
+        ChartStyle::Braille => braille_rows(&values, width, height, min, max),
+        ChartStyle::Block => block_rows(&values, height, min, max),
+        ChartStyle::Ascii => ascii_rows(&values, height, min, max),
+    };
+    let (tick, corner) = match style {
+        ChartStyle::Ascii => ('|', '+'),
+        _ => ('┤', '└'),
+    };
+
+    let top = label(max);
+    let bottom = label(min);
+    let margin = top.chars().count().max(bottom.chars().count());
+    let mut lines: Vec<String> = rows
+        .into_iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let axis = match i {
+                0 => top.as_str(),
+                i if i == height - 1 => bottom.as_str(),
+                _ => "",
+            };
+            format!("{:>margin$} {}{}", axis, tick, row)
+        })
+        .collect();
+
+    if let Some((start, end)) = span {
+        let used = start.chars().count() + end.chars().count();
+        let axis = if used < width {
+            format!("{}{}{}", start, " ".repeat(width - used), end)
+        } else {
+            start.clone()
+        };
+        lines.push(format!("{:margin$} {}{}", "", corner, axis));
+    }
+    lines
+}
+
+fn braille_rows(values: &[f64], width: usize, height: usize, min: f64, max: f64) -> Vec<String> {
+    let dots_high = height * 4;
+    let y = |v: f64| ((1.0 - level(v, min, max)) * (dots_high - 1) as f64).round() as usize;
+    let mut cells = vec![vec![0u32; width]; height];
+    let mut plot = |x: usize, y: usize| {
+        cells[y / 4][x / 2] |= BRAILLE_DOTS[x % 2][y % 4];
+    };
+
+    // Join neighbouring samples with vertical runs so steep changes stay
+    // connected instead of leaving isolated dots
+    for (x, value) in values.iter().enumerate() {
+        let current = y(*value);
+        let next = values.get(x + 1).map_or(current, |v| y(*v));
+        let (from, to) = if current <= next { (current, next) } else { (next, current) };
+        let midpoint = (from + to) / 2;
+        for row in from..=to {
+            let column = if row <= midpoint && current <= next || row > midpoint && current > next {
+                x
+            } else {
+                (x + 1).min(values.len() - 1)
+            };
+            plot(column, row);
+        }
+    }
+
+    cells
+        .into_iter()
+        .map(|row| row.into_iter().map(|bits| char::from_u32(0x2800 + bits).unwrap_or(' ')).collect())
+        .collect()
+}
+
+fn block_rows(values: &[f64], height: usize, min: f64, max: f64) -> Vec<String> {
+    let eighths: Vec<usize> = values
+        .iter()
+        .map(|v| (level(*v, min, max) * (height * 8 - 1) as f64).round() as usize + 1)
+        .collect();
+
+    (0..height)
+        .map(|row| {
+            let floor = (height - 1 - row) * 8;
+            eighths
+                .iter()
+                .map(|e| BLOCK_EIGHTHS[e.saturating_sub(floor).min(8)])
+                .collect()
+        })
+        .collect()
+}
+
+fn ascii_rows(values: &[f64], height: usize, min: f64, max: f64) -> Vec<String> {
+    let y = |v: f64| ((1.0 - level(v, min, max)) * (height - 1) as f64).round() as usize;
+    (0..height)
+        .map(|row| values.iter().map(|v| if y(*v) == row { '*' } else { ' ' }).collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sparkline_and_charts() {
+        let points: Vec<(u64, f64)> = (0..8).map(|i| (i * 3600, i as f64)).collect();
+        assert_eq!(sparkline(&points, 8), "▁▂▃▄▅▆▇█");
+        assert_eq!(sparkline(&[(0, 5.0), (60, 5.0)], 3), "▅▅▅");
+
+        let span = ("a".to_string(), "b".to_string());
+        let chart = line_chart(&points, 4, 2, ChartStyle::Braille, |v| format!("{:.0}", v), Some(&span));
+        assert_eq!(chart.len(), 3);
+        assert!(chart[0].starts_with("7 ┤"));
+        assert!(chart[1].starts_with("0 ┤"));
+        assert_eq!(chart[0].chars().count(), "7 ┤".chars().count() + 4);
+        assert_eq!(chart[2], "  └a  b");
+
+        let blocks = line_chart(&points, 8, 2, ChartStyle::Block, |v| format!("{:.0}", v), None);
+        assert_eq!(blocks, vec!["7 ┤    ▂▄▆█".to_string(), "0 ┤▁▃▅▇████".to_string()]);
+
+        assert_eq!(ascii_sparkline(&points, 8), ".,:-=+*#");
+        let ascii = line_chart(&points, 8, 3, ChartStyle::Ascii, |v| format!("{:.0}", v), Some(&span));
+        assert_eq!(ascii, vec!["7 |      **", "  |  ****  ", "0 |**      ", "  +a      b"]);
+    }
+}
@@ -0,0 +1,251 @@
+use crate::models::weather::WeatherResponse;
+use crate::utils::condition::{self, ConditionGroup};
+use anyhow::{anyhow, Result};
+use std::fs;
+use std::path::Path;
+
+/// A web theme is a block of CSS that sets the custom properties used by the
+/// page and widget stylesheets (`--page-bg`, `--card-bg`, `--text`, ...).
+#[derive(Debug, Clone)]
+pub struct Theme {
+    pub name: String,
+    pub css: String,
+    /// Whether condition/day-night backgrounds may replace `--page-bg`
+    pub condition_backgrounds: bool,
+}
+
+const LIGHT_VARS: &str = "
+    --page-bg: linear-gradient(135deg, #667eea 0%, #764ba2 100%);
+    --header-bg: linear-gradient(135deg, #667eea 0%, #764ba2 100%);
+    --header-text: white;
+    --card-bg: white;
+    --panel-bg: #f8f9fa;
+    --hero-bg: linear-gradient(to right, rgba(102, 126, 234, 0.1), rgba(118, 75, 162, 0.1));
+    --text: #2c3e50;
+    --muted: #7f8c8d;
+    --accent: #667eea;
+    --accent-2: #764ba2;
+    --border: #ecf0f1;
+    --footer-bg: #ecf0f1;
+    --sunrise-bg: linear-gradient(135deg, #ffd89b 0%, #ff6b6b 100%);
+    --sunset-bg: linear-gradient(135deg, #667eea 0%, #764ba2 100%);
+    --sun-text: white;
+    --shadow: 0 20px 60px rgba(0, 0, 0, 0.3);";
+
+const DARK_VARS: &str = "
+    --page-bg: linear-gradient(135deg, #141e30 0%, #243b55 100%);
+    --header-bg: linear-gradient(135deg, #30336b 0%, #4b2c5e 100%);
+    --header-text: #ecf0f1;
+    --card-bg: #1e1f2b;
+    --panel-bg: #272838;
+    --hero-bg: linear-gradient(to right, rgba(102, 126, 234, 0.15), rgba(118, 75, 162, 0.15));
+    --text: #ecf0f1;
+    --muted: #95a5a6;
+    --accent: #8c9eff;
+    --accent-2: #b388ff;
+    --border: #33354a;
+    --footer-bg: #191a24;
+    --sunrise-bg: linear-gradient(135deg, #b8860b 0%, #a83232 100%);
+    --sunset-bg: linear-gradient(135deg, #30336b 0%, #4b2c5e 100%);
+    --sun-text: #ecf0f1;
+    --shadow: 0 20px 60px rgba(0, 0, 0, 0.6);";
+
+const HIGH_CONTRAST_VARS: &str = "
+    --page-bg: #000000;
+    --header-bg: #000000;
+    --header-text: #ffffff;
+    --card-bg: #000000;
+    --panel-bg: #000000;
+    --hero-bg: #000000;
+    --text: #ffffff;
+    --muted: #ffff00;
+    --accent: #00ffff;
+    --accent-2: #00ffff;
+    --border: #ffffff;
+    --footer-bg: #000000;
+    --sunrise-bg: #000000;
+    --sunset-bg: #000000;
+    --sun-text: #ffff00;
+    --shadow: 0 0 0 2px #ffffff;";
+
+impl Theme {
+    pub fn light() -> Self {
+        Self::builtin("light", format!(":root {{{}\n}}", LIGHT_VARS), true)
+    }
+
+    pub fn dark() -> Self {
+        Self::builtin("dark", format!(":root {{{}\n}}", DARK_VARS), true)
+    }
+
+    pub fn high_contrast() -> Self {
+        Self::builtin("high-contrast", format!(":root {{{}\n}}", HIGH_CONTRAST_VARS), false)
+    }
+
+    /// Light by default, dark when the browser prefers a dark color scheme.
+    pub fn auto() -> Self {
+        Self::builtin(
+            "auto",
+            format!(
+                ":root {{{}\n}}\n@media (prefers-color-scheme: dark) {{\n:root {{{}\n}}\n}}",
+                LIGHT_VARS, DARK_VARS
+            ),
+            true,
+        )
+    }
+
+    fn builtin(name: &str, css: String, condition_backgrounds: bool) -> Self {
+        Self {
+            name: name.to_string(),
+            css,
+            condition_backgrounds,
+        }
+    }
+
+    /// Load a theme from a CSS file of custom properties; the theme is
+    /// named after the file stem (`ocean.css` -> `ocean`).
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let name = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .ok_or_else(|| anyhow!("Invalid theme file name: {}", path.display()))?;
+        let css = fs::read_to_string(path)?;
+
+        // HTML end tags are case-insensitive
+        if css.to_ascii_lowercase().contains("</style") {
+            return Err(anyhow!("Theme file {} must contain only CSS", path.display()));
+        }
+
+        Ok(Self {
+            name: name.to_lowercase(),
+            css,
+            condition_backgrounds: true,
+        })
+    }
+
+    /// Full stylesheet for this theme, including the condition backgrounds.
+    pub fn stylesheet(&self) -> String {
+        if self.condition_backgrounds {
+            format!("{}\n{}", self.css, CONDITION_CSS)
+        } else {
+            self.css.clone()
+        }
+    }
+}
+
+/// Built-in themes plus any loaded from a directory of `*.css` files.
+#[derive(Debug, Clone)]
+pub struct ThemeRegistry {
+    themes: Vec<Theme>,
+    default: String,
+}
+
+impl Default for ThemeRegistry {
+    fn default() -> Self {
+        Self {
+            themes: vec![Theme::auto(), Theme::light(), Theme::dark(), Theme::high_contrast()],
+            default: "auto".to_string(),
+        }
+    }
+}
+
+impl ThemeRegistry {
+    /// Add every `*.css` file in `dir`, replacing built-ins of the same name.
+    pub fn load_dir(&mut self, dir: &Path) -> Result<()> {
+        let mut paths: Vec<_> = fs::read_dir(dir)?
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| p.extension().is_some_and(|e| e.eq_ignore_ascii_case("css")))
+            .collect();
+        paths.sort();
+
+        for path in paths {
+            let theme = Theme::from_file(&path)?;
+            self.themes.retain(|t| t.name != theme.name);
+            self.themes.push(theme);
+        }
+        Ok(())
+    }
+
+    pub fn set_default(&mut self, name: &str) -> Result<()> {
+        if self.find(name).is_none() {
+            return Err(anyhow!(
+                "Unknown theme '{}'. Available themes: {}",
+                name,
+                self.names().join(", ")
+            ));
+        }
+        self.default = name.to_lowercase();
+        Ok(())
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.themes.iter().map(|t| t.name.as_str()).collect()
+    }
+
+    pub fn find(&self, name: &str) -> Option<&Theme> {
+        let name = name.to_lowercase();
+        self.themes.iter().find(|t| t.name == name)
+    }
+
+    /// Look up a theme by name, falling back to the default.
+    pub fn get(&self, name: Option<&str>) -> &Theme {
+        name.and_then(|n| self.find(n))
+            .or_else(|| self.find(&self.default))
+            .unwrap_or(&self.themes[0])
+    }
+}
+
+/// CSS classes describing the current condition group and time of day,
+/// e.g. `cond-rain night`, matched by the condition backgrounds.
+pub fn condition_classes(weather: &WeatherResponse) -> String {
+    let (id, icon) = weather
+        .weather
+        .first()
+        .map_or((800, "01d"), |w| (w.id, w.icon.as_str()));
+
+    let group = match ConditionGroup::of(id) {
+        // Few and scattered clouds get a lighter background
+        Some(ConditionGroup::Clouds) if id <= 802 => "partly-cloudy",
+        Some(ConditionGroup::Clouds) | None => "cloudy",
+        Some(group) => group.name(),
+    };
+    let period = if condition::is_night(icon) { "night" } else { "day" };
+
+    format!("cond-{} {}", group, period)
+}
+
+const CONDITION_CSS: &str = "
+body.cond-clear.day { --page-bg: linear-gradient(135deg, #56ccf2 0%, #2f80ed 100%); }
+body.cond-clear.night { --page-bg: linear-gradient(135deg, #0f2027 0%, #203a43 50%, #2c5364 100%); }
+body.cond-partly-cloudy.day { --page-bg: linear-gradient(135deg, #89c4f4 0%, #667eea 100%); }
+body.cond-partly-cloudy.night { --page-bg: linear-gradient(135deg, #232526 0%, #414345 100%); }
+body.cond-cloudy.day { --page-bg: linear-gradient(135deg, #bdc3c7 0%, #7f8c8d 100%); }
+body.cond-cloudy.night { --page-bg: linear-gradient(135deg, #2c3e50 0%, #4b5563 100%); }
+body.cond-drizzle.day, body.cond-rain.day { --page-bg: linear-gradient(135deg, #4b6cb7 0%, #182848 100%); }
+body.cond-drizzle.night, body.cond-rain.night { --page-bg: linear-gradient(135deg, #141e30 0%, #243b55 100%); }
+body.cond-thunderstorm { --page-bg: linear-gradient(135deg, #373b44 0%, #4286f4 100%); }
+body.cond-snow.day { --page-bg: linear-gradient(135deg, #e6e9f0 0%, #a1c4fd 100%); }
+body.cond-snow.night { --page-bg: linear-gradient(135deg, #536976 0%, #292e49 100%); }
+body.cond-mist { --page-bg: linear-gradient(135deg, #757f9a 0%, #d7dde8 100%); }
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn test_from_file_rejects_closing_style_tags_in_any_case() {
+        let dir = env::temp_dir().join(format!("weather-themes-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let path = dir.join("Ocean.css");
+        fs::write(&path, ":root { --accent: #2193b0; }\n").unwrap();
+        assert_eq!(Theme::from_file(&path).unwrap().name, "ocean");
+
+        for tag in ["</style>", "</STYLE>", "</StYlE >"] {
+            fs::write(&path, format!(":root {{}}\n{}<script>alert(1)</script>", tag)).unwrap();
+            assert!(Theme::from_file(&path).is_err(), "{}", tag);
+        }
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
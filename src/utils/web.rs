@@ -1,8 +1,17 @@
+use crate::models::forecast::ForecastResponse;
 use crate::models::weather::{WeatherResponse, TemperatureUnit};
+use crate::utils::charts::{self, ChartMetric};
+use crate::utils::condition;
 use crate::utils::formatters::*;
+use crate::utils::themes::{self, Theme};
 
-pub fn generate_html(weather: &WeatherResponse, unit: &TemperatureUnit) -> String {
-    let weather_data = &weather.weather[0];
+pub fn generate_html(
+    weather: &WeatherResponse,
+    unit: &TemperatureUnit,
+    forecast: Option<&ForecastResponse>,
+    theme: &Theme,
+) -> String {
+    let condition = weather.weather.first();
     let temp_c = weather.main.temp - 273.15;
     let temp_min = weather.main.temp_min - 273.15;
     let temp_max = weather.main.temp_max - 273.15;
@@ -40,8 +49,28 @@ pub fn generate_html(weather: &WeatherResponse, unit: &TemperatureUnit) -> Strin
     } else {
         weather.name.clone()
     };
+    let location = escape_html(&location);
 
-    let emoji_map = get_emoji(&weather_data.icon);
+    let emoji_map = condition.map_or("🌈", |c| condition::emoji(c.id, &c.icon));
+    let theme_css = theme.stylesheet();
+    let body_class = themes::condition_classes(weather);
+
+    let forecast_section = match forecast {
+        Some(forecast) => {
+            let charts: String = ChartMetric::ALL
+                .iter()
+                .map(|m| format!(
+                    "<div class=\"chart-box\">{}</div>\n",
+                    charts::render_chart(*m, forecast, unit)
+                ))
+                .collect();
+            format!(
+                "<div class=\"section\">\n<div class=\"section-title\">📈 Forecast</div>\n{}</div>",
+                charts
+            )
+        }
+        None => String::new(),
+    };
 
     format!(r#"
 <!DOCTYPE html>
@@ -49,7 +78,10 @@ pub fn generate_html(weather: &WeatherResponse, unit: &TemperatureUnit) -> Strin
 <head>
     <meta charset="UTF-8">
     <meta name="viewport" content="width=device-width, initial-scale=1.0">
-    <title>Weather - {}</title>
+    <title>Weather - {location}</title>
+    <style>
+{theme_css}
+    </style>
     <style>
         * {{
             margin: 0;
@@ -59,7 +91,8 @@ pub fn generate_html(weather: &WeatherResponse, unit: &TemperatureUnit) -> Strin
         
         body {{
             font-family: 'Segoe UI', Tahoma, Geneva, Verdana, sans-serif;
-            background: linear-gradient(135deg, #667eea 0%, #764ba2 100%);
+            background: var(--page-bg);
+            color: var(--text);
             min-height: 100vh;
             display: flex;
             justify-content: center;
@@ -68,17 +101,17 @@ pub fn generate_html(weather: &WeatherResponse, unit: &TemperatureUnit) -> Strin
         }}
         
         .container {{
-            background: white;
+            background: var(--card-bg);
             border-radius: 20px;
-            box-shadow: 0 20px 60px rgba(0, 0, 0, 0.3);
+            box-shadow: var(--shadow);
             max-width: 800px;
             width: 100%;
             overflow: hidden;
         }}
         
         .header {{
-            background: linear-gradient(135deg, #667eea 0%, #764ba2 100%);
-            color: white;
+            background: var(--header-bg);
+            color: var(--header-text);
             padding: 40px 20px;
             text-align: center;
         }}
@@ -104,8 +137,8 @@ pub fn generate_html(weather: &WeatherResponse, unit: &TemperatureUnit) -> Strin
             align-items: center;
             justify-content: space-around;
             padding: 40px 20px;
-            background: linear-gradient(to right, rgba(102, 126, 234, 0.1), rgba(118, 75, 162, 0.1));
-            border-bottom: 2px solid #ecf0f1;
+            background: var(--hero-bg);
+            border-bottom: 2px solid var(--border);
         }}
         
         .weather-icon {{
@@ -119,13 +152,13 @@ pub fn generate_html(weather: &WeatherResponse, unit: &TemperatureUnit) -> Strin
         .temperature {{
             font-size: 3.5em;
             font-weight: bold;
-            color: {};
+            color: {temp_color};
             margin: 10px 0;
         }}
         
         .condition {{
             font-size: 1.3em;
-            color: #2c3e50;
+            color: var(--text);
             text-transform: capitalize;
         }}
         
@@ -137,15 +170,15 @@ pub fn generate_html(weather: &WeatherResponse, unit: &TemperatureUnit) -> Strin
         }}
         
         .detail-item {{
-            background: #f8f9fa;
+            background: var(--panel-bg);
             padding: 20px;
             border-radius: 10px;
-            border-left: 4px solid #667eea;
+            border-left: 4px solid var(--accent);
         }}
         
         .detail-label {{
             font-size: 0.9em;
-            color: #7f8c8d;
+            color: var(--muted);
             text-transform: uppercase;
             letter-spacing: 1px;
             margin-bottom: 8px;
@@ -154,7 +187,7 @@ pub fn generate_html(weather: &WeatherResponse, unit: &TemperatureUnit) -> Strin
         .detail-value {{
             font-size: 1.5em;
             font-weight: bold;
-            color: #2c3e50;
+            color: var(--text);
         }}
         
         .row {{
@@ -175,10 +208,10 @@ pub fn generate_html(weather: &WeatherResponse, unit: &TemperatureUnit) -> Strin
         .section-title {{
             font-size: 1.3em;
             font-weight: bold;
-            color: #2c3e50;
+            color: var(--text);
             margin-bottom: 15px;
             padding-bottom: 10px;
-            border-bottom: 2px solid #667eea;
+            border-bottom: 2px solid var(--accent);
         }}
         
         .sun-moon {{
@@ -189,10 +222,10 @@ pub fn generate_html(weather: &WeatherResponse, unit: &TemperatureUnit) -> Strin
         }}
         
         .sun-moon-item {{
-            background: linear-gradient(135deg, #ffd89b 0%, #ff6b6b 100%);
+            background: var(--sunrise-bg);
             padding: 20px;
             border-radius: 10px;
-            color: white;
+            color: var(--sun-text);
             text-align: center;
         }}
         
@@ -203,15 +236,15 @@ pub fn generate_html(weather: &WeatherResponse, unit: &TemperatureUnit) -> Strin
         }}
         
         .footer {{
-            background: #ecf0f1;
+            background: var(--footer-bg);
             padding: 20px;
             text-align: center;
             font-size: 0.9em;
-            color: #7f8c8d;
+            color: var(--muted);
         }}
         
         .progress-bar {{
-            background: #ecf0f1;
+            background: var(--border);
             height: 8px;
             border-radius: 4px;
             overflow: hidden;
@@ -219,9 +252,16 @@ pub fn generate_html(weather: &WeatherResponse, unit: &TemperatureUnit) -> Strin
         }}
         
         .progress-fill {{
-            background: linear-gradient(to right, #667eea, #764ba2);
+            background: linear-gradient(to right, var(--accent), var(--accent-2));
             height: 100%;
-            width: {}%;
+            width: {cloudiness}%;
+        }}
+        
+        .chart-box {{
+            background: var(--panel-bg);
+            border-radius: 10px;
+            padding: 10px;
+            margin: 0 20px 20px;
         }}
         
         @media (max-width: 600px) {{
@@ -251,20 +291,20 @@ pub fn generate_html(weather: &WeatherResponse, unit: &TemperatureUnit) -> Strin
         }}
     </style>
 </head>
-<body>
+<body class="{body_class}">
     <div class="container">
         <div class="header">
-            <h1>{}</h1>
-            <div class="location">{}</div>
-            <div class="coords">📍 {:.3}°N, {:.3}°E | {}</div>
+            <h1>🌤️ Weather</h1>
+            <div class="location">{location}</div>
+            <div class="coords">📍 {lat:.3}°N, {lon:.3}°E | {timezone_str}</div>
         </div>
         
         <div class="main-weather">
-            <div class="weather-icon">{}</div>
+            <div class="weather-icon">{emoji_map}</div>
             <div class="weather-main">
-                <div class="condition">{}</div>
-                <div class="temperature">{}</div>
-                <div style="color: #7f8c8d; margin-top: 5px;">Feels like: {}</div>
+                <div class="condition">{description}</div>
+                <div class="temperature">{temp_display}</div>
+                <div style="color: var(--muted); margin-top: 5px;">Feels like: {feels_like}</div>
             </div>
         </div>
         
@@ -274,19 +314,19 @@ pub fn generate_html(weather: &WeatherResponse, unit: &TemperatureUnit) -> Strin
                 <div class="row">
                     <div class="detail-item">
                         <div class="detail-label">Current</div>
-                        <div class="detail-value">{}</div>
+                        <div class="detail-value">{temp_display}</div>
                     </div>
                     <div class="detail-item">
                         <div class="detail-label">Feels Like</div>
-                        <div class="detail-value">{}</div>
+                        <div class="detail-value">{feels_like}</div>
                     </div>
                     <div class="detail-item">
                         <div class="detail-label">Min</div>
-                        <div class="detail-value">{:.1}°</div>
+                        <div class="detail-value">{temp_min:.1}°</div>
                     </div>
                     <div class="detail-item">
                         <div class="detail-label">Max</div>
-                        <div class="detail-value">{:.1}°</div>
+                        <div class="detail-value">{temp_max:.1}°</div>
                     </div>
                 </div>
             </div>
@@ -296,30 +336,30 @@ pub fn generate_html(weather: &WeatherResponse, unit: &TemperatureUnit) -> Strin
                 <div class="row">
                     <div class="detail-item">
                         <div class="detail-label">💧 Humidity</div>
-                        <div class="detail-value">{}%</div>
+                        <div class="detail-value">{humidity}%</div>
                     </div>
                     <div class="detail-item">
                         <div class="detail-label">🎈 Pressure</div>
-                        <div class="detail-value">{} hPa</div>
+                        <div class="detail-value">{pressure} hPa</div>
                     </div>
                     <div class="detail-item">
                         <div class="detail-label">💨 Wind Speed</div>
-                        <div class="detail-value">{:.1} m/s</div>
+                        <div class="detail-value">{wind_speed:.1} m/s</div>
                     </div>
                     <div class="detail-item">
                         <div class="detail-label">🧭 Direction</div>
-                        <div class="detail-value">{}</div>
+                        <div class="detail-value">{wind_direction}</div>
                     </div>
                     <div class="detail-item full-width">
                         <div class="detail-label">☁️ Cloudiness</div>
-                        <div class="detail-value">{}%</div>
+                        <div class="detail-value">{cloudiness}%</div>
                         <div class="progress-bar">
-                            <div class="progress-fill" style="width: {}%;"></div>
+                            <div class="progress-fill" style="width: {cloudiness}%;"></div>
                         </div>
                     </div>
                     <div class="detail-item full-width">
                         <div class="detail-label">👁️ Visibility</div>
-                        <div class="detail-value">{:.1} km</div>
+                        <div class="detail-value">{visibility_km:.1} km</div>
                     </div>
                 </div>
             </div>
@@ -329,64 +369,37 @@ pub fn generate_html(weather: &WeatherResponse, unit: &TemperatureUnit) -> Strin
                 <div class="sun-moon">
                     <div class="sun-moon-item">
                         <div>🌅 Sunrise</div>
-                        <div class="sun-moon-time">{}</div>
+                        <div class="sun-moon-time">{sunrise}</div>
                     </div>
-                    <div class="sun-moon-item" style="background: linear-gradient(135deg, #667eea 0%, #764ba2 100%);">
+                    <div class="sun-moon-item" style="background: var(--sunset-bg);">
                         <div>🌇 Sunset</div>
-                        <div class="sun-moon-time">{}</div>
+                        <div class="sun-moon-time">{sunset}</div>
                     </div>
                 </div>
             </div>
+            
+            {forecast_section}
         </div>
         
         <div class="footer">
             <p>🌤️ Weather CLI | 🔗 Powered by OpenWeatherMap API</p>
-            <p>Last updated: {}</p>
+            <p>Last updated: {updated}</p>
         </div>
     </div>
 </body>
 </html>
 "#,
-        location,
-        emoji_map,
-        location,
-        location,
-        weather.coord.lat,
-        weather.coord.lon,
-        timezone_str,
-        emoji_map,
-        weather_data.description,
-        temp_display,
-        feels_like,
-        temp_display,
-        feels_like,
-        temp_min,
-        temp_max,
-        weather.main.humidity,
-        weather.main.pressure,
-        weather.wind.speed,
-        format_wind_direction(weather.wind.deg),
-        weather.clouds.all,
-        weather.clouds.all,
-        temp_color,
-        weather.visibility.unwrap_or(10000) as f64 / 1000.0,
-        format_sun_time(weather.sys.sunrise, weather.timezone),
-        format_sun_time(weather.sys.sunset, weather.timezone),
-        chrono::Utc::now().format("%Y-%m-%d %H:%M:%S UTC"),
+        lat = weather.coord.lat,
+        lon = weather.coord.lon,
+        description = escape_html(&weather.description()),
+        humidity = weather.main.humidity,
+        pressure = weather.main.pressure,
+        wind_speed = weather.wind.speed,
+        wind_direction = format_wind_direction(weather.wind.deg),
+        cloudiness = weather.clouds.all,
+        visibility_km = weather.visibility.unwrap_or(10000) as f64 / 1000.0,
+        sunrise = format_sun_time(weather.sys.sunrise, weather.timezone),
+        sunset = format_sun_time(weather.sys.sunset, weather.timezone),
+        updated = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S UTC"),
     )
 }
-
-fn get_emoji(icon: &str) -> &'static str {
-    match icon {
-        "01d" => "☀️",
-        "01n" => "🌙",
-        "02d" | "02n" => "⛅",
-        "03d" | "03n" | "04d" | "04n" => "☁️",
-        "09d" | "09n" => "🌧️",
-        "10d" | "10n" => "🌦️",
-        "11d" | "11n" => "⛈️",
-        "13d" | "13n" => "❄️",
-        "50d" | "50n" => "🌫️",
-        _ => "🌈",
-    }
-}
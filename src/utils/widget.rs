@@ -0,0 +1,180 @@
+use crate::models::weather::{WeatherResponse, TemperatureUnit};
+use crate::utils::condition;
+use crate::utils::formatters::*;
+use crate::utils::themes::Theme;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WidgetSize {
+    Small,
+    Medium,
+    Large,
+}
+
+impl WidgetSize {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "small" | "s" => Some(WidgetSize::Small),
+            "medium" | "m" => Some(WidgetSize::Medium),
+            "large" | "l" => Some(WidgetSize::Large),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            WidgetSize::Small => "small",
+            WidgetSize::Medium => "medium",
+            WidgetSize::Large => "large",
+        }
+    }
+
+    /// Iframe dimensions in pixels (width, height).
+    pub fn dimensions(&self) -> (u32, u32) {
+        match self {
+            WidgetSize::Small => (220, 90),
+            WidgetSize::Medium => (300, 150),
+            WidgetSize::Large => (380, 210),
+        }
+    }
+}
+
+/// Minimal standalone page meant to be loaded inside an iframe.
+pub fn generate_widget(
+    weather: &WeatherResponse,
+    unit: &TemperatureUnit,
+    theme: &Theme,
+    size: WidgetSize,
+) -> String {
+    let condition = weather.weather.first();
+    let (width, height) = size.dimensions();
+    let scale = match size {
+        WidgetSize::Small => 0.8,
+        WidgetSize::Medium => 1.0,
+        WidgetSize::Large => 1.2,
+    };
+
+    let details = match size {
+        WidgetSize::Small => String::new(),
+        _ => format!(
+            r#"<div class="details">💧 {} · 💨 {:.1} m/s {} · 🎈 {}</div>"#,
+            format_humidity(weather.main.humidity),
+            weather.wind.speed,
+            format_wind_direction(weather.wind.deg),
+            format_pressure(weather.main.pressure)
+        ),
+    };
+
+    let sun = match size {
+        WidgetSize::Large => format!(
+            r#"<div class="details">🌅 {} · 🌇 {}</div>"#,
+            format_sun_time(weather.sys.sunrise, weather.timezone),
+            format_sun_time(weather.sys.sunset, weather.timezone)
+        ),
+        _ => String::new(),
+    };
+
+    format!(r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="UTF-8">
+<meta name="viewport" content="width=device-width, initial-scale=1.0">
+<title>Weather - {name}</title>
+<style>
+{theme_css}
+</style>
+<style>
+* {{ margin: 0; padding: 0; box-sizing: border-box; }}
+html, body {{ background: transparent; }}
+body {{ font-family: 'Segoe UI', Tahoma, Geneva, Verdana, sans-serif; font-size: {scale}em; }}
+.widget {{ width: {width}px; height: {height}px; background: var(--card-bg); color: var(--text); border-radius: 12px; overflow: hidden; border-top: 4px solid var(--accent); padding: 10px 14px; }}
+.location {{ font-weight: bold; background: linear-gradient(135deg, var(--accent) 0%, var(--accent-2) 100%); -webkit-background-clip: text; background-clip: text; color: transparent; }}
+.main {{ display: flex; align-items: center; gap: 10px; margin-top: 4px; }}
+.icon {{ font-size: 2em; }}
+.temperature {{ font-size: 1.6em; font-weight: bold; }}
+.condition {{ color: var(--muted); text-transform: capitalize; font-size: 0.85em; }}
+.details {{ color: var(--muted); font-size: 0.8em; margin-top: 6px; }}
+</style>
+</head>
+<body>
+<div class="widget theme-{theme} size-{size}">
+<div class="location">{name}</div>
+<div class="main">
+<div class="icon">{emoji}</div>
+<div>
+<div class="temperature">{temperature}</div>
+<div class="condition">{description}</div>
+</div>
+</div>
+{details}
+{sun}
+</div>
+</body>
+</html>
+"#,
+        name = escape_html(&location_name(weather)),
+        theme_css = theme.css,
+        theme = theme.name,
+        size = size.name(),
+        emoji = condition.map_or("🌈", |c| condition::emoji(c.id, &c.icon)),
+        temperature = weather.format_temperature(unit),
+        description = escape_html(&weather.description()),
+    )
+}
+
+/// Loader script: replaces every `<div class="weather-widget" data-city="...">`
+/// on the host page with an iframe pointing back at this server.
+pub const WIDGET_LOADER_JS: &str = r#"(function () {
+  var script = document.currentScript;
+  var origin = new URL(script.src).origin;
+  var sizes = { small: [220, 90], medium: [300, 150], large: [380, 210] };
+  document.querySelectorAll('.weather-widget').forEach(function (el) {
+    var city = el.getAttribute('data-city');
+    if (!city) { return; }
+    var size = el.getAttribute('data-size') || 'medium';
+    var params = new URLSearchParams({ city: city, size: size });
+    ['theme', 'unit'].forEach(function (key) {
+      var value = el.getAttribute('data-' + key);
+      if (value) { params.set(key, value); }
+    });
+    var dims = sizes[size] || sizes.medium;
+    var frame = document.createElement('iframe');
+    frame.src = origin + '/widget?' + params.toString();
+    frame.width = dims[0];
+    frame.height = dims[1];
+    frame.title = 'Weather in ' + city;
+    frame.style.border = '0';
+    frame.loading = 'lazy';
+    el.replaceWith(frame);
+  });
+})();
+"#;
+
+/// oEmbed "rich" response wrapping the widget iframe.
+pub fn oembed_json(widget_url: &str, title: &str, size: WidgetSize) -> serde_json::Value {
+    let (width, height) = size.dimensions();
+    let html = format!(
+        r#"<iframe src="{}" width="{}" height="{}" frameborder="0" title="{}"></iframe>"#,
+        escape_html(widget_url),
+        width,
+        height,
+        escape_html(title)
+    );
+
+    serde_json::json!({
+        "version": "1.0",
+        "type": "rich",
+        "provider_name": "Weather CLI",
+        "title": title,
+        "html": html,
+        "width": width,
+        "height": height,
+    })
+}
+
+fn location_name(weather: &WeatherResponse) -> String {
+    if !weather.sys.country.is_empty() {
+        format!("{}, {}", weather.name, weather.sys.country)
+    } else {
+        weather.name.clone()
+    }
+}
@@ -0,0 +1,204 @@
+//! End-to-end tests of the `weather` binary against the recorded provider
+//! responses in `tests/fixtures/http`, so they run without a network or a
+//! real API key. Record new responses with
+//! `WEATHER_RECORD_DIR=tests/fixtures/http weather <city>`.
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+
+fn weather(args: &[&str]) -> Output {
+    weather_with(args, &[])
+}
+
+fn weather_with(args: &[&str], env: &[(&str, &str)]) -> Output {
+    let tests = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests");
+    let mut command = Command::new(env!("CARGO_BIN_EXE_weather"));
+    for (name, _) in std::env::vars() {
+        if name.starts_with("WEATHER_") || name == "OPENWEATHER_API_KEY" {
+            command.env_remove(name);
+        }
+    }
+    command
+        .args(args)
+        .env("WEATHER_REPLAY_DIR", tests.join("fixtures/http"))
+        .env("WEATHER_API_KEY", "replay")
+        // Keep the user's config, profiles and aliases out of the tests
+        .env("WEATHER_CONFIG", tests.join("fixtures/http/missing-config.toml"))
+        .env_remove("TERM")
+        .envs(env.iter().copied())
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn minimal_template_prints_one_plain_line() {
+    let output = weather(&["Berlin", "--template", "minimal"]);
+    assert_eq!(output.status.code(), Some(0));
+
+    let text = stdout(&output);
+    assert!(text.contains("🌥️ broken clouds 14.3°C in Berlin | 😎 Cool"), "{}", text);
+    assert!(!text.contains('\x1b'), "piped output should have no colors");
+}
+
+#[test]
+fn compact_template_charts_the_recorded_forecast() {
+    let output = weather(&["Berlin", "-t", "compact", "-u", "fahrenheit"]);
+    assert_eq!(output.status.code(), Some(0));
+
+    let text = stdout(&output);
+    assert!(text.contains("57.7°F"), "{}", text);
+    assert!(text.contains("next 5 days"), "{}", text);
+}
+
+#[test]
+fn art_template_draws_the_recorded_forecast() {
+    let output = weather(&["Berlin", "-t", "art"]);
+    assert_eq!(output.status.code(), Some(0));
+
+    let text = stdout(&output);
+    assert!(text.contains(".--.      14.3°C (feels 13.6°C)"), "{}", text);
+    assert!(text.contains("FORECAST") && text.contains("Sat 18"), "{}", text);
+}
+
+#[test]
+fn ascii_output_is_plain() {
+    let flagged = weather(&["Berlin", "--ascii", "-t", "detailed"]);
+    assert_eq!(flagged.status.code(), Some(0));
+    let text = stdout(&flagged);
+    assert!(text.is_ascii() && text.contains("+=="), "{}", text);
+
+    let dumb = weather_with(&["Berlin", "London"], &[("TERM", "dumb")]);
+    assert_eq!(dumb.status.code(), Some(0));
+    let text = stdout(&dumb);
+    assert!(text.is_ascii() && text.contains("[cloudy] broken clouds"), "{}", text);
+}
+
+#[test]
+fn unknown_city_fails() {
+    let output = weather(&["Atlantis"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output).contains("City 'Atlantis' not found"));
+}
+
+#[test]
+fn locations_rm_and_rename_find_mixed_case_aliases() {
+    let path = std::env::temp_dir().join(format!("weather-cli-locations-{}.toml", std::process::id()));
+    fs::write(
+        &path,
+        "[locations.Home]\nname = \"Berlin\"\nlat = 52.52\nlon = 13.405\n\n\
+         [locations.Office]\nname = \"London\"\nlat = 51.5085\nlon = -0.1257\n",
+    )
+    .unwrap();
+    let config = [("WEATHER_CONFIG", path.to_str().unwrap())];
+
+    let renamed = weather_with(&["locations", "rename", "home", "base"], &config);
+    let removed = weather_with(&["locations", "rm", "OFFICE"], &config);
+    let saved = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(renamed.status.code(), Some(0), "{}", stdout(&renamed));
+    assert_eq!(removed.status.code(), Some(0), "{}", stdout(&removed));
+    assert!(saved.contains("[locations.base]"), "{}", saved);
+    assert!(!saved.contains("Home") && !saved.contains("Office"), "{}", saved);
+}
+
+#[test]
+fn config_list_masks_webhook_urls() {
+    let path = std::env::temp_dir().join(format!("weather-cli-webhooks-{}.toml", std::process::id()));
+    fs::write(
+        &path,
+        "[[notify.channels]]\nname = \"team\"\nkind = \"slack\"\nurl = \"https://hooks.slack.com/services/T0/B0/SECRET\"\n",
+    )
+    .unwrap();
+    let config = [("WEATHER_CONFIG", path.to_str().unwrap())];
+
+    let list = weather_with(&["config", "list"], &config);
+    let get = weather_with(&["config", "get", "notify.channels"], &config);
+    fs::remove_file(&path).unwrap();
+
+    for output in [list, get] {
+        let text = stdout(&output);
+        assert_eq!(output.status.code(), Some(0), "{}", text);
+        assert!(text.contains("https://hooks.slack.com/…") && !text.contains("SECRET"), "{}", text);
+    }
+}
+
+#[test]
+fn out_of_range_port_is_a_config_error() {
+    let output = weather_with(&["Berlin"], &[("WEATHER_PORT", "70000")]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output).contains("WEATHER_PORT must be a port from 0 to 65535, got '70000'"));
+}
+
+#[test]
+fn cache_ttl_below_the_minimum_is_rejected() {
+    let flag = weather(&["Berlin", "--cache-ttl", "0"]);
+    assert_eq!(flag.status.code(), Some(1));
+    assert!(stdout(&flag).contains("Cache TTL must be at least 10s"), "{}", stdout(&flag));
+
+    let env = weather_with(&["Berlin"], &[("WEATHER_CACHE_TTL", "5")]);
+    assert_eq!(env.status.code(), Some(1));
+    assert!(stdout(&env).contains("Cache TTL must be at least 10s"), "{}", stdout(&env));
+}
+
+#[test]
+fn unrecorded_request_fails_without_network() {
+    let output = weather(&["Paris"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output).contains("No recorded response"));
+}
+
+#[test]
+fn compare_keeps_going_after_a_failure() {
+    let output = weather(&["Berlin", "London", "Atlantis"]);
+    assert_eq!(output.status.code(), Some(0));
+
+    let text = stdout(&output);
+    assert!(text.contains("Berlin, DE") && text.contains("London, GB"), "{}", text);
+    assert!(text.contains("Atlantis: City 'Atlantis' not found"), "{}", text);
+}
+
+#[test]
+fn check_exit_codes() {
+    let matched = weather(&["check", "Berlin", "--temp-above", "10"]);
+    assert_eq!(matched.status.code(), Some(1));
+    assert!(stdout(&matched).contains("1 of 1 rules matched"));
+
+    let clear = weather(&["check", "London", "--snow", "--quiet"]);
+    assert_eq!(clear.status.code(), Some(0));
+    assert_eq!(stdout(&clear), "");
+
+    let failed = weather(&["check", "Atlantis", "--rain"]);
+    assert_eq!(failed.status.code(), Some(2));
+}
+
+#[test]
+fn cities_named_like_subcommands_follow_a_double_dash() {
+    let subcommand = weather(&["watch", "--help"]);
+    assert!(stdout(&subcommand).contains("Usage: weather watch"), "{}", stdout(&subcommand));
+
+    let city = weather(&["--template", "minimal", "--", "watch"]);
+    assert!(stdout(&city).contains("Fetching weather data for 'watch'"), "{}", stdout(&city));
+
+    let compared = weather(&["--", "check", "Berlin"]);
+    assert!(stdout(&compared).contains("Fetching weather data for 2 locations"), "{}", stdout(&compared));
+}
+
+#[test]
+fn check_output_follows_the_charset() {
+    let flagged = weather(&["check", "Berlin", "--temp-above", "10", "--ascii"]);
+    let text = stdout(&flagged);
+    assert!(text.is_ascii() && text.contains("temp > 10C"), "{}", text);
+
+    let dumb = weather_with(&["check", "Berlin", "--hours", "12", "--rain"], &[("TERM", "dumb")]);
+    let text = stdout(&dumb);
+    assert!(text.is_ascii() && text.contains("Checking"), "{}", text);
+
+    let unicode = weather(&["check", "Berlin", "--temp-above", "10"]);
+    assert!(stdout(&unicode).contains("🔎"));
+}
@@ -0,0 +1,97 @@
+//! Fixture corpus and golden files shared by the snapshot tests.
+//!
+//! Fixtures are recorded provider responses in `tests/fixtures/*.json`; every
+//! file there is picked up by the tests. Rendered output is compared with
+//! `tests/snapshots/<fixture>.<case>`. To accept changed output, review the
+//! diff and rerun with `BLESS=1 cargo test`; missing snapshots are always
+//! written, and fail the run so they get a look before being committed.
+
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use weather_cl::WeatherResponse;
+
+fn tests_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests")
+}
+
+/// Every fixture as (name, response), sorted by name.
+pub fn fixtures() -> Vec<(String, WeatherResponse)> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(tests_dir().join("fixtures"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|e| e == "json"))
+        .collect();
+    paths.sort();
+
+    paths
+        .iter()
+        .map(|path| {
+            let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+            let weather = serde_json::from_str(&fs::read_to_string(path).unwrap())
+                .unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+            (name, weather)
+        })
+        .collect()
+}
+
+pub fn fixture(name: &str) -> WeatherResponse {
+    let path = tests_dir().join("fixtures").join(format!("{}.json", name));
+    serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap()
+}
+
+fn blessing() -> bool {
+    std::env::var_os("BLESS").is_some_and(|v| !v.is_empty() && v != "0")
+}
+
+/// Compare `actual` with the snapshot `name`, or record it when blessing.
+pub fn assert_snapshot(name: &str, actual: &str) {
+    let path = tests_dir().join("snapshots").join(name);
+    match fs::read_to_string(&path) {
+        Ok(expected) if expected == actual => {}
+        Ok(_) if blessing() => fs::write(&path, actual).unwrap(),
+        Ok(expected) => {
+            let line = expected.lines().zip(actual.lines()).position(|(e, a)| e != a);
+            panic!(
+                "output differs from {} (first difference on line {}), rerun with BLESS=1 to accept it",
+                path.display(),
+                line.map_or_else(|| expected.lines().count().min(actual.lines().count()) + 1, |l| l + 1)
+            );
+        }
+        Err(_) => {
+            fs::write(&path, actual).unwrap();
+            if !blessing() {
+                panic!("wrote new snapshot {}, review it and run the tests again", path.display());
+            }
+        }
+    }
+}
+
+/// Replace "YYYY-MM-DD HH:MM:SS UTC" stamps of the render time, which
+/// differ on every run.
+pub fn redact_timestamps(text: &str) -> String {
+    const SHAPE: &[u8] = b"0000-00-00 00:00:00 UTC";
+    let bytes = text.as_bytes();
+    let matches_at = |i: usize| {
+        bytes.len() >= i + SHAPE.len()
+            && SHAPE.iter().zip(&bytes[i..]).all(|(s, b)| match s {
+                b'0' => b.is_ascii_digit(),
+                _ => s == b,
+            })
+    };
+
+    let mut out = String::with_capacity(text.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if matches_at(i) {
+            out.push_str("[timestamp]");
+            i += SHAPE.len();
+        } else {
+            let ch = text[i..].chars().next().unwrap();
+            out.push(ch);
+            i += ch.len_utf8();
+        }
+    }
+    out
+}
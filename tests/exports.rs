@@ -0,0 +1,98 @@
+//! Snapshot tests for the machine-facing outputs: the HTML page, the SVG
+//! card, the embeddable widgets, the Prometheus gauges and the MQTT state,
+//! over every fixture.
+
+mod common;
+
+use common::{assert_snapshot, fixture, fixtures, redact_timestamps};
+use weather_cl::utils::export::{write_to, Format};
+use weather_cl::utils::metrics::write_gauges;
+use weather_cl::utils::themes::ThemeRegistry;
+use weather_cl::utils::widget::{generate_widget, WidgetSize};
+use weather_cl::TemperatureUnit;
+
+#[test]
+fn html_pages_match_snapshots() {
+    let themes = ThemeRegistry::default();
+    for (fixture, weather) in fixtures() {
+        let mut page = Vec::new();
+        write_to(&mut page, Format::Html, &weather, None, &TemperatureUnit::Celsius, themes.get(None)).unwrap();
+        let page = redact_timestamps(&String::from_utf8(page).unwrap());
+        assert_snapshot(&format!("{}.page.html", fixture), &page);
+    }
+}
+
+#[test]
+fn svg_cards_match_snapshots() {
+    let themes = ThemeRegistry::default();
+    for (fixture, weather) in fixtures() {
+        let mut card = Vec::new();
+        write_to(&mut card, Format::Svg, &weather, None, &TemperatureUnit::Fahrenheit, themes.get(None)).unwrap();
+        assert_snapshot(&format!("{}.card.svg", fixture), &String::from_utf8(card).unwrap());
+    }
+}
+
+#[test]
+fn widgets_match_snapshots() {
+    let themes = ThemeRegistry::default();
+    for (fixture, weather) in fixtures() {
+        for size in [WidgetSize::Small, WidgetSize::Medium, WidgetSize::Large] {
+            let widget = generate_widget(&weather, &TemperatureUnit::Celsius, themes.get(None), size);
+            assert_snapshot(&format!("{}.widget-{}.html", fixture, size.name()), &widget);
+        }
+    }
+}
+
+#[test]
+fn markup_in_provider_text_is_escaped() {
+    let themes = ThemeRegistry::default();
+    let mut weather = fixture("berlin");
+    weather.name = "<script>alert(1)</script>".to_string();
+    weather.weather[0].description = "rain & \"snow\"".to_string();
+
+    for format in [Format::Html, Format::Svg] {
+        let mut out = Vec::new();
+        write_to(&mut out, format, &weather, None, &TemperatureUnit::Celsius, themes.get(None)).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(!out.contains("<script>alert"), "{:?}", format);
+        assert!(out.contains("&lt;script&gt;alert(1)&lt;/script&gt;"), "{:?}", format);
+        assert!(out.contains("rain &amp; &quot;snow&quot;"), "{:?}", format);
+    }
+    let widget = generate_widget(&weather, &TemperatureUnit::Celsius, themes.get(None), WidgetSize::Small);
+    assert!(widget.contains("&lt;script&gt;alert(1)&lt;/script&gt;"));
+}
+
+#[test]
+fn weather_without_conditions_still_exports() {
+    let themes = ThemeRegistry::default();
+    let mut weather = fixture("berlin");
+    weather.weather.clear();
+
+    for format in [Format::Html, Format::Svg] {
+        let mut out = Vec::new();
+        write_to(&mut out, format, &weather, None, &TemperatureUnit::Celsius, themes.get(None)).unwrap();
+        assert!(String::from_utf8(out).unwrap().contains("Berlin"), "{:?}", format);
+    }
+    let widget = generate_widget(&weather, &TemperatureUnit::Celsius, themes.get(None), WidgetSize::Large);
+    assert!(widget.contains("🌈"));
+}
+
+#[test]
+fn metrics_match_snapshot() {
+    let fixtures = fixtures();
+    let samples: Vec<_> = fixtures.iter().map(|(fixture, weather)| (fixture.as_str(), weather)).collect();
+    let mut out = String::new();
+    write_gauges(&mut out, &samples, "openweathermap");
+    assert_snapshot("all.metrics.txt", &out);
+}
+
+#[cfg(feature = "mqtt")]
+#[test]
+fn mqtt_states_match_snapshots() {
+    use weather_cl::mqtt::discovery::state_payload;
+
+    for (fixture, weather) in fixtures() {
+        let state = serde_json::to_string_pretty(&state_payload(&weather, &TemperatureUnit::Celsius)).unwrap();
+        assert_snapshot(&format!("{}.mqtt.json", fixture), &(state + "\n"));
+    }
+}
@@ -0,0 +1,194 @@
+//! Snapshot tests for the terminal templates. Each template renders every
+//! fixture without color at a fixed width and must match
+//! `tests/snapshots/<fixture>.<template>.txt`.
+
+mod common;
+
+use chrono::{TimeZone, Utc};
+use common::{assert_snapshot, fixture, fixtures};
+use weather_cl::utils::display_templates::{DisplayTemplate, WeatherDisplay};
+use weather_cl::utils::glyphs::Charset;
+use weather_cl::utils::layout::width;
+use weather_cl::models::forecast::ForecastResponse;
+use weather_cl::utils::term_charts::{Trend, TrendPoint};
+use weather_cl::{TemperatureUnit, WeatherResponse};
+
+const TEMPLATES: [(&str, DisplayTemplate); 5] = [
+    ("default", DisplayTemplate::Default),
+    ("compact", DisplayTemplate::Compact),
+    ("detailed", DisplayTemplate::Detailed),
+    ("minimal", DisplayTemplate::Minimal),
+    ("art", DisplayTemplate::Art),
+];
+
+/// A day of 3-hourly points, warming up and drying out.
+fn trend() -> Trend {
+    Trend {
+        label: "next 5 days".to_string(),
+        points: (0..8)
+            .map(|i| TrendPoint {
+                time: 1_760_788_800 + i * 3 * 3600,
+                temp: 283.15 + i as f64,
+                pressure: 1010.0 + (i % 3) as f64,
+                humidity: 80.0 - 2.0 * i as f64,
+            })
+            .collect(),
+        timezone: 7200,
+    }
+}
+
+/// Five days of 3-hourly slots, one condition per day.
+fn forecast() -> ForecastResponse {
+    let days = [(800, "clear sky", "01"), (500, "light rain", "10"), (211, "thunderstorm", "11"), (601, "snow", "13"), (741, "fog", "50")];
+    let list: Vec<_> = (0..40)
+        .map(|i| {
+            let (id, description, icon) = days[i / 8];
+            serde_json::json!({
+                "dt": 1_760_745_600 + i as u64 * 3 * 3600,
+                "main": {
+                    "temp": 280.15 + i as f64 / 4.0,
+                    "feels_like": 279.15,
+                    "temp_min": 278.15 + (i / 8) as f64 + (i % 8) as f64,
+                    "temp_max": 281.15 + (i / 8) as f64 + (i % 8) as f64,
+                    "pressure": 1012,
+                    "humidity": 70
+                },
+                "weather": [{"id": id, "main": "", "description": description, "icon": format!("{}d", icon)}],
+                "clouds": {"all": 40},
+                "wind": {"speed": 3.0, "deg": 200},
+                "visibility": 10000,
+                "pop": (i % 8) as f64 / 10.0,
+                "rain": {"3h": (i / 8) as f64 * 0.5}
+            })
+        })
+        .collect();
+    serde_json::from_value(serde_json::json!({
+        "list": list,
+        "city": {"name": "Berlin", "coord": {"lat": 52.52, "lon": 13.41}, "country": "DE", "timezone": 0, "sunrise": 0, "sunset": 0}
+    }))
+    .unwrap()
+}
+
+fn render(weather: &WeatherResponse, template: DisplayTemplate, trend: Option<&Trend>) -> String {
+    render_at(weather, template, trend, 80, Charset::Unicode)
+}
+
+fn render_at(
+    weather: &WeatherResponse,
+    template: DisplayTemplate,
+    trend: Option<&Trend>,
+    columns: usize,
+    charset: Charset,
+) -> String {
+    let mut out = Vec::new();
+    WeatherDisplay::new(weather, TemperatureUnit::Celsius)
+        .with_template(template)
+        .with_trend(trend)
+        .with_updated(Utc.with_ymd_and_hms(2025, 10, 18, 12, 0, 0).unwrap())
+        .with_charset(charset)
+        .render_to(&mut out, false, columns)
+        .unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn templates_match_snapshots() {
+    for (fixture, weather) in fixtures() {
+        for (name, template) in TEMPLATES {
+            assert_snapshot(&format!("{}.{}.txt", fixture, name), &render(&weather, template, None));
+        }
+    }
+}
+
+#[test]
+fn trend_templates_match_snapshots() {
+    let weather = fixture("berlin");
+    let trend = trend();
+    for (name, template) in [("compact", DisplayTemplate::Compact), ("detailed", DisplayTemplate::Detailed)] {
+        assert_snapshot(&format!("berlin.{}.trend.txt", name), &render(&weather, template, Some(&trend)));
+    }
+}
+
+#[test]
+fn art_forecast_matches_snapshots() {
+    let weather = fixture("berlin");
+    let forecast = forecast();
+    for (columns, charset, name) in [(80, Charset::Unicode, "80"), (40, Charset::Unicode, "40"), (80, Charset::Ascii, "ascii")] {
+        let mut out = Vec::new();
+        WeatherDisplay::new(&weather, TemperatureUnit::Celsius)
+            .with_template(DisplayTemplate::Art)
+            .with_forecast(Some(&forecast))
+            .with_charset(charset)
+            .render_to(&mut out, false, columns)
+            .unwrap();
+        assert_snapshot(&format!("berlin.art.forecast.{}.txt", name), &String::from_utf8(out).unwrap());
+    }
+}
+
+#[test]
+fn uncolored_output_has_no_escapes() {
+    for (_, weather) in fixtures() {
+        for (_, template) in TEMPLATES {
+            assert!(!render(&weather, template, None).contains('\x1b'));
+        }
+    }
+}
+
+#[test]
+fn weather_without_conditions_still_renders() {
+    let mut weather = fixture("berlin");
+    weather.weather.clear();
+    for (name, template) in TEMPLATES {
+        let text = render(&weather, template, None);
+        assert!(text.contains("Berlin"), "{}:
+{}", name, text);
+        assert!(render_at(&weather, template, None, 80, Charset::Ascii).is_ascii(), "{}", name);
+    }
+}
+
+#[test]
+fn ascii_templates_match_snapshots() {
+    let weather = fixture("berlin");
+    let trend = trend();
+    for (name, template) in TEMPLATES {
+        let text = render_at(&weather, template, Some(&trend), 80, Charset::Ascii);
+        assert_snapshot(&format!("berlin.{}.ascii.txt", name), &text);
+    }
+}
+
+#[test]
+fn ascii_templates_are_ascii() {
+    let trend = trend();
+    for (fixture, weather) in fixtures() {
+        for (name, template) in TEMPLATES {
+            let text = render_at(&weather, template, Some(&trend), 80, Charset::Ascii);
+            assert!(text.is_ascii(), "{} {}:\n{}", fixture, name, text);
+        }
+    }
+}
+
+#[test]
+fn boxes_line_up_at_any_width() {
+    let trend = trend();
+    for (fixture, weather) in fixtures() {
+        for template in [DisplayTemplate::Compact, DisplayTemplate::Detailed] {
+            for (columns, charset) in [30, 40, 55, 62, 120].into_iter().flat_map(|c| [(c, Charset::Unicode), (c, Charset::Ascii)]) {
+                let text = render_at(&weather, template, Some(&trend), columns, charset);
+                let widths: Vec<usize> = text
+                    .lines()
+                    .filter(|line| line.starts_with(['┌', '│', '└', '╔', '║', '╚', '+', '|']))
+                    .map(width)
+                    .collect();
+                assert!(
+                    widths.iter().all(|w| *w == widths[0] && *w <= columns.max(40)),
+                    "{} {:?} {:?} at {} columns:\n{}",
+                    fixture,
+                    template,
+                    charset,
+                    columns,
+                    text
+                );
+            }
+        }
+    }
+}